@@ -0,0 +1,212 @@
+//! Fingerprints the files in `assets/` for `src/assets.rs`: each one gets a
+//! URL carrying a hash of its contents, so it can be cached for good. Also
+//! generates the `asset!` macro, which only accepts files that exist, and
+//! warns about content pointing to missing files and assets nobody uses.
+//!
+//! Also embeds the build metadata served at `/__version`: the commit, the
+//! build time and the enabled features.
+
+use std::collections::BTreeSet;
+use std::fmt::Write as _;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const ASSETS_DIR: &str = "assets";
+const SOURCE_DIR: &str = "src";
+const CONTENT_DIR: &str = "content";
+
+/// FNV-1a, 64 bits: fast, and plenty to tell versions of a file apart.
+fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in bytes {
+        hash ^= u64::from(*byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Every file under `dir`, skipping hidden ones.
+fn files(dir: &Path, found: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.file_name().is_some_and(|name| name.to_string_lossy().starts_with('.')) {
+            continue;
+        }
+        if path.is_dir() {
+            files(&path, found)?;
+        } else {
+            found.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// `icons/rust_logo.png` -> `icons/rust_logo.<hash>.png`.
+fn fingerprint(relative: &str, hash: u64) -> String {
+    let (dir, name) = match relative.rsplit_once('/') {
+        Some((dir, name)) => (format!("{dir}/"), name),
+        None => (String::new(), relative),
+    };
+    match name.rsplit_once('.') {
+        Some((stem, extension)) if !stem.is_empty() => format!("{dir}{stem}.{hash:016x}.{extension}"),
+        _ => format!("{dir}{name}.{hash:016x}"),
+    }
+}
+
+/// The text after each `marker` up to the next `"`: with `asset!("`, the
+/// argument of every `asset!("...")`.
+fn after<'a>(text: &'a str, marker: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+    text.match_indices(marker).filter_map(move |(start, _)| {
+        let rest = &text[start + marker.len()..];
+        rest.find('"').map(|end| &rest[..end])
+    })
+}
+
+/// Paths passed to `asset!` in the source code.
+fn code_references() -> io::Result<BTreeSet<String>> {
+    let mut sources = Vec::new();
+    files(Path::new(SOURCE_DIR), &mut sources)?;
+    let mut found = BTreeSet::new();
+    for path in sources.iter().filter(|p| p.extension().is_some_and(|e| e == "rs")) {
+        let text = fs::read_to_string(path)?;
+        found.extend(after(&text, "asset!(\"").map(str::to_string));
+    }
+    Ok(found)
+}
+
+/// `(file, asset path)` for every asset a content file points to: paths in
+/// `/assets/` and skill icons, which live in `assets/icons`.
+fn content_references() -> io::Result<Vec<(PathBuf, String)>> {
+    let mut documents = Vec::new();
+    files(Path::new(CONTENT_DIR), &mut documents)?;
+    let mut found = Vec::new();
+    for path in documents
+        .iter()
+        .filter(|p| p.extension().is_some_and(|e| e == "md" || e == "toml"))
+    {
+        let text = fs::read_to_string(path)?;
+        found.extend(after(&text, "\"/assets/").map(|p| (path.clone(), format!("/assets/{p}"))));
+        found.extend(after(&text, "icon = \"").map(|p| (path.clone(), format!("/assets/icons/{p}.png"))));
+    }
+    Ok(found)
+}
+
+/// The checked-out commit: `GITHUB_SHA` in CI, otherwise asked to git, with
+/// `-dirty` when the tree has uncommitted changes.
+fn git_sha() -> String {
+    if let Ok(sha) = std::env::var("GITHUB_SHA") {
+        return sha;
+    }
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .args(args)
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+            .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+    };
+    match git(&["rev-parse", "HEAD"]) {
+        Some(sha) if git(&["status", "--porcelain"]).is_some_and(|s| !s.is_empty()) => format!("{sha}-dirty"),
+        Some(sha) => sha,
+        None => "unknown".to_string(),
+    }
+}
+
+/// Exposes the build metadata to the crate as `BUILD_*` variables for `env!`.
+fn build_info() {
+    println!("cargo:rerun-if-env-changed=GITHUB_SHA");
+    println!("cargo:rerun-if-env-changed=SOURCE_DATE_EPOCH");
+    // A new commit changes HEAD or the branch it points to
+    if let Ok(head) = fs::read_to_string(".git/HEAD") {
+        println!("cargo:rerun-if-changed=.git/HEAD");
+        if let Some(branch) = head.trim().strip_prefix("ref: ") {
+            println!("cargo:rerun-if-changed=.git/{branch}");
+        }
+    }
+
+    // Reproducible builds set the time themselves
+    let timestamp = std::env::var("SOURCE_DATE_EPOCH")
+        .ok()
+        .and_then(|epoch| epoch.trim().parse::<u64>().ok())
+        .unwrap_or_else(|| {
+            SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0, |elapsed| elapsed.as_secs())
+        });
+    let mut features = std::env::vars()
+        .filter_map(|(name, _)| {
+            let feature = name.strip_prefix("CARGO_FEATURE_")?;
+            Some(feature.to_lowercase().replace('_', "-"))
+        })
+        .collect::<Vec<_>>();
+    features.sort();
+
+    println!("cargo:rustc-env=BUILD_GIT_SHA={}", git_sha());
+    println!("cargo:rustc-env=BUILD_TIMESTAMP={timestamp}");
+    println!("cargo:rustc-env=BUILD_FEATURES={}", features.join(","));
+}
+
+fn main() -> io::Result<()> {
+    build_info();
+    println!("cargo:rerun-if-changed={ASSETS_DIR}");
+    println!("cargo:rerun-if-changed={SOURCE_DIR}");
+    println!("cargo:rerun-if-changed={CONTENT_DIR}");
+
+    let mut paths = Vec::new();
+    files(Path::new(ASSETS_DIR), &mut paths)?;
+    let mut manifest = paths
+        .iter()
+        .map(|path| {
+            let relative = path
+                .strip_prefix(ASSETS_DIR)
+                .unwrap_or(path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            let hash = fnv1a(&fs::read(path)?);
+            Ok((format!("/assets/{relative}"), format!("/assets/{}", fingerprint(&relative, hash))))
+        })
+        .collect::<io::Result<Vec<_>>>()?;
+    manifest.sort();
+
+    let mut code = String::from(
+        "// Generated by build.rs from `assets/`.\n\n/// `(path, fingerprinted path)` of every asset, sorted by path.\npub static MANIFEST: &[(&str, &str)] = &[\n",
+    );
+    for (path, hashed) in &manifest {
+        let _ = writeln!(code, "    ({path:?}, {hashed:?}),");
+    }
+    code.push_str("];\n\n");
+
+    code.push_str(concat!(
+        "/// Fingerprinted URL of a file in `assets/`, checked at compile time:\n",
+        "/// a path that is not there does not build.\n",
+        "macro_rules! asset {\n",
+    ));
+    for (path, hashed) in &manifest {
+        let _ = writeln!(code, "    ({path:?}) => {{ {hashed:?} }};");
+    }
+    code.push_str(concat!(
+        "    ($path:literal) => { compile_error!(concat!($path, \" is not in assets/\")) };\n",
+        "}\n",
+    ));
+
+    let known = manifest.iter().map(|(path, _)| path.as_str()).collect::<BTreeSet<_>>();
+    let mut used = code_references()?;
+    for (file, path) in content_references()? {
+        if !known.contains(path.as_str()) {
+            println!("cargo:warning={}: {path} is not in assets/", file.display());
+        }
+        used.insert(path);
+    }
+    for path in known {
+        if !used.contains(path) {
+            let file = &path["/assets/".len()..];
+            println!("cargo:warning={ASSETS_DIR}/{file} is not used by the code or the content");
+        }
+    }
+
+    let out = PathBuf::from(std::env::var_os("OUT_DIR").expect("OUT_DIR is set by cargo"));
+    fs::write(out.join("assets.rs"), code)
+}
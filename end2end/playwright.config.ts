@@ -11,6 +11,8 @@ import { devices, defineConfig } from "@playwright/test";
  */
 export default defineConfig({
   testDir: "./tests",
+  /* SMTP stand-in for the contact form tests; see smtp-stub.ts */
+  globalSetup: "./smtp-stub.ts",
   /* Maximum time one test can run for. */
   timeout: 30 * 1000,
   expect: {
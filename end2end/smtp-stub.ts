@@ -0,0 +1,122 @@
+import { createServer as createHttpServer, Server as HttpServer } from "node:http";
+import { createServer as createTcpServer, Server as TcpServer, Socket } from "node:net";
+
+// Global setup: an SMTP stand-in for the contact form tests, so they need no
+// mail server. It accepts every message on SMTP_STUB_PORT (1025) and lists
+// them on SMTP_STUB_API_PORT (8025) in the shape of Mailpit's API:
+//   GET /api/v1/messages  -> { messages: [{ Subject, To, Raw }] }
+//   DELETE /api/v1/messages
+// Start the server under test with `SMTP_URL=smtp://localhost:1025`.
+const SMTP_PORT = Number(process.env.SMTP_STUB_PORT ?? 1025);
+const API_PORT = Number(process.env.SMTP_STUB_API_PORT ?? 8025);
+
+type Mail = { Subject: string; To: string[]; Raw: string };
+
+/** Unfolded value of a header in the head of a message. */
+function header(raw: string, name: string): string {
+  const head = raw.split("\r\n\r\n")[0].replace(/\r\n[ \t]+/g, " ");
+  const line = head
+    .split("\r\n")
+    .find((line) => line.toLowerCase().startsWith(`${name.toLowerCase()}:`));
+  return line?.slice(name.length + 1).trim() ?? "";
+}
+
+/** Just enough SMTP for lettre: no TLS, no authentication. */
+function session(socket: Socket, mailbox: Mail[]) {
+  let buffer = "";
+  let recipients: string[] = [];
+  let inData = false;
+  const reply = (line: string) => socket.write(`${line}\r\n`);
+
+  socket.setEncoding("utf8");
+  socket.on("error", () => socket.destroy());
+  socket.on("data", (chunk: string) => {
+    buffer += chunk;
+    for (;;) {
+      if (inData) {
+        // The data ends with a line holding a single dot
+        const end = `\r\n${buffer}`.indexOf("\r\n.\r\n");
+        if (end < 0) return;
+        const raw = buffer.slice(0, Math.max(end - 2, 0)).replace(/^\.\./gm, ".");
+        buffer = buffer.slice(end + 3);
+        mailbox.push({ Subject: header(raw, "Subject"), To: recipients, Raw: raw });
+        recipients = [];
+        inData = false;
+        reply("250 OK: queued");
+        continue;
+      }
+      const end = buffer.indexOf("\r\n");
+      if (end < 0) return;
+      const line = buffer.slice(0, end);
+      buffer = buffer.slice(end + 2);
+      const verb = line.split(" ")[0].toUpperCase();
+      switch (verb) {
+        case "EHLO":
+        case "HELO":
+          reply("250 localhost");
+          break;
+        case "RCPT":
+          recipients.push(line.replace(/^RCPT TO:\s*/i, "").replace(/[<>]/g, ""));
+          reply("250 OK");
+          break;
+        case "RSET":
+          recipients = [];
+          reply("250 OK");
+          break;
+        case "MAIL":
+        case "NOOP":
+          reply("250 OK");
+          break;
+        case "DATA":
+          inData = true;
+          reply("354 End data with <CR><LF>.<CR><LF>");
+          break;
+        case "QUIT":
+          reply("221 Bye");
+          socket.end();
+          return;
+        default:
+          reply("502 Command not implemented");
+      }
+    }
+  });
+  reply("220 localhost SMTP stub");
+}
+
+function listen(server: TcpServer | HttpServer, port: number): Promise<void> {
+  return new Promise((resolve, reject) => {
+    server.once("error", reject);
+    server.listen(port, "127.0.0.1", () => resolve());
+  });
+}
+
+export default async function globalSetup() {
+  const mailbox: Mail[] = [];
+  // lettre keeps connections open in its pool; closed on teardown
+  const sockets = new Set<Socket>();
+  const smtp = createTcpServer((socket) => {
+    sockets.add(socket);
+    socket.on("close", () => sockets.delete(socket));
+    session(socket, mailbox);
+  });
+  const api = createHttpServer((req, res) => {
+    if (req.url !== "/api/v1/messages") {
+      res.writeHead(404).end();
+    } else if (req.method === "DELETE") {
+      mailbox.length = 0;
+      res.writeHead(200).end();
+    } else {
+      res.writeHead(200, { "Content-Type": "application/json" });
+      res.end(JSON.stringify({ messages: mailbox }));
+    }
+  });
+  await listen(smtp, SMTP_PORT);
+  await listen(api, API_PORT);
+
+  // Returned function is the global teardown
+  return async () => {
+    sockets.forEach((socket) => socket.destroy());
+    await new Promise((resolve) => smtp.close(resolve));
+    await new Promise((resolve) => api.close(resolve));
+  };
+}
@@ -0,0 +1,71 @@
+import { test, expect, type Page } from "@playwright/test";
+
+// Runs against a server started with the hash of `ADMIN_PASSWORD`:
+//   ADMIN_PASSWORD_HASH="$(echo "$ADMIN_PASSWORD" | cargo run --features ssr -- hash-password)"
+// and `ADMIN_MAX_FAILURES` high enough for every browser project.
+const SITE = process.env.SITE_URL ?? "http://localhost:3010";
+const PASSWORD = process.env.ADMIN_PASSWORD ?? "senha-de-teste";
+
+test("the dashboard requires a session", async ({ page }) => {
+  const response = await page.goto(`${SITE}/admin`);
+  await expect(page).toHaveURL(`${SITE}/admin/login`);
+  expect(response?.headers()["cache-control"]).toBe("no-store");
+});
+
+test("wrong credentials are refused", async ({ page }) => {
+  await page.goto(`${SITE}/admin/login`);
+  await page.locator("input[name=username]").fill("admin");
+  await page.locator("input[name=password]").fill("errada");
+  await page.locator("button[type=submit]").click();
+
+  await expect(page.getByRole("alert")).toHaveText("Usuário ou senha inválidos.");
+});
+
+test("the login form needs its CSRF cookie", async ({ page, context }) => {
+  await page.goto(`${SITE}/admin/login`);
+  const cookie = (await context.cookies()).find((c) => c.name === "admin_login_csrf");
+  expect(cookie?.httpOnly).toBe(true);
+  expect(cookie?.sameSite).toBe("Strict");
+  await expect(page.locator("input[name=csrf]")).toHaveValue(cookie?.value ?? "");
+
+  // A form posted from elsewhere comes without the cookie
+  await context.clearCookies();
+  await page.locator("input[name=username]").fill("admin");
+  await page.locator("input[name=password]").fill(PASSWORD);
+  await page.locator("button[type=submit]").click();
+  await expect(page.getByRole("alert")).toHaveText("Formulário expirado. Recarregue a página.");
+});
+
+async function logIn(page: Page) {
+  await page.goto(`${SITE}/admin/login`);
+  await page.locator("input[name=username]").fill("admin");
+  await page.locator("input[name=password]").fill(PASSWORD);
+  await page.locator("button[type=submit]").click();
+  await expect(page).toHaveURL(`${SITE}/admin`);
+}
+
+test("log in and out", async ({ page, context }) => {
+  await logIn(page);
+  await expect(page.locator("h1")).toHaveText("Conteúdo");
+  const cookie = (await context.cookies()).find((c) => c.name === "admin_session");
+  expect(cookie?.httpOnly).toBe(true);
+  expect(cookie?.sameSite).toBe("Strict");
+
+  await page.getByRole("button", { name: "Sair" }).click();
+  await expect(page).toHaveURL(`${SITE}/admin/login`);
+});
+
+test("the editor previews changes and validates before saving", async ({ page }) => {
+  await logIn(page);
+  await page.getByRole("link", { name: "Perfil" }).click();
+  await expect(page).toHaveURL(`${SITE}/admin/perfil`);
+
+  const tagline = page.getByLabel("Chamada");
+  await tagline.fill("Rust • Vídeo");
+  await expect(page.locator("section").filter({ hasText: "Rust • Vídeo" })).toBeVisible();
+  await expect(page.getByText("Alterações não salvas")).toBeVisible();
+
+  await page.getByLabel("E-mail").fill("sem-arroba");
+  await expect(page.getByText("E-mail inválido.")).toBeVisible();
+  await expect(page.getByRole("button", { name: "Salvar rascunho" })).toBeDisabled();
+});
@@ -0,0 +1,90 @@
+import { test, expect, APIRequestContext, TestInfo } from "@playwright/test";
+
+// Runs against a server started with `SMTP_URL=smtp://localhost:1025`, which
+// the SMTP stub started by the global setup (smtp-stub.ts) answers; its
+// mailbox is read over HTTP on port 8025. Every browser project submits from
+// the same address, so raise the per-IP limit (`ANTISPAM_MAX_SUBMISSIONS=100`)
+// and keep the puzzle quick (`ANTISPAM_DIFFICULTY=12`).
+const SITE = process.env.SITE_URL ?? "http://localhost:3010";
+const MAILBOX = process.env.SMTP_STUB_URL ?? "http://localhost:8025";
+
+// Projects run in parallel against one mailbox, so each looks for its own
+// sender name instead of emptying it
+function sender(testInfo: TestInfo, name: string): string {
+  return `${name} ${testInfo.project.name} ${Date.now()}`;
+}
+
+async function subjects(request: APIRequestContext): Promise<string[]> {
+  const res = await request.get(`${MAILBOX}/api/v1/messages`);
+  const body = await res.json();
+  return body.messages?.map((m: { Subject: string }) => m.Subject) ?? [];
+}
+
+test("invalid fields show inline errors", async ({ page }) => {
+  await page.goto(`${SITE}/#contact`);
+  const form = page.locator("#contact form");
+
+  await form.locator("input[name=name]").fill("Visitante");
+  // Passes the browser's own check but not the server's
+  await form.locator("input[name=email]").fill("visitante@localhost");
+  await form.locator("textarea[name=message]").fill("Olá, tudo bem?");
+  await form.locator("button[type=submit]").click();
+
+  await expect(page.locator("#contact-email-error")).toHaveText(
+    "Informe um e-mail válido.",
+  );
+  await expect(page.locator("#contact-name-error")).toBeEmpty();
+});
+
+test("a valid message is delivered over SMTP", async ({ page, request }, testInfo) => {
+  const name = sender(testInfo, "Visitante");
+
+  await page.goto(`${SITE}/#contact`);
+  const form = page.locator("#contact form");
+  await form.locator("input[name=name]").fill(name);
+  await form.locator("input[name=email]").fill("visitante@example.com");
+  await form
+    .locator("textarea[name=message]")
+    .fill("Gostaria de conversar sobre um projeto.");
+  await form.locator("button[type=submit]").click();
+
+  await expect(page.locator("#contact [role=status]")).toBeVisible();
+
+  await expect.poll(() => subjects(request)).toContain(`Contato pelo site: ${name}`);
+});
+
+test("a filled honeypot looks sent but is dropped", async ({ page, request }, testInfo) => {
+  const name = sender(testInfo, "Robo");
+
+  await page.goto(`${SITE}/#contact`);
+  const form = page.locator("#contact form");
+  await form.locator("input[name=name]").fill(name);
+  await form.locator("input[name=email]").fill("robo@example.com");
+  await form.locator("textarea[name=message]").fill("Compre seguidores baratos!");
+  await form
+    .locator("input[name=website]")
+    .evaluate((input: HTMLInputElement) => (input.value = "https://spam.example"));
+  await form.locator("button[type=submit]").click();
+
+  await expect(page.locator("#contact [role=status]")).toBeVisible();
+  expect(await subjects(request)).not.toContain(`Contato pelo site: ${name}`);
+});
+
+test("a wrong proof of work is rejected", async ({ page }) => {
+  await page.goto(`${SITE}/#contact`);
+  const form = page.locator("#contact form");
+  const submit = form.locator("button[type=submit]");
+  await form.locator("input[name=name]").fill("Visitante");
+  await form.locator("input[name=email]").fill("visitante@example.com");
+  await form.locator("textarea[name=message]").fill("Mensagem com nonce errado.");
+  // Wait for the solver, then spoil its answer
+  await expect(submit).toBeEnabled();
+  await form
+    .locator("input[name=pow_nonce]")
+    .evaluate((input: HTMLInputElement) => (input.value = "not-a-nonce"));
+  await submit.click();
+
+  await expect(form.locator("[role=alert]")).toHaveText(
+    "Não foi possível verificar o envio. Tente novamente.",
+  );
+});
@@ -0,0 +1,24 @@
+import { test, expect } from "@playwright/test";
+import { readFile, writeFile } from "node:fs/promises";
+import { resolve } from "node:path";
+
+// Needs a development server (`cargo leptos watch`), which watches `content/`.
+// Edits a real content file and puts it back afterwards, so run it alone:
+//   npx playwright test live-reload --workers=1 --project=chromium
+const SITE = process.env.SITE_URL ?? "http://localhost:3010";
+const PROFILE = resolve(__dirname, "../../content/profile.toml");
+
+test("editing a content file reloads open pages", async ({ page }) => {
+  const original = await readFile(PROFILE, "utf8");
+  const marker = `Live reload ${Date.now()}`;
+  try {
+    await page.goto(`${SITE}/`);
+    await writeFile(
+      PROFILE,
+      original.replace(/^tagline = .*$/m, `tagline = "${marker}"`),
+    );
+    await expect(page.getByText(marker)).toBeVisible({ timeout: 2000 });
+  } finally {
+    await writeFile(PROFILE, original);
+  }
+});
@@ -0,0 +1,133 @@
+//! Login, logout and session lookup, the content editor and the log filter.
+
+use leptos::prelude::*;
+
+use leptos::server_fn::codec::Json;
+
+use super::editor::{Document, EditorError, Entry, Loaded, Payload, Status};
+use super::{AdminSession, AuthError};
+use crate::i18n::Locale;
+
+/// The current session, or `None` when not logged in.
+#[server]
+pub async fn current_session() -> Result<Option<AdminSession>, AuthError> {
+    crate::server::auth::current().await
+}
+
+/// The CSRF token for the login form, also set as a cookie.
+#[server]
+pub async fn login_csrf() -> Result<String, AuthError> {
+    crate::server::auth::login_csrf().await
+}
+
+/// Checks the credentials, sets the session cookie and goes to `/admin`.
+#[server]
+pub async fn login(csrf: String, username: String, password: String) -> Result<(), AuthError> {
+    use crate::server::auth;
+
+    let (req, auth) = auth::request().await?;
+    auth::require_login_csrf(&csrf).await?;
+    let token = auth
+        .login(auth.client_ip(&req), &username, &password)
+        .await?;
+    auth::set_cookie(auth.cookie(&token));
+    auth::set_cookie(auth.login_csrf_removal_cookie());
+    leptos_actix::redirect("/admin");
+    Ok(())
+}
+
+#[server]
+pub async fn logout(csrf: String) -> Result<(), AuthError> {
+    use crate::server::auth;
+
+    let (req, auth) = auth::request().await?;
+    auth::require_admin(&csrf).await?;
+    if let Some(cookie) = req.cookie(super::SESSION_COOKIE) {
+        auth.logout(cookie.value());
+    }
+    auth::set_cookie(auth.removal_cookie());
+    leptos_actix::redirect("/admin/login");
+    Ok(())
+}
+
+// --- Content editor ---
+
+/// Every editable document in `locale` with its state, for the dashboard.
+#[server(input = Json)]
+pub async fn editor_overview(locale: Locale) -> Result<Vec<Entry>, EditorError> {
+    use crate::server::editor;
+
+    let editor = editor::authorize(None).await?;
+    editor::run(editor, move |editor| editor.overview(locale)).await
+}
+
+/// The document as the editor should show it: its draft, or the live file.
+#[server(input = Json)]
+pub async fn load_document(locale: Locale, document: Document) -> Result<Loaded, EditorError> {
+    use crate::server::editor;
+
+    let editor = editor::authorize(None).await?;
+    editor::run(editor, move |editor| editor.load(locale, &document)).await
+}
+
+/// Validates and stores a draft, without touching the site.
+#[server(input = Json)]
+pub async fn save_draft(csrf: String, locale: Locale, payload: Payload) -> Result<Status, EditorError> {
+    use crate::server::editor;
+
+    let editor = editor::authorize(Some(&csrf)).await?;
+    editor::run(editor, move |editor| editor.save_draft(locale, payload)).await
+}
+
+/// Puts the saved draft live and reloads the content.
+#[server(input = Json)]
+pub async fn publish(csrf: String, locale: Locale, document: Document) -> Result<(), EditorError> {
+    use crate::server::editor;
+
+    let editor = editor::authorize(Some(&csrf)).await?;
+    editor::run(editor, move |editor| editor.publish(locale, &document)).await
+}
+
+#[server(input = Json)]
+pub async fn discard_draft(csrf: String, locale: Locale, document: Document) -> Result<(), EditorError> {
+    use crate::server::editor;
+
+    let editor = editor::authorize(Some(&csrf)).await?;
+    editor::run(editor, move |editor| editor.discard(locale, &document)).await
+}
+
+/// Renders Markdown with the same renderer as the site, for the live preview.
+#[server(input = Json)]
+pub async fn render_markdown(markdown: String) -> Result<String, EditorError> {
+    crate::server::editor::authorize(None).await?;
+    Ok(crate::content::markdown::render(&markdown))
+}
+
+// --- Logging ---
+
+/// The server's log filter, such as `info`.
+#[server]
+pub async fn log_filter() -> Result<String, AuthError> {
+    use crate::server::telemetry;
+
+    let (req, _) = crate::server::auth::request().await?;
+    crate::server::auth::current().await?.ok_or(AuthError::Unauthorized)?;
+    let level = req
+        .app_data::<actix_web::web::Data<telemetry::LogLevel>>()
+        .ok_or_else(|| AuthError::Server("logging is not configured".to_string()))?;
+    Ok(level.get())
+}
+
+/// Changes the log filter until the next restart, e.g. to `debug` while
+/// chasing a problem.
+#[server]
+pub async fn set_log_filter(csrf: String, filter: String) -> Result<(), AuthError> {
+    use crate::server::telemetry;
+
+    let (req, _) = crate::server::auth::request().await?;
+    crate::server::auth::require_admin(&csrf).await?;
+    let level = req
+        .app_data::<actix_web::web::Data<telemetry::LogLevel>>()
+        .ok_or_else(|| AuthError::Server("logging is not configured".to_string()))?;
+    level.set(&filter).map_err(AuthError::Invalid)
+}
@@ -0,0 +1,438 @@
+//! Types and validation shared by the content editor pages and its server
+//! functions. Validation runs in the browser while editing and again on the
+//! server before anything is written.
+
+use std::fmt;
+
+use chrono::NaiveDate;
+use leptos::server_fn::codec::JsonEncoding;
+use leptos::server_fn::error::{FromServerFnError, ServerFnErrorErr};
+use serde::{Deserialize, Serialize};
+
+use super::AuthError;
+use crate::contact::is_email;
+use crate::content::{slugify, Experience, ProfileData, Project, SkillCategory};
+use crate::i18n::Locale;
+
+/// Query parameter of the editor pages holding the locale being edited, e.g.
+/// `/admin/perfil?idioma=en`. Absent for the default locale.
+pub const LOCALE_PARAM: &str = "idioma";
+
+/// The locale named by [`LOCALE_PARAM`], the default one if missing or unknown.
+pub fn locale_from_param(value: Option<&str>) -> Locale {
+    value.and_then(Locale::from_tag).unwrap_or_default()
+}
+
+/// `path` of an admin page with the [`LOCALE_PARAM`] for `locale`.
+pub fn locale_path(path: &str, locale: Locale) -> String {
+    match locale {
+        Locale::PtBr => path.to_string(),
+        locale => format!("{path}?{LOCALE_PARAM}={}", locale.tag()),
+    }
+}
+
+/// An editable content file. The same document exists once per locale: the
+/// default one in the content directory, the others in its overlay
+/// (`content/en`).
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Document {
+    Profile,
+    Skills,
+    Experience,
+    /// A project, by slug (file stem in `content/projects`).
+    Project(String),
+}
+
+impl Document {
+    /// Path of the editor page for the `locale` version.
+    pub fn editor_path(&self, locale: Locale) -> String {
+        let path = match self {
+            Document::Profile => "/admin/perfil".to_string(),
+            Document::Skills => "/admin/habilidades".to_string(),
+            Document::Experience => "/admin/experiencia".to_string(),
+            Document::Project(slug) => format!("/admin/projetos/{slug}"),
+        };
+        locale_path(&path, locale)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Status {
+    /// The live file, no pending changes.
+    Published,
+    /// Live, with a saved draft not yet published.
+    Draft,
+    /// Only a draft exists; not on the site yet.
+    New,
+    /// A project that has not been saved at all.
+    Unsaved,
+    /// No translation yet: the site shows the default-locale version, which
+    /// the editor loads as a starting point.
+    Untranslated,
+}
+
+impl Status {
+    pub fn label(self) -> &'static str {
+        match self {
+            Status::Published => "Publicado",
+            Status::Draft => "Rascunho pendente",
+            Status::New => "Rascunho (não publicado)",
+            Status::Unsaved => "Novo (não salvo)",
+            Status::Untranslated => "Sem tradução",
+        }
+    }
+}
+
+/// The contents of one document, as edited.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Payload {
+    Profile(ProfileData),
+    Skills(Vec<SkillCategory>),
+    Experience(Vec<Experience>),
+    /// Front matter and Markdown body. `project.body_html` is ignored.
+    Project { project: Project, body: String },
+}
+
+/// A validation problem, shown next to the form.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Issue {
+    /// Human-readable location, e.g. "Categoria 2 › item 1 › ícone".
+    pub field: String,
+    pub message: String,
+}
+
+impl Issue {
+    fn new(field: impl Into<String>, message: impl Into<String>) -> Self {
+        Issue {
+            field: field.into(),
+            message: message.into(),
+        }
+    }
+}
+
+/// A document as loaded into the editor: the draft if there is one.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Loaded {
+    pub payload: Payload,
+    pub status: Status,
+    /// Which version of the document this is.
+    pub locale: Locale,
+    /// Icon names available in `assets/icons`, without extension.
+    pub icons: Vec<String>,
+    /// Server date, so the preview measures ongoing roles like the site does.
+    pub today: NaiveDate,
+}
+
+/// One row of the editor dashboard.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub document: Document,
+    pub title: String,
+    pub status: Status,
+}
+
+fn trim(value: &mut String) {
+    let trimmed = value.trim();
+    if trimmed.len() != value.len() {
+        *value = trimmed.to_string();
+    }
+}
+
+fn trim_option(value: &mut Option<String>) {
+    if let Some(text) = value {
+        trim(text);
+    }
+    if value.as_deref() == Some("") {
+        *value = None;
+    }
+}
+
+fn trim_list(values: &mut Vec<String>) {
+    values.iter_mut().for_each(trim);
+    values.retain(|v| !v.is_empty());
+}
+
+fn required(issues: &mut Vec<Issue>, field: &str, value: &str) {
+    if value.is_empty() {
+        issues.push(Issue::new(field, "Obrigatório."));
+    }
+}
+
+fn is_url(value: &str) -> bool {
+    value.starts_with("https://") || value.starts_with("http://")
+}
+
+/// Site paths (`/assets/...`) or absolute URLs.
+fn is_link(value: &str) -> bool {
+    value.starts_with('/') || is_url(value)
+}
+
+impl Payload {
+    pub fn document(&self) -> Document {
+        match self {
+            Payload::Profile(_) => Document::Profile,
+            Payload::Skills(_) => Document::Skills,
+            Payload::Experience(_) => Document::Experience,
+            Payload::Project { project, .. } => Document::Project(project.slug.clone()),
+        }
+    }
+
+    /// Trims text, turns blank optional fields into `None` and drops blank
+    /// list entries (the editor keeps them while typing).
+    pub fn normalized(mut self) -> Self {
+        match &mut self {
+            Payload::Profile(profile) => {
+                for field in [
+                    &mut profile.name,
+                    &mut profile.title,
+                    &mut profile.tagline,
+                    &mut profile.email,
+                    &mut profile.linkedin,
+                ] {
+                    trim(field);
+                }
+            }
+            Payload::Skills(categories) => {
+                for category in categories {
+                    trim(&mut category.category);
+                    for item in &mut category.items {
+                        trim(&mut item.name);
+                        trim(&mut item.icon);
+                    }
+                }
+            }
+            Payload::Experience(experiences) => {
+                for experience in experiences {
+                    trim(&mut experience.title);
+                    trim(&mut experience.company);
+                    trim_list(&mut experience.tasks);
+                    trim_list(&mut experience.tags);
+                }
+            }
+            Payload::Project { project, .. } => {
+                trim(&mut project.slug);
+                trim(&mut project.title);
+                trim(&mut project.summary);
+                trim_list(&mut project.tags);
+                for field in [
+                    &mut project.challenge,
+                    &mut project.solution,
+                    &mut project.results,
+                    &mut project.url,
+                    &mut project.url_label,
+                    &mut project.poster,
+                ] {
+                    trim_option(field);
+                }
+                for video in &mut project.videos {
+                    trim(&mut video.src);
+                    trim(&mut video.mime);
+                    trim_option(&mut video.media);
+                }
+                for item in &mut project.gallery {
+                    trim(&mut item.src);
+                    trim(&mut item.alt);
+                    trim_option(&mut item.caption);
+                }
+                project.body_html.clear();
+            }
+        }
+        self
+    }
+
+    /// Problems that block saving. Expects a [`normalized`](Self::normalized)
+    /// payload; `icons` are the names in `assets/icons`.
+    pub fn validate(&self, icons: &[String]) -> Vec<Issue> {
+        let mut issues = Vec::new();
+        match self {
+            Payload::Profile(profile) => {
+                required(&mut issues, "Nome", &profile.name);
+                required(&mut issues, "Título", &profile.title);
+                if !is_email(&profile.email) {
+                    issues.push(Issue::new("E-mail", "E-mail inválido."));
+                }
+                if !is_url(&profile.linkedin) {
+                    issues.push(Issue::new("LinkedIn", "Use um endereço http(s)://."));
+                }
+            }
+            Payload::Skills(categories) => {
+                for (i, category) in categories.iter().enumerate() {
+                    let at = format!("Categoria {}", i + 1);
+                    required(&mut issues, &at, &category.category);
+                    for (j, item) in category.items.iter().enumerate() {
+                        let at = format!("{at} › item {}", j + 1);
+                        required(&mut issues, &format!("{at} › nome"), &item.name);
+                        if !icons.contains(&item.icon) {
+                            issues.push(Issue::new(
+                                format!("{at} › ícone"),
+                                "Escolha um ícone de assets/icons.",
+                            ));
+                        }
+                    }
+                }
+            }
+            Payload::Experience(experiences) => {
+                for (i, experience) in experiences.iter().enumerate() {
+                    let at = format!("Experiência {}", i + 1);
+                    required(&mut issues, &format!("{at} › cargo"), &experience.title);
+                    required(&mut issues, &format!("{at} › empresa"), &experience.company);
+                    if experience.end.is_some_and(|end| end < experience.start) {
+                        issues.push(Issue::new(
+                            format!("{at} › fim"),
+                            "O fim não pode ser antes do início.",
+                        ));
+                    }
+                }
+            }
+            Payload::Project { project, .. } => {
+                if project.slug.is_empty() || slugify(&project.slug) != project.slug {
+                    issues.push(Issue::new(
+                        "Slug",
+                        "Use só letras minúsculas, números e hífens.",
+                    ));
+                }
+                required(&mut issues, "Título", &project.title);
+                required(&mut issues, "Resumo", &project.summary);
+                if project.url.as_deref().is_some_and(|url| !is_url(url)) {
+                    issues.push(Issue::new("Link", "Use um endereço http(s)://."));
+                }
+                if project.poster.as_deref().is_some_and(|poster| !is_link(poster)) {
+                    issues.push(Issue::new("Capa", "Use um caminho /assets/... ou um endereço."));
+                }
+                for (i, video) in project.videos.iter().enumerate() {
+                    if !is_link(&video.src) {
+                        issues.push(Issue::new(
+                            format!("Vídeo {}", i + 1),
+                            "Use um caminho /assets/... ou um endereço.",
+                        ));
+                    }
+                }
+                for (i, item) in project.gallery.iter().enumerate() {
+                    if !is_link(&item.src) {
+                        issues.push(Issue::new(
+                            format!("Galeria {}", i + 1),
+                            "Use um caminho /assets/... ou um endereço.",
+                        ));
+                    }
+                }
+            }
+        }
+        issues
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum EditorError {
+    Auth(AuthError),
+    Invalid(Vec<Issue>),
+    NotFound,
+    /// Publishing was refused because the site could not load the result.
+    Rejected(String),
+    Server(String),
+}
+
+impl EditorError {
+    /// Text shown on the editor pages.
+    pub fn message(&self) -> String {
+        match self {
+            EditorError::Auth(e) => e.message(),
+            EditorError::Invalid(_) => "Corrija os campos indicados.".to_string(),
+            EditorError::NotFound => "Documento não encontrado.".to_string(),
+            EditorError::Rejected(e) => format!("Publicação recusada: {e}"),
+            EditorError::Server(_) => "Erro no servidor. Tente novamente.".to_string(),
+        }
+    }
+}
+
+impl From<AuthError> for EditorError {
+    fn from(value: AuthError) -> Self {
+        EditorError::Auth(value)
+    }
+}
+
+impl fmt::Display for EditorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EditorError::Auth(e) => write!(f, "{e}"),
+            EditorError::Invalid(issues) => write!(f, "{} invalid fields", issues.len()),
+            EditorError::NotFound => f.write_str("document not found"),
+            EditorError::Rejected(e) => write!(f, "content failed to load: {e}"),
+            EditorError::Server(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for EditorError {}
+
+impl FromServerFnError for EditorError {
+    type Encoder = JsonEncoding;
+
+    fn from_server_fn_error(value: ServerFnErrorErr) -> Self {
+        EditorError::Server(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content::SkillItem;
+
+    fn skills(icon: &str) -> Payload {
+        Payload::Skills(vec![SkillCategory {
+            category: " Rust ".to_string(),
+            items: vec![SkillItem {
+                name: "Leptos".to_string(),
+                icon: icon.to_string(),
+            }],
+        }])
+        .normalized()
+    }
+
+    #[test]
+    fn skills_need_a_known_icon() {
+        let icons = ["rust_logo".to_string()];
+        assert_eq!(skills("rust_logo").validate(&icons), Vec::new());
+        assert_eq!(
+            skills("leptos_logo").validate(&icons),
+            vec![Issue::new("Categoria 1 › item 1 › ícone", "Escolha um ícone de assets/icons.")]
+        );
+        // Blank after trimming is not a name in the list either
+        assert_eq!(skills(" ").validate(&icons).len(), 1);
+        assert_eq!(skills("rust_logo").validate(&[]).len(), 1);
+    }
+
+    #[test]
+    fn project_links_and_slug() {
+        let project = |slug: &str, poster: &str| Payload::Project {
+            project: Project {
+                slug: slug.to_string(),
+                title: "Camará".to_string(),
+                summary: "Resumo".to_string(),
+                poster: Some(poster.to_string()),
+                ..Project::default()
+            },
+            body: String::new(),
+        }
+        .normalized();
+        let fields = |payload: Payload| {
+            payload
+                .validate(&[])
+                .into_iter()
+                .map(|issue| issue.field)
+                .collect::<Vec<_>>()
+        };
+        assert!(fields(project("camara", "/assets/camara_poster.jpg")).is_empty());
+        assert_eq!(fields(project("Camará", "capa.jpg")), ["Slug", "Capa"]);
+        // A blank poster is no poster
+        assert!(fields(project("camara", "  ")).is_empty());
+    }
+
+    #[test]
+    fn locale_paths() {
+        assert_eq!(locale_path("/admin/perfil", Locale::PtBr), "/admin/perfil");
+        assert_eq!(Document::Profile.editor_path(Locale::En), "/admin/perfil?idioma=en");
+        assert_eq!(locale_from_param(Some("en")), Locale::En);
+        assert_eq!(locale_from_param(Some("xx")), Locale::default());
+        assert_eq!(locale_from_param(None), Locale::default());
+    }
+}
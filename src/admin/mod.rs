@@ -0,0 +1,83 @@
+//! Admin area: session and error types shared by the `/admin` pages and the
+//! server functions in [`api`]. Sessions, password checks and lockout live in
+//! `server::auth`; the content editor's types are in [`editor`].
+
+pub mod api;
+pub mod editor;
+
+use std::fmt;
+
+use leptos::server_fn::codec::JsonEncoding;
+use leptos::server_fn::error::{FromServerFnError, ServerFnErrorErr};
+use serde::{Deserialize, Serialize};
+
+/// Name of the HttpOnly session cookie.
+pub const SESSION_COOKIE: &str = "admin_session";
+
+/// Name of the HttpOnly cookie holding the login form's CSRF token.
+pub const LOGIN_CSRF_COOKIE: &str = "admin_login_csrf";
+
+/// The logged-in admin, as the pages see it. Provided as context by
+/// `AdminGuard`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AdminSession {
+    pub username: String,
+    /// Sent back with every mutating server function.
+    pub csrf: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum AuthError {
+    InvalidCredentials,
+    /// Too many failed logins from this address.
+    LockedOut { retry_after: u64 },
+    /// No valid session.
+    Unauthorized,
+    /// Missing or wrong CSRF token.
+    Csrf,
+    /// A value the admin entered was refused.
+    Invalid(String),
+    Server(String),
+}
+
+impl AuthError {
+    /// Text shown on the admin pages.
+    pub fn message(&self) -> String {
+        match self {
+            AuthError::InvalidCredentials => "Usuário ou senha inválidos.".to_string(),
+            AuthError::LockedOut { retry_after } => format!(
+                "Muitas tentativas. Tente novamente em {} min.",
+                retry_after.div_ceil(60)
+            ),
+            AuthError::Unauthorized => "Sessão expirada. Entre novamente.".to_string(),
+            AuthError::Csrf => "Formulário expirado. Recarregue a página.".to_string(),
+            AuthError::Invalid(e) => format!("Valor inválido: {e}"),
+            AuthError::Server(_) => "Erro no servidor. Tente novamente.".to_string(),
+        }
+    }
+}
+
+impl fmt::Display for AuthError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuthError::InvalidCredentials => f.write_str("invalid credentials"),
+            AuthError::LockedOut { retry_after } => {
+                write!(f, "locked out for {retry_after} more seconds")
+            }
+            AuthError::Unauthorized => f.write_str("not logged in"),
+            AuthError::Csrf => f.write_str("invalid CSRF token"),
+            AuthError::Invalid(e) => write!(f, "invalid value: {e}"),
+            AuthError::Server(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for AuthError {}
+
+impl FromServerFnError for AuthError {
+    type Encoder = JsonEncoding;
+
+    fn from_server_fn_error(value: ServerFnErrorErr) -> Self {
+        AuthError::Server(value.to_string())
+    }
+}
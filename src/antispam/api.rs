@@ -0,0 +1,16 @@
+//! Server function that hands out challenges.
+
+use leptos::prelude::*;
+
+use super::Challenge;
+
+/// A fresh challenge. Each one is good for a single submission.
+#[server]
+pub async fn get_challenge() -> Result<Challenge, ServerFnError> {
+    use actix_web::web;
+
+    use crate::server::antispam::Antispam;
+
+    let antispam: web::Data<Antispam> = leptos_actix::extract().await?;
+    Ok(antispam.issue())
+}
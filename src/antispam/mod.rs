@@ -0,0 +1,247 @@
+//! Self-hosted proof of work for public forms.
+//!
+//! The server hands out a signed [`Challenge`]; the hydrated client searches
+//! for a nonce whose SHA-256 with the challenge salt starts with `difficulty`
+//! zero bits and submits both with the form. Checking costs one hash, solving
+//! about `2^difficulty`. Issuing and verifying live in `server::antispam`.
+
+pub mod api;
+
+use std::fmt;
+use std::future::Future;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::i18n::Locale;
+
+/// A puzzle as sent to the client. The signature covers every other field,
+/// so the client cannot lower the difficulty or extend the deadline.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Challenge {
+    /// Random, hex encoded. Also identifies the challenge for replay checks.
+    pub salt: String,
+    /// Required leading zero bits.
+    pub difficulty: u32,
+    /// Unix timestamp after which the challenge is refused.
+    pub expires: i64,
+    /// Hex HMAC-SHA256 of [`Challenge::signed_part`].
+    pub signature: String,
+}
+
+impl Challenge {
+    /// The part covered by the signature.
+    pub fn signed_part(&self) -> String {
+        format!("{}.{}.{}", self.salt, self.difficulty, self.expires)
+    }
+
+    /// Single string for a hidden form field.
+    pub fn to_token(&self) -> String {
+        format!("{}.{}", self.signed_part(), self.signature)
+    }
+
+    pub fn from_token(token: &str) -> Option<Challenge> {
+        let mut parts = token.split('.');
+        let challenge = Challenge {
+            salt: parts.next()?.to_string(),
+            difficulty: parts.next()?.parse().ok()?,
+            expires: parts.next()?.parse().ok()?,
+            signature: parts.next()?.to_string(),
+        };
+        parts.next().is_none().then_some(challenge)
+    }
+
+    pub fn is_solved_by(&self, nonce: u64) -> bool {
+        leading_zero_bits(&digest(&self.salt, nonce)) >= self.difficulty
+    }
+
+    /// The first of `count` nonces from `start` that solves the challenge.
+    pub fn solve_range(&self, start: u64, count: u64) -> Option<u64> {
+        (start..start.saturating_add(count)).find(|nonce| self.is_solved_by(*nonce))
+    }
+
+    /// Brute-forces the nonce in one go.
+    pub fn solve(&self) -> u64 {
+        self.solve_range(0, u64::MAX).unwrap_or_default()
+    }
+
+    /// Brute-forces the nonce [`SOLVE_BATCH`] hashes at a time, awaiting
+    /// `pause` in between. Runs on the client, where `pause` hands control
+    /// back to the browser so the page stays responsive.
+    pub async fn solve_with<F: Future<Output = ()>>(&self, mut pause: impl FnMut() -> F) -> u64 {
+        let mut start = 0;
+        loop {
+            if let Some(nonce) = self.solve_range(start, SOLVE_BATCH) {
+                return nonce;
+            }
+            start += SOLVE_BATCH;
+            pause().await;
+        }
+    }
+}
+
+/// Hashes between two pauses of [`Challenge::solve_with`], a few
+/// milliseconds of work in WASM.
+pub const SOLVE_BATCH: u64 = 1 << 14;
+
+fn digest(salt: &str, nonce: u64) -> [u8; 32] {
+    Sha256::new()
+        .chain_update(salt.as_bytes())
+        .chain_update(b":")
+        .chain_update(nonce.to_string().as_bytes())
+        .finalize()
+        .into()
+}
+
+fn leading_zero_bits(hash: &[u8]) -> u32 {
+    let mut bits = 0;
+    for byte in hash {
+        bits += byte.leading_zeros();
+        if *byte != 0 {
+            break;
+        }
+    }
+    bits
+}
+
+/// Why a submission was refused before its content was looked at.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Rejection {
+    /// No challenge or nonce, e.g. the form was posted without the WASM client.
+    Missing,
+    /// Signature mismatch or malformed token.
+    Forged,
+    Expired,
+    /// The challenge was already used once.
+    Replayed,
+    /// The nonce does not solve the challenge.
+    Unsolved,
+    RateLimited,
+}
+
+impl Rejection {
+    /// Text shown to the visitor.
+    pub fn message(self, locale: Locale) -> &'static str {
+        match (locale, self) {
+            (Locale::PtBr, Rejection::RateLimited) => {
+                "Muitas mensagens em pouco tempo. Tente novamente mais tarde."
+            }
+            (Locale::PtBr, Rejection::Missing) => {
+                "O envio precisa de JavaScript para a verificação anti-spam."
+            }
+            (Locale::PtBr, _) => "Não foi possível verificar o envio. Tente novamente.",
+            (Locale::En, Rejection::RateLimited) => {
+                "Too many messages in a short time. Please try again later."
+            }
+            (Locale::En, Rejection::Missing) => {
+                "Sending requires JavaScript for the anti-spam check."
+            }
+            (Locale::En, _) => "The submission could not be verified. Please try again.",
+        }
+    }
+}
+
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason = match self {
+            Rejection::Missing => "missing challenge",
+            Rejection::Forged => "invalid challenge signature",
+            Rejection::Expired => "challenge expired",
+            Rejection::Replayed => "challenge already used",
+            Rejection::Unsolved => "challenge not solved",
+            Rejection::RateLimited => "rate limited",
+        };
+        f.write_str(reason)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn challenge(difficulty: u32) -> Challenge {
+        Challenge {
+            salt: "5a1t".to_string(),
+            difficulty,
+            expires: 1_700_000_000,
+            signature: "abcd".to_string(),
+        }
+    }
+
+    #[test]
+    fn leading_zero_bits_across_bytes() {
+        assert_eq!(leading_zero_bits(&[0xff, 0x00]), 0);
+        assert_eq!(leading_zero_bits(&[0x80]), 0);
+        assert_eq!(leading_zero_bits(&[0x01, 0xff]), 7);
+        assert_eq!(leading_zero_bits(&[0x00, 0x40, 0x00]), 9);
+        assert_eq!(leading_zero_bits(&[0x00, 0x00, 0x0f]), 20);
+        // Stops at the first set bit, whatever follows
+        assert_eq!(leading_zero_bits(&[0x10, 0x00, 0x00]), 3);
+        assert_eq!(leading_zero_bits(&[0; 4]), 32);
+    }
+
+    #[test]
+    fn solve_at_several_difficulties() {
+        for difficulty in [0, 1, 4, 8, 12, 16] {
+            let challenge = challenge(difficulty);
+            let nonce = challenge.solve();
+            assert!(challenge.is_solved_by(nonce), "difficulty {difficulty}");
+            assert!(leading_zero_bits(&digest(&challenge.salt, nonce)) >= difficulty);
+            // The first solution: every smaller nonce fails
+            assert!((0..nonce).all(|n| !challenge.is_solved_by(n)), "difficulty {difficulty}");
+        }
+    }
+
+    #[test]
+    fn harder_challenges_reject_easier_solutions() {
+        let easy = challenge(4);
+        let nonce = (0..)
+            .find(|n| leading_zero_bits(&digest(&easy.salt, *n)) == 4)
+            .unwrap();
+        assert!(easy.is_solved_by(nonce));
+        assert!(!challenge(5).is_solved_by(nonce));
+    }
+
+    #[test]
+    fn solve_with_finds_the_same_nonce_in_batches() {
+        let challenge = challenge(16);
+        let mut pauses = 0;
+        let nonce = block_on(challenge.solve_with(|| {
+            pauses += 1;
+            std::future::ready(())
+        }));
+        assert_eq!(nonce, challenge.solve());
+        assert_eq!(pauses, nonce / SOLVE_BATCH);
+    }
+
+    #[test]
+    fn solve_range_is_bounded() {
+        let challenge = challenge(8);
+        let nonce = challenge.solve();
+        assert_eq!(challenge.solve_range(0, nonce), None);
+        assert_eq!(challenge.solve_range(nonce, 1), Some(nonce));
+    }
+
+    #[test]
+    fn token_round_trip() {
+        let challenge = challenge(18);
+        assert_eq!(Challenge::from_token(&challenge.to_token()), Some(challenge));
+        assert_eq!(Challenge::from_token("salt.18.1700000000"), None);
+        assert_eq!(Challenge::from_token("salt.18.1700000000.sig.extra"), None);
+        assert_eq!(Challenge::from_token("salt.hard.1700000000.sig"), None);
+    }
+
+    /// Polls a future that never waits to completion.
+    fn block_on<T>(future: impl Future<Output = T>) -> T {
+        use std::pin::pin;
+        use std::task::{Context, Poll, Waker};
+
+        let mut future = pin!(future);
+        let mut context = Context::from_waker(Waker::noop());
+        loop {
+            if let Poll::Ready(value) = future.as_mut().poll(&mut context) {
+                return value;
+            }
+        }
+    }
+}
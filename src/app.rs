@@ -1,11 +1,16 @@
 use leptos::prelude::*;
-use leptos_meta::{provide_meta_context, Stylesheet, Title, Body, Html};
+use leptos_meta::{provide_meta_context, Stylesheet, Title, Body, Html, Link};
 use leptos_router::{
-    components::{Route, Router, Routes},
-    StaticSegment, WildcardSegment,
+    components::{Outlet, ParentRoute, Route, Router, Routes},
+    MatchNestedRoutes, ParamSegment, SsrMode, StaticSegment, WildcardSegment,
 };
 
+use crate::components::page_meta::PageMeta;
+use crate::i18n::{provide_i18n, use_locale};
+use crate::routes::admin::AdminRoutes;
+use crate::routes::blog::{BlogIndex, BlogPost};
 use crate::routes::home::HomePage;
+use crate::routes::project::ProjectPage;
 
 #[component]
 pub fn App() -> impl IntoView {
@@ -13,8 +18,6 @@ pub fn App() -> impl IntoView {
     provide_meta_context();
 
     view! {
-        // FIX: Added {..} to spread attributes, fixing EmptyPropsBuilder error
-        <Html {..} lang="pt-BR" class="scroll-smooth"/>
         <Body {..} class="bg-[#0a0a0a] text-white selection:bg-blue-500 selection:text-white"/>
         
         // Sets the document title
@@ -26,35 +29,81 @@ pub fn App() -> impl IntoView {
         
         // Fontes de ícones (Phosphor Icons ou similar via CDN para logos leves)
         // Usaremos SVGs inline no home.rs para performance máxima, mas deixo a opção aqui se expandir.
-        
-        <Stylesheet id="leptos" href="/pkg/nicolasalmino-site.css"/>
+
+        // The site stylesheet is linked by the shell, which knows its hashed name
+
+        // Feed discovery
+        <Link rel="alternate" type_="application/rss+xml" title="Nicolas Almino (RSS)" href="/feed.xml"/>
+        <Link rel="alternate" type_="application/atom+xml" title="Nicolas Almino (Atom)" href="/atom.xml"/>
+        <Link rel="alternate" type_="application/feed+json" title="Nicolas Almino (JSON Feed)" href="/feed.json"/>
 
         <Router>
-            <main class="w-full min-h-screen overflow-x-hidden">
-                <Routes fallback=move || view! { <div class="h-screen w-full flex items-center justify-center"><h1>"404 - Not Found"</h1></div> }>
-                    <Route path=StaticSegment("") view=HomePage/>
-                    <Route path=WildcardSegment("any") view=NotFound/>
-                </Routes>
-            </main>
+            <LocalizedApp/>
         </Router>
     }
 }
 
+/// Everything that depends on the locale, which comes from the URL and so
+/// needs the router.
+#[component]
+fn LocalizedApp() -> impl IntoView {
+    let locale = provide_i18n();
+
+    view! {
+        // FIX: Added {..} to spread attributes, fixing EmptyPropsBuilder error
+        <Html {..} lang=move || locale.get().tag() class="scroll-smooth"/>
+
+        <main class="w-full min-h-screen overflow-x-hidden">
+            <Routes fallback=NotFound>
+                // English pages are the same routes under `/en`; listed first so
+                // the root wildcard does not swallow them
+                <ParentRoute path=StaticSegment("en") view=Outlet>
+                    <PageRoutes/>
+                </ParentRoute>
+                <AdminRoutes/>
+                <PageRoutes/>
+            </Routes>
+        </main>
+    }
+}
+
+/// The page routes of one locale.
+#[component(transparent)]
+fn PageRoutes() -> impl MatchNestedRoutes + Clone {
+    view! {
+        // Async so the page meta tags, which depend on content, make it into <head>
+        <Route path=StaticSegment("") view=HomePage ssr=SsrMode::Async/>
+        // Async so an unknown slug can still set the 404 status before headers go out
+        <Route path=(StaticSegment("projetos"), ParamSegment("slug")) view=ProjectPage ssr=SsrMode::Async/>
+        <Route path=StaticSegment("blog") view=BlogIndex ssr=SsrMode::Async/>
+        <Route path=(StaticSegment("blog"), StaticSegment("pagina"), ParamSegment("page")) view=BlogIndex ssr=SsrMode::Async/>
+        <Route path=(StaticSegment("blog"), StaticSegment("tag"), ParamSegment("tag")) view=BlogIndex ssr=SsrMode::Async/>
+        <Route path=(StaticSegment("blog"), StaticSegment("tag"), ParamSegment("tag"), StaticSegment("pagina"), ParamSegment("page")) view=BlogIndex ssr=SsrMode::Async/>
+        <Route path=(StaticSegment("blog"), ParamSegment("slug")) view=BlogPost ssr=SsrMode::Async/>
+        <Route path=WildcardSegment("any") view=NotFound/>
+    }
+    .into_inner()
+}
+
 /// 404 - Not Found
 #[component]
-fn NotFound() -> impl IntoView {
+pub fn NotFound() -> impl IntoView {
     #[cfg(feature = "ssr")]
     {
         let resp = expect_context::<leptos_actix::ResponseOptions>();
         resp.set_status(actix_web::http::StatusCode::NOT_FOUND);
     }
 
+    let locale = use_locale();
+    let t = locale.t();
+
     view! {
+        <PageMeta title="404 - Nicolas Almino" noindex=true/>
         <div class="h-screen w-full flex flex-col items-center justify-center bg-[#0a0a0a] text-white">
             <h1 class="text-6xl font-black mb-4">"404"</h1>
-            <p class="text-xl text-gray-400">"Página não encontrada."</p>
-            <a href="/" class="mt-8 px-6 py-3 bg-blue-600 rounded-full hover:bg-blue-500 transition-all">
-                "Voltar ao Início"
+            <p class="text-xl text-gray-400">{t.not_found}</p>
+            <a href=locale.localize("/") class="mt-8 px-6 py-3 bg-blue-600 rounded-full hover:bg-blue-500 transition-all">
+                {t.not_found_back}
             </a>
         </div>
     }
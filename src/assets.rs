@@ -0,0 +1,26 @@
+//! Fingerprinted asset URLs. `build.rs` hashes every file in `assets/`, and
+//! pages link to `/assets/logo.<hash>.svg` instead of `/assets/logo.svg`, so
+//! the files can be cached for good: a changed file gets a new URL.
+//!
+//! Paths written in the code go through the generated `asset!` macro, which
+//! does not compile for a file missing from `assets/`. Paths only known at
+//! run time (content files, skill icons) go through [`url`].
+
+include!(concat!(env!("OUT_DIR"), "/assets.rs"));
+
+/// Fingerprinted URL of a site path such as `/assets/logo.svg`. Anything
+/// else (external URLs, files added after the build) comes back unchanged.
+pub fn url(path: &str) -> String {
+    match MANIFEST.binary_search_by(|(asset, _)| (*asset).cmp(path)) {
+        Ok(i) => MANIFEST[i].1.to_string(),
+        Err(_) => path.to_string(),
+    }
+}
+
+/// The real path behind a fingerprinted URL, `None` for any other path.
+pub fn original(path: &str) -> Option<&'static str> {
+    MANIFEST
+        .iter()
+        .find(|(_, hashed)| *hashed == path)
+        .map(|(asset, _)| *asset)
+}
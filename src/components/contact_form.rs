@@ -0,0 +1,160 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::antispam::api::get_challenge;
+use crate::contact::api::SendMessage;
+use crate::contact::{ContactError, Field, EMAIL_MAX, MESSAGE_MAX, MESSAGE_MIN, NAME_MAX};
+use crate::i18n::use_locale;
+
+const INPUT_CLASS: &str = "w-full px-4 py-3 bg-white/5 border border-white/10 rounded-xl text-white placeholder-gray-500 focus:outline-none focus:border-blue-500 transition-colors";
+
+/// Inline error under a field, announced to screen readers.
+#[component]
+fn FieldMessage(id: &'static str, message: Signal<Option<String>>) -> impl IntoView {
+    view! {
+        <span id=id class="block mt-2 text-sm text-red-400 min-h-5" aria-live="polite">
+            {move || message.get()}
+        </span>
+    }
+}
+
+/// Lets the browser handle input and paint between batches of hashing.
+async fn yield_to_browser() {
+    #[cfg(feature = "hydrate")]
+    gloo_timers::future::TimeoutFuture::new(0).await;
+}
+
+/// Contact form posting to the `send_message` server function, with errors
+/// inline. The hydrated client solves a proof-of-work challenge in the
+/// background, so sending stays disabled until it is done; without
+/// JavaScript it stays disabled and a notice points to the email address.
+#[component]
+pub fn ContactForm() -> impl IntoView {
+    let locale = use_locale();
+    let t = locale.t();
+    let action = ServerAction::<SendMessage>::new();
+    let result = action.value();
+
+    // Challenges are single-use: fetch and solve a new one after every attempt
+    let token = RwSignal::new(String::new());
+    let nonce = RwSignal::new(String::new());
+    Effect::new(move |_| {
+        let version = action.version().get();
+        nonce.set(String::new());
+        spawn_local(async move {
+            if let Ok(challenge) = get_challenge().await {
+                let solution = challenge.solve_with(yield_to_browser).await;
+                // A newer attempt started meanwhile and solves its own
+                if action.version().get_untracked() == version {
+                    token.set(challenge.to_token());
+                    nonce.set(solution.to_string());
+                }
+            }
+        });
+    });
+    let ready = move || !nonce.get().is_empty() && !action.pending().get();
+
+    let error = move |field: Field| {
+        Signal::derive(move || {
+            result.with(|result| match result {
+                Some(Err(e)) => e.field(field).map(|invalid| invalid.message(locale)),
+                _ => None,
+            })
+        })
+    };
+    let (name_error, email_error, message_error) =
+        (error(Field::Name), error(Field::Email), error(Field::Message));
+    let failure = move || match result.get() {
+        Some(Err(ContactError::Rejected(rejection))) => Some(rejection.message(locale)),
+        Some(Err(ContactError::Server(_))) => Some(t.contact_failed),
+        _ => None,
+    };
+    let sent = move || matches!(result.get(), Some(Ok(())));
+
+    view! {
+        <Show
+            when=sent
+            fallback=move || view! {
+                <ActionForm action=action>
+                    <input type="hidden" name="pow_token" prop:value=move || token.get()/>
+                    <input type="hidden" name="pow_nonce" prop:value=move || nonce.get()/>
+                    // Honeypot: off screen and skipped by keyboard and screen readers
+                    <div class="absolute -left-[9999px]" aria-hidden="true">
+                        <label>
+                            "Website"
+                            <input type="text" name="website" tabindex="-1" autocomplete="off"/>
+                        </label>
+                    </div>
+                    <div class="grid grid-cols-1 md:grid-cols-2 gap-6">
+                        <label class="block">
+                            <span class="block text-sm font-bold text-gray-300 mb-2">{t.contact_name}</span>
+                            <input
+                                type="text"
+                                name="name"
+                                required=true
+                                maxlength=NAME_MAX
+                                autocomplete="name"
+                                class=INPUT_CLASS
+                                aria-invalid=move || name_error.get().is_some().to_string()
+                                aria-describedby="contact-name-error"
+                            />
+                            <FieldMessage id="contact-name-error" message=name_error/>
+                        </label>
+                        <label class="block">
+                            <span class="block text-sm font-bold text-gray-300 mb-2">{t.contact_email}</span>
+                            <input
+                                type="email"
+                                name="email"
+                                required=true
+                                maxlength=EMAIL_MAX
+                                autocomplete="email"
+                                class=INPUT_CLASS
+                                aria-invalid=move || email_error.get().is_some().to_string()
+                                aria-describedby="contact-email-error"
+                            />
+                            <FieldMessage id="contact-email-error" message=email_error/>
+                        </label>
+                    </div>
+                    <label class="block mt-2">
+                        <span class="block text-sm font-bold text-gray-300 mb-2">{t.contact_message}</span>
+                        <textarea
+                            name="message"
+                            required=true
+                            rows="6"
+                            minlength=MESSAGE_MIN
+                            maxlength=MESSAGE_MAX
+                            class=INPUT_CLASS
+                            aria-invalid=move || message_error.get().is_some().to_string()
+                            aria-describedby="contact-message-error"
+                        ></textarea>
+                        <FieldMessage id="contact-message-error" message=message_error/>
+                    </label>
+
+                    <noscript>
+                        <p class="mb-4 text-sm text-yellow-300">{t.contact_no_js}</p>
+                    </noscript>
+
+                    {move || failure().map(|message| view! {
+                        <p class="mb-4 text-sm text-red-400" role="alert">{message}</p>
+                    })}
+
+                    <button
+                        type="submit"
+                        class="px-8 py-4 rounded-full bg-white text-black font-bold hover:bg-blue-500 hover:text-white transition-colors disabled:opacity-50"
+                        disabled=move || !ready()
+                    >
+                        {move || if action.pending().get() {
+                            t.contact_sending
+                        } else if nonce.get().is_empty() {
+                            t.contact_verifying
+                        } else {
+                            t.contact_send
+                        }}
+                    </button>
+                </ActionForm>
+            }
+        >
+            <p class="text-xl text-blue-300" role="status">{t.contact_sent}</p>
+        </Show>
+    }
+}
@@ -1,5 +1,6 @@
 // File: /home/user/RustroverProjects/fronteirasdainovacao/src/components/header/navbar.rs
 use crate::components::stacks::hstack::{AlignItems, HStack};
+use crate::i18n::use_locale;
 use leptos::either::Either;
 use leptos::prelude::*;
 
@@ -78,48 +79,51 @@ fn DropdownMenu(
 
 #[component]
 pub fn Navbar() -> impl IntoView {
+    let locale = use_locale();
+    let t = locale.t();
+
     let institucional_items = vec![
         DropdownItem {
-            text: "NOSSA HISTÓRIA".to_string(),
-            href: "/nossa-historia".to_string(),
+            text: t.navbar_history.to_string(),
+            href: locale.localize("/nossa-historia"),
         },
         DropdownItem {
-            text: "TERRITÓRIOS DE ATUAÇÃO".to_string(),
-            href: "/territorios-atuacao".to_string(),
+            text: t.navbar_territories.to_string(),
+            href: locale.localize("/territorios-atuacao"),
         },
         DropdownItem {
-            text: "QUEM SOMOS".to_string(),
-            href: "/quem-somos".to_string(),
+            text: t.navbar_about_us.to_string(),
+            href: locale.localize("/quem-somos"),
         },
     ];
 
     let agenda_items = vec![
         DropdownItem {
-            text: "CALENDÁRIO DE EVENTOS".to_string(),
-            href: "/calendario-eventos".to_string(),
+            text: t.navbar_calendar.to_string(),
+            href: locale.localize("/calendario-eventos"),
         },
     ];
 
     view! {
-        <nav role="navigation" aria-label="Menu principal">
+        <nav role="navigation" aria-label=t.navbar_label>
             <HStack class="navbar" align=AlignItems::Center spacing="0.5rem".to_string()>
-                <a href="/blog" class="navbar-link">
-                    "BLOG"
+                <a href=locale.localize("/blog") class="navbar-link">
+                    {t.nav_blog}
                 </a>
-                <a href="/a-igr" class="navbar-link">
-                    "A IGR"
+                <a href=locale.localize("/a-igr") class="navbar-link">
+                    {t.navbar_igr}
                 </a>
-                <DropdownMenu title="INSTITUCIONAL".to_string() items=institucional_items/>
-                <a href="/projetos" class="navbar-link">
-                    "PROJETOS"
+                <DropdownMenu title=t.navbar_institutional.to_string() items=institucional_items/>
+                <a href=locale.localize("/projetos") class="navbar-link">
+                    {t.nav_projects}
                 </a>
                 <DropdownMenu
-                    title="AGENDA".to_string()
+                    title=t.navbar_agenda.to_string()
                     items=agenda_items
-                    redirect_href="/agenda"
+                    redirect_href=locale.localize("/agenda")
                 />
-                <a href="/midia" class="navbar-link">
-                    "MÍDIA"
+                <a href=locale.localize("/midia") class="navbar-link">
+                    {t.navbar_media}
                 </a>
             </HStack>
         </nav>
@@ -0,0 +1,18 @@
+use leptos::prelude::*;
+
+use crate::content::schema::{Absolute, JsonLdDocument};
+use crate::components::page_meta::SiteMeta;
+
+impl Absolute for SiteMeta {
+    fn absolute(&self, path: &str) -> String {
+        SiteMeta::absolute(self, path)
+    }
+}
+
+/// Structured data block, rendered into the page during SSR.
+#[component]
+pub fn JsonLd(document: JsonLdDocument) -> impl IntoView {
+    view! {
+        <script type="application/ld+json" inner_html=document.to_script()></script>
+    }
+}
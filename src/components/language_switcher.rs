@@ -0,0 +1,42 @@
+use leptos::prelude::*;
+use leptos_router::hooks::use_location;
+
+use crate::i18n::{use_locale, Locale};
+
+/// PT / EN links to the current page in the other language. They go through
+/// `/lang/{tag}`, which remembers the choice in a cookie, so they bypass the
+/// client-side router.
+#[component]
+pub fn LanguageSwitcher() -> impl IntoView {
+    let current = use_locale();
+    let pathname = use_location().pathname;
+
+    view! {
+        <nav class="flex gap-1 text-xs font-bold font-mono" aria-label=current.t().language_switch>
+            {Locale::ALL.into_iter().map(|locale| {
+                let href = move || format!(
+                    "/lang/{}?next={}",
+                    locale.tag(),
+                    locale.localize(&pathname.get()),
+                );
+                let class = if locale == current {
+                    "px-2 py-1 rounded-full bg-white/10 text-white"
+                } else {
+                    "px-2 py-1 rounded-full text-gray-400 hover:text-white transition-colors"
+                };
+                view! {
+                    <a
+                        href=href
+                        rel="external"
+                        hreflang=locale.tag()
+                        lang=locale.tag()
+                        class=class
+                        aria-current=(locale == current).then_some("true")
+                    >
+                        {locale.label()}
+                    </a>
+                }
+            }).collect_view()}
+        </nav>
+    }
+}
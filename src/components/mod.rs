@@ -1,4 +1,8 @@
+pub mod contact_form;
 pub mod header;
+pub mod json_ld;
+pub mod language_switcher;
+pub mod page_meta;
 pub mod stacks;
 pub mod stacking_container;
 pub mod scaling_panel;
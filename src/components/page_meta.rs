@@ -0,0 +1,193 @@
+use chrono::NaiveDate;
+use leptos::prelude::*;
+use leptos_meta::{Link, Meta, Title};
+use leptos_router::hooks::use_location;
+
+use crate::i18n::{use_locale, Locale};
+
+/// Site-wide defaults for [`PageMeta`]. The server provides it through context
+/// with the configured public URL; on the client it falls back to the page origin.
+/// Default title and description come from the locale's catalog.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SiteMeta {
+    /// Public base URL without trailing slash, used for canonical and `og:url`.
+    pub base_url: String,
+    pub site_name: String,
+    /// Default `og:image`, as a site path.
+    pub default_image: Option<String>,
+    /// Twitter/X handle including the `@`.
+    pub twitter_site: Option<String>,
+    /// Rendering for the static export, which has no query strings.
+    pub static_export: bool,
+}
+
+impl Default for SiteMeta {
+    fn default() -> Self {
+        SiteMeta {
+            base_url: default_base_url(),
+            site_name: "Nicolas Almino".to_string(),
+            default_image: None,
+            twitter_site: None,
+            static_export: false,
+        }
+    }
+}
+
+fn default_base_url() -> String {
+    #[cfg(feature = "hydrate")]
+    {
+        if let Ok(origin) = window().location().origin() {
+            return origin;
+        }
+    }
+    String::new()
+}
+
+impl SiteMeta {
+    /// The defaults provided by the server, or the built-in ones.
+    pub fn current() -> Self {
+        use_context::<SiteMeta>().unwrap_or_default()
+    }
+
+    /// Absolute URL for a site path; URLs that already have a scheme are kept.
+    pub fn absolute(&self, path: &str) -> String {
+        if path.starts_with("http://") || path.starts_with("https://") {
+            path.to_string()
+        } else {
+            format!("{}/{}", self.base_url, path.trim_start_matches('/'))
+        }
+    }
+}
+
+/// Generated preview card (`/og/{kind}/{slug}.png`) for a site path, if the
+/// page has one. Pages outside the default locale get `?lang=`, or, in a
+/// `static_export` where a query would be ignored, their own file under the
+/// locale prefix (`/en/og/...`).
+pub fn og_image_path(path: &str, static_export: bool) -> Option<String> {
+    let locale = Locale::from_path(path);
+    let path = Locale::strip_prefix(path);
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+    let (kind, slug) = match segments.as_slice() {
+        [""] => ("pagina", "home"),
+        ["blog"] => ("pagina", "blog"),
+        ["projetos", slug] => ("projeto", *slug),
+        ["blog", "tag", tag] => ("tag", *tag),
+        ["blog", slug] => ("blog", *slug),
+        _ => return None,
+    };
+    Some(match locale {
+        Locale::PtBr => format!("/og/{kind}/{slug}.png"),
+        locale if static_export => format!("{}/og/{kind}/{slug}.png", locale.prefix()),
+        locale => format!("/og/{kind}/{slug}.png?lang={}", locale.tag()),
+    })
+}
+
+/// `og:type` of a page.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OgType {
+    #[default]
+    Website,
+    Article,
+    Profile,
+}
+
+impl OgType {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            OgType::Website => "website",
+            OgType::Article => "article",
+            OgType::Profile => "profile",
+        }
+    }
+}
+
+/// Title, description, canonical URL, `hreflang` alternates and Open Graph /
+/// Twitter card tags for the current page. Anything left out falls back to
+/// [`SiteMeta`] and the locale's catalog.
+#[component]
+pub fn PageMeta(
+    /// Full page title. Defaults to the site title.
+    #[prop(optional, into)]
+    title: Option<String>,
+    #[prop(optional, into)] description: Option<String>,
+    /// Canonical path. Defaults to the current location.
+    #[prop(optional, into)]
+    path: Option<String>,
+    /// Preview image, as a site path or absolute URL. Defaults to the
+    /// generated card for the page.
+    #[prop(optional_no_strip, into)]
+    image: Option<String>,
+    /// Preview video (`og:video`), as a site path or absolute URL.
+    #[prop(optional_no_strip, into)]
+    video: Option<String>,
+    #[prop(optional_no_strip, into)] video_type: Option<String>,
+    #[prop(optional)] og_type: OgType,
+    /// `article:published_time` for articles.
+    #[prop(optional_no_strip)]
+    published: Option<NaiveDate>,
+    /// Adds `noindex` (error pages).
+    #[prop(optional)]
+    noindex: bool,
+) -> impl IntoView {
+    let site = SiteMeta::current();
+    let locale = use_locale();
+
+    let title = title.unwrap_or_else(|| locale.t().site_title.to_string());
+    let description = description.unwrap_or_else(|| locale.t().site_description.to_string());
+    let path = path.unwrap_or_else(|| use_location().pathname.get_untracked());
+    let url = site.absolute(&path);
+    let alternates = (!noindex).then(|| {
+        Locale::ALL
+            .into_iter()
+            .map(|alternate| (alternate.tag(), site.absolute(&alternate.localize(&path))))
+            .chain([("x-default", site.absolute(&Locale::default().localize(&path)))])
+            .map(|(hreflang, href)| view! { <Link rel="alternate" hreflang=hreflang href=href/> })
+            .collect_view()
+    });
+    let generated = image
+        .is_none()
+        .then(|| og_image_path(&path, site.static_export))
+        .flatten();
+    let image_size = generated.is_some().then_some(("1200", "630"));
+    let image = image
+        .or(generated)
+        .or_else(|| site.default_image.clone())
+        .map(|image| site.absolute(&image));
+    let video = video.map(|video| site.absolute(&video));
+    let card = if image.is_some() { "summary_large_image" } else { "summary" };
+
+    view! {
+        <Title text=title.clone()/>
+        <Meta name="description" content=description.clone()/>
+        <Link rel="canonical" href=url.clone()/>
+        {noindex.then(|| view! { <Meta name="robots" content="noindex"/> })}
+        {alternates}
+
+        <Meta property="og:type" content=og_type.as_str()/>
+        <Meta property="og:site_name" content=site.site_name.clone()/>
+        <Meta property="og:locale" content=locale.og_tag()/>
+        {Locale::ALL.into_iter().filter(|other| *other != locale).map(|other| view! {
+            <Meta property="og:locale:alternate" content=other.og_tag()/>
+        }).collect_view()}
+        <Meta property="og:title" content=title.clone()/>
+        <Meta property="og:description" content=description.clone()/>
+        <Meta property="og:url" content=url/>
+        {image.clone().map(|image| view! { <Meta property="og:image" content=image/> })}
+        {image_size.map(|(width, height)| view! {
+            <Meta property="og:image:width" content=width/>
+            <Meta property="og:image:height" content=height/>
+        })}
+        {video.map(|video| view! {
+            <Meta property="og:video" content=video.clone()/>
+            <Meta property="og:video:secure_url" content=video/>
+            <Meta property="og:video:type" content=video_type.unwrap_or_else(|| "video/mp4".to_string())/>
+        })}
+        {published.map(|date| view! { <Meta property="article:published_time" content=date.to_string()/> })}
+
+        <Meta name="twitter:card" content=card/>
+        {site.twitter_site.clone().map(|handle| view! { <Meta name="twitter:site" content=handle/> })}
+        <Meta name="twitter:title" content=title/>
+        <Meta name="twitter:description" content=description/>
+        {image.map(|image| view! { <Meta name="twitter:image" content=image/> })}
+    }
+}
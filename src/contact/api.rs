@@ -0,0 +1,63 @@
+//! Server function behind the contact form.
+
+use leptos::prelude::*;
+
+use super::{ContactError, ContactMessage};
+
+/// Checks the anti-spam fields, then validates, stores and forwards the
+/// message. Argument names match the form fields.
+#[server]
+pub async fn send_message(
+    name: String,
+    email: String,
+    message: String,
+    // Honeypot, hidden from people; bots tend to fill it in
+    #[server(default)]
+    website: String,
+    // Proof-of-work challenge token and the nonce that solves it
+    #[server(default)]
+    pow_token: String,
+    #[server(default)]
+    pow_nonce: String,
+) -> Result<(), ContactError> {
+    use actix_web::{web, HttpRequest};
+
+    use crate::contact::honeypot_filled;
+    use crate::server::antispam::Antispam;
+    use crate::server::contact::ContactService;
+    use crate::server::metrics::count_contact;
+
+    let req: HttpRequest = leptos_actix::extract()
+        .await
+        .map_err(|e| ContactError::Server(e.to_string()))?;
+    let not_configured = || ContactError::Server("contact form is not configured".to_string());
+    let antispam = req
+        .app_data::<web::Data<Antispam>>()
+        .ok_or_else(not_configured)?;
+    let service = req
+        .app_data::<web::Data<ContactService>>()
+        .ok_or_else(not_configured)?;
+
+    let ip = antispam.client_ip(&req);
+    // Pretend it worked, so the bot has nothing to adapt to
+    if honeypot_filled(&website) {
+        count_contact(&req, "honeypot");
+        return Ok(());
+    }
+    let checked = antispam
+        .check_rate(ip)
+        .and_then(|()| antispam.verify(&pow_token, &pow_nonce));
+    if let Err(rejection) = checked {
+        count_contact(&req, "rejected");
+        return Err(ContactError::Rejected(rejection));
+    }
+
+    let message = ContactMessage::new(&name, &email, &message);
+    if let Err(e) = message.validate() {
+        count_contact(&req, "invalid");
+        return Err(e);
+    }
+    let result = service.submit(message, ip.map(|ip| ip.to_string())).await;
+    count_contact(&req, if result.is_ok() { "stored" } else { "failed" });
+    result
+}
@@ -0,0 +1,324 @@
+//! Contact form: the submitted message, its validation and the typed errors
+//! the form renders inline. Storage and delivery live in `server::contact`.
+
+pub mod api;
+
+use std::fmt;
+
+use leptos::server_fn::codec::JsonEncoding;
+use leptos::server_fn::error::{FromServerFnError, ServerFnErrorErr};
+use serde::{Deserialize, Serialize};
+
+use crate::antispam::Rejection;
+use crate::i18n::Locale;
+
+pub const NAME_MAX: usize = 100;
+pub const EMAIL_MAX: usize = 254;
+pub const MESSAGE_MIN: usize = 10;
+pub const MESSAGE_MAX: usize = 5000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Field {
+    Name,
+    Email,
+    Message,
+}
+
+/// Why a field was rejected.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Invalid {
+    Required,
+    TooShort(usize),
+    TooLong(usize),
+    Email,
+}
+
+impl Invalid {
+    /// Inline error text shown under the field.
+    pub fn message(self, locale: Locale) -> String {
+        match (locale, self) {
+            (Locale::PtBr, Invalid::Required) => "Campo obrigatório.".to_string(),
+            (Locale::PtBr, Invalid::TooShort(n)) => format!("Use pelo menos {n} caracteres."),
+            (Locale::PtBr, Invalid::TooLong(n)) => format!("Use no máximo {n} caracteres."),
+            (Locale::PtBr, Invalid::Email) => "Informe um e-mail válido.".to_string(),
+            (Locale::En, Invalid::Required) => "This field is required.".to_string(),
+            (Locale::En, Invalid::TooShort(n)) => format!("Use at least {n} characters."),
+            (Locale::En, Invalid::TooLong(n)) => format!("Use at most {n} characters."),
+            (Locale::En, Invalid::Email) => "Enter a valid email address.".to_string(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FieldError {
+    pub field: Field,
+    pub error: Invalid,
+}
+
+/// Error of the contact server functions: per-field validation errors, shown
+/// inline, an anti-spam rejection, or a failure the visitor can only retry.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum ContactError {
+    Invalid(Vec<FieldError>),
+    Rejected(Rejection),
+    Server(String),
+}
+
+impl ContactError {
+    /// The validation error of `field`, if any.
+    pub fn field(&self, field: Field) -> Option<Invalid> {
+        match self {
+            ContactError::Invalid(errors) => errors
+                .iter()
+                .find(|e| e.field == field)
+                .map(|e| e.error),
+            ContactError::Rejected(_) | ContactError::Server(_) => None,
+        }
+    }
+}
+
+impl fmt::Display for ContactError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContactError::Invalid(errors) => write!(f, "{} invalid field(s)", errors.len()),
+            ContactError::Rejected(rejection) => write!(f, "rejected: {rejection}"),
+            ContactError::Server(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for ContactError {}
+
+impl FromServerFnError for ContactError {
+    type Encoder = JsonEncoding;
+
+    fn from_server_fn_error(value: ServerFnErrorErr) -> Self {
+        ContactError::Server(value.to_string())
+    }
+}
+
+/// A message as submitted, trimmed.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ContactMessage {
+    pub name: String,
+    pub email: String,
+    pub message: String,
+}
+
+impl ContactMessage {
+    pub fn new(name: &str, email: &str, message: &str) -> Self {
+        ContactMessage {
+            name: name.trim().to_string(),
+            email: email.trim().to_string(),
+            message: message.trim().to_string(),
+        }
+    }
+
+    pub fn validate(&self) -> Result<(), ContactError> {
+        let mut errors = Vec::new();
+        let mut check = |field, error: Option<Invalid>| {
+            if let Some(error) = error {
+                errors.push(FieldError { field, error });
+            }
+        };
+        check(Field::Name, length(&self.name, 1, NAME_MAX));
+        check(
+            Field::Email,
+            length(&self.email, 1, EMAIL_MAX)
+                .or_else(|| (!is_email(&self.email)).then_some(Invalid::Email)),
+        );
+        check(Field::Message, length(&self.message, MESSAGE_MIN, MESSAGE_MAX));
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(ContactError::Invalid(errors))
+        }
+    }
+}
+
+/// Whether the honeypot field, hidden from people, was filled in. Only bots
+/// do; anything in it, even blanks, gives them away.
+pub fn honeypot_filled(website: &str) -> bool {
+    !website.is_empty()
+}
+
+fn length(value: &str, min: usize, max: usize) -> Option<Invalid> {
+    match value.chars().count() {
+        0 => Some(Invalid::Required),
+        n if n < min => Some(Invalid::TooShort(min)),
+        n if n > max => Some(Invalid::TooLong(max)),
+        _ => None,
+    }
+}
+
+/// Deliberately loose: one `@`, something before it and a dotted domain
+/// after it, no whitespace. The mail server has the final word.
+pub(crate) fn is_email(value: &str) -> bool {
+    let Some((local, domain)) = value.split_once('@') else {
+        return false;
+    };
+    !local.is_empty()
+        && !domain.contains('@')
+        && domain.split('.').count() >= 2
+        && domain.split('.').all(|part| !part.is_empty())
+        && !value.chars().any(char::is_whitespace)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message() -> ContactMessage {
+        ContactMessage::new("Visitante", "visitante@example.com", "Gostaria de conversar.")
+    }
+
+    fn errors(message: &ContactMessage) -> Vec<FieldError> {
+        match message.validate() {
+            Err(ContactError::Invalid(errors)) => errors,
+            other => panic!("expected field errors, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn valid_message() {
+        assert_eq!(message().validate(), Ok(()));
+    }
+
+    #[test]
+    fn fields_are_trimmed() {
+        let message = ContactMessage::new("  Visitante\n", " a@b.co ", "\tOlá, tudo bem?  ");
+        assert_eq!(message.name, "Visitante");
+        assert_eq!(message.email, "a@b.co");
+        assert_eq!(message.message, "Olá, tudo bem?");
+    }
+
+    #[test]
+    fn empty_fields_are_required() {
+        let message = ContactMessage::new(" ", "", "\n");
+        let expected = [Field::Name, Field::Email, Field::Message].map(|field| FieldError {
+            field,
+            error: Invalid::Required,
+        });
+        assert_eq!(errors(&message), expected);
+    }
+
+    #[test]
+    fn short_message() {
+        let message = ContactMessage {
+            message: "Oi".to_string(),
+            ..message()
+        };
+        assert_eq!(
+            errors(&message),
+            [FieldError {
+                field: Field::Message,
+                error: Invalid::TooShort(MESSAGE_MIN)
+            }]
+        );
+    }
+
+    #[test]
+    fn oversized_fields() {
+        let message = ContactMessage {
+            name: "n".repeat(NAME_MAX + 1),
+            email: format!("{}@example.com", "a".repeat(EMAIL_MAX)),
+            message: "m".repeat(MESSAGE_MAX + 1),
+        };
+        let errors = errors(&message);
+        assert_eq!(errors.len(), 3);
+        assert!(errors.contains(&FieldError {
+            field: Field::Name,
+            error: Invalid::TooLong(NAME_MAX)
+        }));
+        assert!(errors.contains(&FieldError {
+            field: Field::Email,
+            error: Invalid::TooLong(EMAIL_MAX)
+        }));
+        assert!(errors.contains(&FieldError {
+            field: Field::Message,
+            error: Invalid::TooLong(MESSAGE_MAX)
+        }));
+    }
+
+    #[test]
+    fn lengths_count_characters_not_bytes() {
+        let message = ContactMessage {
+            name: "ç".repeat(NAME_MAX),
+            ..message()
+        };
+        assert_eq!(message.validate(), Ok(()));
+    }
+
+    #[test]
+    fn emails() {
+        for good in ["a@b.co", "first.last+tag@sub.example.com.br", "ação@exemplo.com"] {
+            assert!(is_email(good), "{good}");
+        }
+        for bad in [
+            "plain",
+            "@example.com",
+            "visitante@localhost",
+            "a@b@example.com",
+            "a@example..com",
+            "a@.example.com",
+            "a@example.com.",
+            "a b@example.com",
+        ] {
+            assert!(!is_email(bad), "{bad}");
+        }
+    }
+
+    #[test]
+    fn bad_email_is_reported_on_its_field() {
+        let message = ContactMessage {
+            email: "visitante@localhost".to_string(),
+            ..message()
+        };
+        let error = message.validate().unwrap_err();
+        assert_eq!(error.field(Field::Email), Some(Invalid::Email));
+        assert_eq!(error.field(Field::Name), None);
+        assert_eq!(error.field(Field::Message), None);
+    }
+
+    #[test]
+    fn honeypot() {
+        assert!(!honeypot_filled(""));
+        assert!(honeypot_filled("https://spam.example"));
+        assert!(honeypot_filled(" "));
+    }
+
+    #[test]
+    fn errors_outside_validation_have_no_field() {
+        for error in [ContactError::Rejected(Rejection::Unsolved), ContactError::Server("smtp down".to_string())] {
+            assert_eq!(error.field(Field::Name), None);
+            assert_eq!(error.field(Field::Email), None);
+            assert_eq!(error.field(Field::Message), None);
+        }
+    }
+
+    #[test]
+    fn error_messages() {
+        for locale in [Locale::PtBr, Locale::En] {
+            assert_ne!(Invalid::Required.message(locale), Invalid::Email.message(locale));
+            assert!(Invalid::TooShort(10).message(locale).contains("10"));
+            assert!(Invalid::TooLong(5000).message(locale).contains("5000"));
+        }
+        assert_eq!(Invalid::Email.message(Locale::PtBr), "Informe um e-mail válido.");
+        assert_eq!(Invalid::Email.message(Locale::En), "Enter a valid email address.");
+    }
+
+    #[test]
+    fn errors_survive_the_server_function_encoding() {
+        let errors = [
+            ContactError::Invalid(errors(&ContactMessage::default())),
+            ContactError::Invalid(Vec::new()),
+            ContactError::Rejected(Rejection::RateLimited),
+            ContactError::Server("storage failed".to_string()),
+        ];
+        for error in errors {
+            let json = serde_json::to_string(&error).unwrap();
+            assert_eq!(serde_json::from_str::<ContactError>(&json).unwrap(), error);
+        }
+    }
+}
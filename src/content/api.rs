@@ -0,0 +1,82 @@
+//! Server functions the components use to read content. Each takes the
+//! locale of the page, which picks the translated content when there is one.
+
+use leptos::prelude::*;
+
+use super::{slugify, HomeContent, Post, PostPage, Project, POSTS_PER_PAGE};
+use crate::i18n::Locale;
+
+#[server]
+pub async fn get_home_content(locale: Locale) -> Result<HomeContent, ServerFnError> {
+    let content = super::store::get_localized(locale).map_err(ServerFnError::new)?;
+    Ok(HomeContent {
+        profile: content.profile.clone(),
+        skills: content.skills.clone(),
+        experiences: content.experiences.clone(),
+        projects: content.projects.clone(),
+        today: chrono::Local::now().date_naive(),
+    })
+}
+
+#[server]
+pub async fn get_project(locale: Locale, slug: String) -> Result<Option<Project>, ServerFnError> {
+    let content = super::store::get_localized(locale).map_err(ServerFnError::new)?;
+    Ok(content.projects.iter().find(|p| p.slug == slug).cloned())
+}
+
+/// Lists published posts, optionally restricted to a tag (matched by slug).
+/// Returns `None` for an unknown tag or a page past the end.
+#[server]
+pub async fn get_posts(
+    locale: Locale,
+    page: usize,
+    tag: Option<String>,
+) -> Result<Option<PostPage>, ServerFnError> {
+    let content = super::store::get_localized(locale).map_err(ServerFnError::new)?;
+
+    let mut tag_name = None;
+    let posts = content
+        .published_posts()
+        .filter(|post| match &tag {
+            None => true,
+            Some(tag) => match post.tags.iter().find(|t| slugify(t) == *tag) {
+                Some(name) => {
+                    tag_name.get_or_insert_with(|| name.clone());
+                    true
+                }
+                None => false,
+            },
+        })
+        .collect::<Vec<_>>();
+
+    if tag.is_some() && posts.is_empty() {
+        return Ok(None);
+    }
+    let total_pages = posts.len().div_ceil(POSTS_PER_PAGE).max(1);
+    if page == 0 || page > total_pages {
+        return Ok(None);
+    }
+
+    let posts = posts
+        .into_iter()
+        .skip((page - 1) * POSTS_PER_PAGE)
+        .take(POSTS_PER_PAGE)
+        .map(|post| Post {
+            body_html: String::new(),
+            ..post.clone()
+        })
+        .collect();
+
+    Ok(Some(PostPage {
+        posts,
+        page,
+        total_pages,
+        tag: tag_name,
+    }))
+}
+
+#[server]
+pub async fn get_post(locale: Locale, slug: String) -> Result<Option<Post>, ServerFnError> {
+    let content = super::store::get_localized(locale).map_err(ServerFnError::new)?;
+    Ok(content.published_posts().find(|p| p.slug == slug).cloned())
+}
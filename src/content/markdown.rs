@@ -0,0 +1,32 @@
+use pulldown_cmark::{html, Options, Parser};
+
+/// Splits a document into its TOML front matter (between `+++` fences) and
+/// the Markdown body. Documents without front matter return `None`.
+pub fn split_front_matter(source: &str) -> Option<(&str, &str)> {
+    let source = source.trim_start_matches('\u{feff}');
+    let rest = source.strip_prefix("+++")?;
+    let rest = rest
+        .strip_prefix("\r\n")
+        .or_else(|| rest.strip_prefix('\n'))?;
+    let end = rest.find("\n+++")?;
+    let front = &rest[..end];
+    let body = &rest[end + 4..];
+    let body = body
+        .strip_prefix("\r\n")
+        .or_else(|| body.strip_prefix('\n'))
+        .unwrap_or(body);
+    Some((front, body))
+}
+
+/// Renders Markdown to HTML. Only called on the server so the hydrate bundle
+/// never carries the parser.
+pub fn render(markdown: &str) -> String {
+    let options = Options::ENABLE_TABLES
+        | Options::ENABLE_FOOTNOTES
+        | Options::ENABLE_STRIKETHROUGH
+        | Options::ENABLE_SMART_PUNCTUATION;
+    let parser = Parser::new_ext(markdown, options);
+    let mut out = String::with_capacity(markdown.len() * 3 / 2);
+    html::push_html(&mut out, parser);
+    out
+}
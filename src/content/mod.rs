@@ -0,0 +1,256 @@
+//! Site content (profile, skills, experience and projects).
+//!
+//! The data types here are shared between the server and the hydrated client,
+//! so they only depend on `serde`. Loading from disk lives in [`store`], which
+//! is compiled for the server only; components fetch content through the
+//! server functions in [`api`].
+
+pub mod api;
+#[cfg(feature = "ssr")]
+pub mod markdown;
+pub mod schema;
+#[cfg(feature = "ssr")]
+pub mod store;
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+// --- Data Structs ---
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ProfileData {
+    pub name: String,
+    pub title: String,
+    #[serde(default)]
+    pub tagline: String,
+    pub email: String,
+    pub linkedin: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Experience {
+    pub title: String,
+    pub company: String,
+    pub start: NaiveDate,
+    /// `None` while the role is ongoing.
+    #[serde(default)]
+    pub end: Option<NaiveDate>,
+    #[serde(default)]
+    pub tasks: Vec<String>,
+    /// Skill tags used to filter the timeline.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+impl Experience {
+    /// Length of the role in whole months, counting both the start and the
+    /// end month. Ongoing roles are measured up to `today`.
+    pub fn months(&self, today: NaiveDate) -> u32 {
+        months_between(self.start, self.end.unwrap_or(today))
+    }
+}
+
+fn month_index(date: NaiveDate) -> i64 {
+    date.year() as i64 * 12 + date.month0() as i64
+}
+
+fn months_between(start: NaiveDate, end: NaiveDate) -> u32 {
+    (month_index(end) - month_index(start) + 1).max(0) as u32
+}
+
+/// Total career length in months. Overlapping roles are only counted once.
+pub fn total_months(experiences: &[Experience], today: NaiveDate) -> u32 {
+    let mut periods = experiences
+        .iter()
+        .map(|e| (month_index(e.start), month_index(e.end.unwrap_or(today))))
+        .filter(|(start, end)| start <= end)
+        .collect::<Vec<_>>();
+    periods.sort_unstable();
+
+    let mut total = 0;
+    let mut current: Option<(i64, i64)> = None;
+    for (start, end) in periods {
+        current = match current {
+            Some((cur_start, cur_end)) if start <= cur_end + 1 => {
+                Some((cur_start, cur_end.max(end)))
+            }
+            Some((cur_start, cur_end)) => {
+                total += cur_end - cur_start + 1;
+                Some((start, end))
+            }
+            None => Some((start, end)),
+        };
+    }
+    if let Some((start, end)) = current {
+        total += end - start + 1;
+    }
+    total as u32
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SkillItem {
+    pub name: String,
+    /// File name (without extension) of the icon in `assets/icons`.
+    pub icon: String,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct SkillCategory {
+    pub category: String,
+    #[serde(default)]
+    pub items: Vec<SkillItem>,
+}
+
+/// A video source for a project cover, one per transcoded resolution.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct VideoSource {
+    pub src: String,
+    #[serde(default = "default_video_type", rename = "type")]
+    pub mime: String,
+    /// Optional media query so smaller screens pick a lighter file.
+    #[serde(default)]
+    pub media: Option<String>,
+}
+
+fn default_video_type() -> String {
+    "video/mp4".to_string()
+}
+
+/// An image in a project gallery.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GalleryItem {
+    pub src: String,
+    #[serde(default)]
+    pub alt: String,
+    #[serde(default)]
+    pub caption: Option<String>,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Project {
+    #[serde(default)]
+    pub slug: String,
+    pub title: String,
+    pub summary: String,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Publication date, used to sort feeds and for `lastmod`.
+    #[serde(default)]
+    pub date: Option<NaiveDate>,
+    #[serde(default)]
+    pub challenge: Option<String>,
+    #[serde(default)]
+    pub solution: Option<String>,
+    #[serde(default)]
+    pub results: Option<String>,
+    /// External link (live site, repository...).
+    #[serde(default)]
+    pub url: Option<String>,
+    #[serde(default)]
+    pub url_label: Option<String>,
+    /// Cover image, used when there is no video (and as the video poster).
+    #[serde(default)]
+    pub poster: Option<String>,
+    #[serde(default)]
+    pub videos: Vec<VideoSource>,
+    #[serde(default)]
+    pub gallery: Vec<GalleryItem>,
+    /// Lower numbers are shown first on the home page.
+    #[serde(default)]
+    pub order: i32,
+    /// Markdown body rendered to HTML when the content is loaded.
+    #[serde(default, skip_deserializing)]
+    pub body_html: String,
+}
+
+impl Project {
+    /// Projects with a cover video get the large showcase layout.
+    pub fn is_featured(&self) -> bool {
+        !self.videos.is_empty()
+    }
+
+    pub fn path(&self) -> String {
+        format!("/projetos/{}", self.slug)
+    }
+}
+
+/// A blog post from `content/blog/*.md`.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Post {
+    #[serde(default)]
+    pub slug: String,
+    pub title: String,
+    pub date: NaiveDate,
+    #[serde(default)]
+    pub tags: Vec<String>,
+    /// Drafts are loaded but never listed or served.
+    #[serde(default)]
+    pub draft: bool,
+    #[serde(default)]
+    pub summary: String,
+    /// Markdown body rendered to HTML when the content is loaded. Left empty
+    /// in listings to keep the payload small.
+    #[serde(default, skip_deserializing)]
+    pub body_html: String,
+}
+
+impl Post {
+    pub fn path(&self) -> String {
+        format!("/blog/{}", self.slug)
+    }
+}
+
+/// One page of the blog index or of a tag archive.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PostPage {
+    pub posts: Vec<Post>,
+    /// 1-based page number.
+    pub page: usize,
+    pub total_pages: usize,
+    /// Display name of the tag when listing a tag archive.
+    pub tag: Option<String>,
+}
+
+pub const POSTS_PER_PAGE: usize = 10;
+
+/// Path of a tag archive page.
+pub fn tag_path(tag: &str) -> String {
+    format!("/blog/tag/{}", slugify(tag))
+}
+
+/// Lowercase ASCII slug: "Rust & Wasm" -> "rust-wasm", "Edição" -> "edicao".
+pub fn slugify(text: &str) -> String {
+    let mut slug = String::with_capacity(text.len());
+    for c in text.chars().flat_map(char::to_lowercase) {
+        let c = match c {
+            'á' | 'à' | 'â' | 'ã' | 'ä' => 'a',
+            'é' | 'è' | 'ê' | 'ë' => 'e',
+            'í' | 'ì' | 'î' | 'ï' => 'i',
+            'ó' | 'ò' | 'ô' | 'õ' | 'ö' => 'o',
+            'ú' | 'ù' | 'û' | 'ü' => 'u',
+            'ç' => 'c',
+            'ñ' => 'n',
+            c => c,
+        };
+        if c.is_ascii_alphanumeric() {
+            slug.push(c);
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    while slug.ends_with('-') {
+        slug.pop();
+    }
+    slug
+}
+
+/// Everything the home page needs, fetched in a single round trip.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct HomeContent {
+    pub profile: ProfileData,
+    pub skills: Vec<SkillCategory>,
+    pub experiences: Vec<Experience>,
+    pub projects: Vec<Project>,
+    /// Server date used to measure ongoing roles, so SSR and hydration agree.
+    pub today: NaiveDate,
+}
@@ -0,0 +1,337 @@
+//! schema.org structured data (JSON-LD) built from the content types.
+//!
+//! Required properties are plain fields and optional ones are `Option`s, so
+//! a block that Google would reject (e.g. a `VideoObject` without
+//! `uploadDate`) does not type-check instead of failing in Search Console.
+
+use chrono::NaiveDate;
+use serde::Serialize;
+
+use super::{Post, ProfileData, Project};
+
+/// A top-level JSON-LD document: one or more nodes sharing the schema.org context.
+#[derive(Clone, Debug, Serialize)]
+pub struct JsonLdDocument {
+    #[serde(rename = "@context")]
+    context: &'static str,
+    #[serde(rename = "@graph")]
+    graph: Vec<Thing>,
+}
+
+impl JsonLdDocument {
+    pub fn new(graph: Vec<Thing>) -> Self {
+        JsonLdDocument {
+            context: "https://schema.org",
+            graph,
+        }
+    }
+
+    /// Serializes for a `<script type="application/ld+json">` body. `<` is
+    /// escaped so content can never close the script element.
+    pub fn to_script(&self) -> String {
+        serde_json::to_string(self)
+            .unwrap_or_default()
+            .replace('<', "\\u003c")
+    }
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(tag = "@type")]
+pub enum Thing {
+    Person(Person),
+    CreativeWork(CreativeWork),
+    VideoObject(VideoObject),
+    BlogPosting(BlogPosting),
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Person {
+    pub name: String,
+    pub url: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub job_title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub email: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub same_as: Vec<String>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct CreativeWork {
+    pub name: String,
+    pub description: String,
+    pub url: String,
+    pub author: Person,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date_published: Option<NaiveDate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub image: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub video: Option<VideoObject>,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VideoObject {
+    pub name: String,
+    pub description: String,
+    pub thumbnail_url: String,
+    pub upload_date: NaiveDate,
+    pub content_url: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BlogPosting {
+    pub headline: String,
+    pub description: String,
+    pub url: String,
+    pub main_entity_of_page: String,
+    pub date_published: NaiveDate,
+    pub author: Person,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keywords: Option<String>,
+}
+
+/// Turns site paths into absolute URLs.
+pub trait Absolute {
+    fn absolute(&self, path: &str) -> String;
+}
+
+impl Person {
+    pub fn from_profile(profile: &ProfileData, site: &impl Absolute) -> Self {
+        Person {
+            name: profile.name.clone(),
+            url: site.absolute("/"),
+            job_title: non_empty(&profile.title),
+            email: non_empty(&profile.email),
+            same_as: non_empty(&profile.linkedin).into_iter().collect(),
+        }
+    }
+
+    /// Minimal author reference when the full profile is not at hand.
+    pub fn reference(name: &str, site: &impl Absolute) -> Self {
+        Person {
+            name: name.to_string(),
+            url: site.absolute("/"),
+            job_title: None,
+            email: None,
+            same_as: Vec::new(),
+        }
+    }
+}
+
+/// Blank profile fields are left out rather than sent as `""`.
+fn non_empty(text: &str) -> Option<String> {
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+fn keywords(tags: &[String]) -> Option<String> {
+    (!tags.is_empty()).then(|| tags.join(", "))
+}
+
+impl VideoObject {
+    /// The cover video of a project. Requires a poster (thumbnail) and a date.
+    pub fn from_project(project: &Project, site: &impl Absolute) -> Option<Self> {
+        Some(VideoObject {
+            name: project.title.clone(),
+            description: project.summary.clone(),
+            thumbnail_url: site.absolute(project.poster.as_deref()?),
+            upload_date: project.date?,
+            content_url: site.absolute(&project.videos.first()?.src),
+        })
+    }
+}
+
+impl CreativeWork {
+    pub fn from_project(project: &Project, author: Person, site: &impl Absolute) -> Self {
+        CreativeWork {
+            name: project.title.clone(),
+            description: project.summary.clone(),
+            url: site.absolute(&project.path()),
+            author,
+            date_published: project.date,
+            image: project.poster.as_deref().map(|poster| site.absolute(poster)),
+            keywords: keywords(&project.tags),
+            video: VideoObject::from_project(project, site),
+        }
+    }
+}
+
+impl BlogPosting {
+    pub fn from_post(post: &Post, author: Person, site: &impl Absolute) -> Self {
+        let url = site.absolute(&post.path());
+        BlogPosting {
+            headline: post.title.clone(),
+            description: post.summary.clone(),
+            main_entity_of_page: url.clone(),
+            url,
+            date_published: post.date,
+            author,
+            keywords: keywords(&post.tags),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::Value;
+
+    use super::*;
+    use crate::content::VideoSource;
+
+    struct Site;
+
+    impl Absolute for Site {
+        fn absolute(&self, path: &str) -> String {
+            format!("https://example.com/{}", path.trim_start_matches('/'))
+        }
+    }
+
+    fn profile() -> ProfileData {
+        ProfileData {
+            name: "Nicolas Almino".to_string(),
+            title: "Software Engineer".to_string(),
+            tagline: String::new(),
+            email: "hello@example.com".to_string(),
+            linkedin: "https://www.linkedin.com/in/example".to_string(),
+        }
+    }
+
+    fn project() -> Project {
+        Project {
+            slug: "studio".to_string(),
+            title: "Studio".to_string(),
+            summary: "A video editor.".to_string(),
+            tags: vec!["rust".to_string(), "wasm".to_string()],
+            date: NaiveDate::from_ymd_opt(2024, 5, 2),
+            poster: Some("/projects/studio.png".to_string()),
+            videos: vec![VideoSource {
+                src: "/videos/studio.mp4".to_string(),
+                mime: "video/mp4".to_string(),
+                media: None,
+            }],
+            ..Project::default()
+        }
+    }
+
+    fn post() -> Post {
+        Post {
+            slug: "hello".to_string(),
+            title: "Hello".to_string(),
+            date: NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            summary: "First post.".to_string(),
+            ..Post::default()
+        }
+    }
+
+    /// The single node of a document, as JSON.
+    fn node(thing: Thing) -> Value {
+        let document = serde_json::to_value(JsonLdDocument::new(vec![thing])).unwrap();
+        assert_eq!(document["@context"], "https://schema.org");
+        document["@graph"][0].clone()
+    }
+
+    #[test]
+    fn person() {
+        let person = node(Thing::Person(Person::from_profile(&profile(), &Site)));
+        assert_eq!(person["@type"], "Person");
+        assert_eq!(person["name"], "Nicolas Almino");
+        assert_eq!(person["jobTitle"], "Software Engineer");
+        assert_eq!(person["url"], "https://example.com/");
+        assert_eq!(person["sameAs"][0], "https://www.linkedin.com/in/example");
+    }
+
+    #[test]
+    fn person_leaves_out_blank_profile_fields() {
+        let profile = ProfileData {
+            title: String::new(),
+            email: " ".to_string(),
+            linkedin: String::new(),
+            ..profile()
+        };
+        let person = node(Thing::Person(Person::from_profile(&profile, &Site)));
+        assert_eq!(person["name"], "Nicolas Almino");
+        assert!(person.get("jobTitle").is_none());
+        assert!(person.get("email").is_none());
+        assert!(person.get("sameAs").is_none());
+    }
+
+    #[test]
+    fn person_reference_leaves_out_empty_fields() {
+        let person = node(Thing::Person(Person::reference("Nicolas Almino", &Site)));
+        assert_eq!(person["name"], "Nicolas Almino");
+        assert!(person.get("jobTitle").is_none());
+        assert!(person.get("sameAs").is_none());
+    }
+
+    #[test]
+    fn creative_work() {
+        let author = Person::from_profile(&profile(), &Site);
+        let work = node(Thing::CreativeWork(CreativeWork::from_project(&project(), author, &Site)));
+        assert_eq!(work["@type"], "CreativeWork");
+        assert_eq!(work["name"], "Studio");
+        assert_eq!(work["url"], "https://example.com/projetos/studio");
+        assert_eq!(work["datePublished"], "2024-05-02");
+        assert_eq!(work["keywords"], "rust, wasm");
+        assert_eq!(work["author"]["name"], "Nicolas Almino");
+        assert_eq!(work["video"]["thumbnailUrl"], "https://example.com/projects/studio.png");
+    }
+
+    #[test]
+    fn video_object() {
+        let video = node(Thing::VideoObject(VideoObject::from_project(&project(), &Site).unwrap()));
+        assert_eq!(video["@type"], "VideoObject");
+        assert_eq!(video["name"], "Studio");
+        assert_eq!(video["thumbnailUrl"], "https://example.com/projects/studio.png");
+        assert_eq!(video["contentUrl"], "https://example.com/videos/studio.mp4");
+        assert_eq!(video["uploadDate"], "2024-05-02");
+    }
+
+    #[test]
+    fn video_object_needs_poster_and_date() {
+        let no_poster = Project {
+            poster: None,
+            ..project()
+        };
+        let no_date = Project {
+            date: None,
+            ..project()
+        };
+        assert!(VideoObject::from_project(&no_poster, &Site).is_none());
+        assert!(VideoObject::from_project(&no_date, &Site).is_none());
+    }
+
+    #[test]
+    fn blog_posting() {
+        let author = Person::reference("Nicolas Almino", &Site);
+        let posting = node(Thing::BlogPosting(BlogPosting::from_post(&post(), author, &Site)));
+        assert_eq!(posting["@type"], "BlogPosting");
+        assert_eq!(posting["headline"], "Hello");
+        assert_eq!(posting["datePublished"], "2025-01-15");
+        assert_eq!(posting["url"], "https://example.com/blog/hello");
+        assert_eq!(posting["mainEntityOfPage"], posting["url"]);
+        assert!(posting.get("keywords").is_none());
+    }
+
+    #[test]
+    fn script_cannot_be_closed_by_content() {
+        let post = Post {
+            title: "</script><script>alert(1)</script>".to_string(),
+            ..post()
+        };
+        let author = Person::reference("Nicolas Almino", &Site);
+        let script = JsonLdDocument::new(vec![Thing::BlogPosting(BlogPosting::from_post(&post, author, &Site))])
+            .to_script();
+        assert!(!script.contains("</script"));
+        assert!(script.contains("\\u003c/script>"));
+        // Still the same document once parsed
+        let parsed: Value = serde_json::from_str(&script).unwrap();
+        assert_eq!(parsed["@graph"][0]["headline"], post.title);
+    }
+}
@@ -0,0 +1,218 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, OnceLock, RwLock};
+
+use serde::{Deserialize, Serialize};
+
+use super::{markdown, Experience, Post, ProfileData, Project, SkillCategory};
+use crate::i18n::Locale;
+
+/// In-memory snapshot of the `content/` directory.
+#[derive(Clone, Debug, Default)]
+pub struct Content {
+    pub profile: ProfileData,
+    pub skills: Vec<SkillCategory>,
+    pub experiences: Vec<Experience>,
+    pub projects: Vec<Project>,
+    /// Newest first, drafts included.
+    pub posts: Vec<Post>,
+}
+
+#[derive(Debug)]
+pub enum ContentError {
+    Io(PathBuf, std::io::Error),
+    Parse(PathBuf, String),
+    NotInitialized,
+}
+
+impl fmt::Display for ContentError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContentError::Io(path, err) => write!(f, "{}: {err}", path.display()),
+            ContentError::Parse(path, err) => write!(f, "{}: {err}", path.display()),
+            ContentError::NotInitialized => write!(f, "content store was not initialized"),
+        }
+    }
+}
+
+impl std::error::Error for ContentError {}
+
+/// Layout of `skills.toml`.
+#[derive(Serialize, Deserialize)]
+pub struct SkillsFile {
+    #[serde(default)]
+    pub categories: Vec<SkillCategory>,
+}
+
+/// Layout of `experience.toml`.
+#[derive(Serialize, Deserialize)]
+pub struct ExperienceFile {
+    #[serde(default)]
+    pub experiences: Vec<Experience>,
+}
+
+struct Store {
+    dir: PathBuf,
+    content: RwLock<HashMap<Locale, Arc<Content>>>,
+}
+
+static STORE: OnceLock<Store> = OnceLock::new();
+
+/// Loads the content directory and makes it available to [`get`].
+/// Must be called once at startup, before the server starts accepting requests.
+pub fn init(dir: impl Into<PathBuf>) -> Result<(), ContentError> {
+    let dir = dir.into();
+    let content = load_all(&dir)?;
+    let _ = STORE.set(Store {
+        dir,
+        content: RwLock::new(content),
+    });
+    Ok(())
+}
+
+/// Returns the current content snapshot in the default locale.
+pub fn get() -> Result<Arc<Content>, ContentError> {
+    get_localized(Locale::default())
+}
+
+/// Returns the current content snapshot for `locale`.
+pub fn get_localized(locale: Locale) -> Result<Arc<Content>, ContentError> {
+    let store = STORE.get().ok_or(ContentError::NotInitialized)?;
+    let content = store.content.read().unwrap_or_else(|e| e.into_inner());
+    content
+        .get(&locale)
+        .or_else(|| content.get(&Locale::default()))
+        .cloned()
+        .ok_or(ContentError::NotInitialized)
+}
+
+/// Re-reads the content directory. On error the previous snapshot is kept.
+pub fn reload() -> Result<(), ContentError> {
+    let store = STORE.get().ok_or(ContentError::NotInitialized)?;
+    let content = load_all(&store.dir)?;
+    *store.content.write().unwrap_or_else(|e| e.into_inner()) = content;
+    Ok(())
+}
+
+/// The default locale reads `dir` directly; other locales read their
+/// subdirectory (`content/en`) and fall back to the default files for
+/// anything they do not translate.
+fn load_all(dir: &Path) -> Result<HashMap<Locale, Arc<Content>>, ContentError> {
+    Locale::ALL
+        .into_iter()
+        .map(|locale| {
+            let overlay = match locale.prefix().trim_start_matches('/') {
+                "" => None,
+                subdir => Some(dir.join(subdir)),
+            };
+            let content = Content::load(dir, overlay.as_deref())?;
+            Ok((locale, Arc::new(content)))
+        })
+        .collect()
+}
+
+impl Content {
+    pub fn load(dir: &Path, overlay: Option<&Path>) -> Result<Self, ContentError> {
+        // A translated file wins over the default one
+        let file = |name: &str| match overlay.map(|o| o.join(name)) {
+            Some(path) if path.exists() => path,
+            _ => dir.join(name),
+        };
+        let documents = |name: &str| -> Result<Vec<_>, ContentError> {
+            let mut docs = read_documents(&dir.join(name))?;
+            if let Some(overlay) = overlay {
+                for doc in read_documents(&overlay.join(name))? {
+                    docs.retain(|(stem, _, _)| *stem != doc.0);
+                    docs.push(doc);
+                }
+            }
+            Ok(docs)
+        };
+
+        let profile = read_toml::<ProfileData>(&file("profile.toml"))?;
+        let skills = read_toml::<SkillsFile>(&file("skills.toml"))?.categories;
+        let experiences = read_toml::<ExperienceFile>(&file("experience.toml"))?.experiences;
+        let mut projects = documents("projects")?
+            .into_iter()
+            .map(|(slug, body, mut project)| {
+                if project.slug.is_empty() {
+                    project.slug = slug;
+                }
+                project.body_html = markdown::render(&body);
+                project
+            })
+            .collect::<Vec<_>>();
+        projects.sort_by(|a, b| a.order.cmp(&b.order).then_with(|| a.slug.cmp(&b.slug)));
+
+        let mut posts = documents("blog")?
+            .into_iter()
+            .map(|(slug, body, mut post)| {
+                if post.slug.is_empty() {
+                    post.slug = slug;
+                }
+                post.body_html = markdown::render(&body);
+                post
+            })
+            .collect::<Vec<_>>();
+        posts.sort_by(|a, b| b.date.cmp(&a.date).then_with(|| a.slug.cmp(&b.slug)));
+
+        Ok(Content {
+            profile,
+            skills,
+            experiences,
+            projects,
+            posts,
+        })
+    }
+
+    /// Posts that are visible on the site, newest first.
+    pub fn published_posts(&self) -> impl Iterator<Item = &Post> {
+        self.posts.iter().filter(|p| !p.draft)
+    }
+}
+
+fn read_to_string(path: &Path) -> Result<String, ContentError> {
+    fs::read_to_string(path).map_err(|e| ContentError::Io(path.to_path_buf(), e))
+}
+
+fn read_toml<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<T, ContentError> {
+    let source = read_to_string(path)?;
+    toml::from_str(&source).map_err(|e| ContentError::Parse(path.to_path_buf(), e.to_string()))
+}
+
+/// Reads every `*.md` file in `dir` as a front-matter document.
+/// Returns `(file stem, markdown body, front matter)` tuples.
+fn read_documents<T: for<'de> Deserialize<'de>>(
+    dir: &Path,
+) -> Result<Vec<(String, String, T)>, ContentError> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(ContentError::Io(dir.to_path_buf(), e)),
+    };
+
+    let mut docs = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| ContentError::Io(dir.to_path_buf(), e))?
+            .path();
+        if path.extension().and_then(|e| e.to_str()) != Some("md") {
+            continue;
+        }
+        let source = read_to_string(&path)?;
+        let (front, body) = markdown::split_front_matter(&source).ok_or_else(|| {
+            ContentError::Parse(path.clone(), "missing `+++` front matter".into())
+        })?;
+        let meta =
+            toml::from_str(front).map_err(|e| ContentError::Parse(path.clone(), e.to_string()))?;
+        let stem = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or_default()
+            .to_string();
+        docs.push((stem, body.to_string(), meta));
+    }
+    Ok(docs)
+}
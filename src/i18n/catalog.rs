@@ -0,0 +1,220 @@
+//! Translation catalogs. Every string is a field, so a missing translation is
+//! a compile error rather than a blank label.
+
+pub struct Catalog {
+    pub site_title: &'static str,
+    pub site_description: &'static str,
+    pub months: [&'static str; 12],
+    pub months_short: [&'static str; 12],
+
+    // Navigation
+    pub nav_projects: &'static str,
+    pub nav_skills: &'static str,
+    pub nav_about: &'static str,
+    pub nav_blog: &'static str,
+    pub nav_contact: &'static str,
+    pub language_switch: &'static str,
+
+    // Home
+    pub hero_cta: &'static str,
+    pub skills_title: &'static str,
+    pub projects_title: &'static str,
+    pub challenge: &'static str,
+    pub solution: &'static str,
+    pub results: &'static str,
+    pub view_case: &'static str,
+    pub visit: &'static str,
+    pub timeline_title: &'static str,
+    pub timeline_all: &'static str,
+    pub timeline_present: &'static str,
+    pub show_less: &'static str,
+    pub show_more: &'static str,
+    pub contact_intro: &'static str,
+    pub contact_name: &'static str,
+    pub contact_email: &'static str,
+    pub contact_message: &'static str,
+    pub contact_send: &'static str,
+    pub contact_sending: &'static str,
+    pub contact_verifying: &'static str,
+    pub contact_sent: &'static str,
+    pub contact_failed: &'static str,
+    /// Shown without JavaScript, which the anti-spam check needs.
+    pub contact_no_js: &'static str,
+    pub contact_direct: &'static str,
+    pub footer_credits: &'static str,
+    pub footer_powered: &'static str,
+
+    // Projects and blog
+    pub back_to_projects: &'static str,
+    pub back_home: &'static str,
+    pub back_to_blog: &'static str,
+    pub blog_description: &'static str,
+    pub blog_all_posts: &'static str,
+    pub blog_empty: &'static str,
+    pub newer: &'static str,
+    pub older: &'static str,
+    pub pagination: &'static str,
+
+    // Preview cards (server/og_image)
+    pub og_project: &'static str,
+    pub og_blog: &'static str,
+
+    // 404
+    pub not_found: &'static str,
+    pub not_found_back: &'static str,
+
+    // Navbar (components/header)
+    pub navbar_label: &'static str,
+    pub navbar_igr: &'static str,
+    pub navbar_institutional: &'static str,
+    pub navbar_history: &'static str,
+    pub navbar_territories: &'static str,
+    pub navbar_about_us: &'static str,
+    pub navbar_agenda: &'static str,
+    pub navbar_calendar: &'static str,
+    pub navbar_media: &'static str,
+}
+
+pub static PT_BR: Catalog = Catalog {
+    site_title: "Nicolas Almino - Fullstack & Audiovisual",
+    site_description: "Desenvolvedor fullstack e audiovisual: aplicações web de alta performance em Rust e produção de vídeo.",
+    months: [
+        "janeiro", "fevereiro", "março", "abril", "maio", "junho", "julho", "agosto", "setembro",
+        "outubro", "novembro", "dezembro",
+    ],
+    months_short: [
+        "jan", "fev", "mar", "abr", "mai", "jun", "jul", "ago", "set", "out", "nov", "dez",
+    ],
+
+    nav_projects: "PROJETOS",
+    nav_skills: "SKILLS",
+    nav_about: "SOBRE",
+    nav_blog: "BLOG",
+    nav_contact: "CONTATO",
+    language_switch: "Idioma",
+
+    hero_cta: "VER PROJETOS",
+    skills_title: "ARSENAL TÉCNICO",
+    projects_title: "PROJETOS SELECIONADOS",
+    challenge: "Desafio",
+    solution: "Solução",
+    results: "Resultados",
+    view_case: "VER CASE COMPLETO",
+    visit: "Acessar",
+    timeline_title: "TRAJETÓRIA",
+    timeline_all: "TODOS",
+    timeline_present: "atual",
+    show_less: "MOSTRAR MENOS",
+    show_more: "MOSTRAR MAIS",
+    contact_intro: "Tem um projeto em mente? Mande uma mensagem.",
+    contact_name: "Nome",
+    contact_email: "E-mail",
+    contact_message: "Mensagem",
+    contact_send: "ENVIAR",
+    contact_sending: "ENVIANDO…",
+    contact_verifying: "VERIFICANDO…",
+    contact_sent: "Mensagem enviada. Obrigado! Respondo em breve.",
+    contact_failed: "Não foi possível enviar agora. Tente novamente em instantes.",
+    contact_no_js: "O formulário precisa de JavaScript para a verificação anti-spam. Sem ele, escreva para o e-mail abaixo.",
+    contact_direct: "Prefere e-mail? Escreva para",
+    footer_credits: "DESIGNED & CODED BY NICOLAS ALMINO",
+    footer_powered: "POWERED BY RUST & LEPTOS",
+
+    back_to_projects: "← VOLTAR AOS PROJETOS",
+    back_home: "← INÍCIO",
+    back_to_blog: "← BLOG",
+    blog_description: "Artigos sobre desenvolvimento web, Rust e audiovisual.",
+    blog_all_posts: "VER TODOS OS POSTS",
+    blog_empty: "Nenhum post publicado ainda.",
+    newer: "← MAIS RECENTES",
+    older: "MAIS ANTIGOS →",
+    pagination: "Paginação",
+
+    og_project: "PROJETO",
+    og_blog: "Artigos e notas",
+
+    not_found: "Página não encontrada.",
+    not_found_back: "Voltar ao Início",
+
+    navbar_label: "Menu principal",
+    navbar_igr: "A IGR",
+    navbar_institutional: "INSTITUCIONAL",
+    navbar_history: "NOSSA HISTÓRIA",
+    navbar_territories: "TERRITÓRIOS DE ATUAÇÃO",
+    navbar_about_us: "QUEM SOMOS",
+    navbar_agenda: "AGENDA",
+    navbar_calendar: "CALENDÁRIO DE EVENTOS",
+    navbar_media: "MÍDIA",
+};
+
+pub static EN: Catalog = Catalog {
+    site_title: "Nicolas Almino - Fullstack & Audiovisual",
+    site_description: "Fullstack and audiovisual developer: high-performance web applications in Rust and video production.",
+    months: [
+        "January", "February", "March", "April", "May", "June", "July", "August", "September",
+        "October", "November", "December",
+    ],
+    months_short: [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ],
+
+    nav_projects: "PROJECTS",
+    nav_skills: "SKILLS",
+    nav_about: "ABOUT",
+    nav_blog: "BLOG",
+    nav_contact: "CONTACT",
+    language_switch: "Language",
+
+    hero_cta: "SEE PROJECTS",
+    skills_title: "TECH ARSENAL",
+    projects_title: "SELECTED PROJECTS",
+    challenge: "Challenge",
+    solution: "Solution",
+    results: "Results",
+    view_case: "VIEW FULL CASE STUDY",
+    visit: "Visit",
+    timeline_title: "CAREER",
+    timeline_all: "ALL",
+    timeline_present: "present",
+    show_less: "SHOW LESS",
+    show_more: "SHOW MORE",
+    contact_intro: "Have a project in mind? Send a message.",
+    contact_name: "Name",
+    contact_email: "Email",
+    contact_message: "Message",
+    contact_send: "SEND",
+    contact_sending: "SENDING…",
+    contact_verifying: "VERIFYING…",
+    contact_sent: "Message sent. Thank you! I'll get back to you soon.",
+    contact_failed: "The message could not be sent right now. Please try again shortly.",
+    contact_no_js: "The form needs JavaScript for its anti-spam check. Without it, write to the address below.",
+    contact_direct: "Prefer email? Write to",
+    footer_credits: "DESIGNED & CODED BY NICOLAS ALMINO",
+    footer_powered: "POWERED BY RUST & LEPTOS",
+
+    back_to_projects: "← BACK TO PROJECTS",
+    back_home: "← HOME",
+    back_to_blog: "← BLOG",
+    blog_description: "Articles about web development, Rust and audiovisual work.",
+    blog_all_posts: "SEE ALL POSTS",
+    blog_empty: "No posts published yet.",
+    newer: "← NEWER",
+    older: "OLDER →",
+    pagination: "Pagination",
+
+    og_project: "PROJECT",
+    og_blog: "Articles and notes",
+
+    not_found: "Page not found.",
+    not_found_back: "Back to Home",
+
+    navbar_label: "Main menu",
+    navbar_igr: "THE IGR",
+    navbar_institutional: "INSTITUTIONAL",
+    navbar_history: "OUR HISTORY",
+    navbar_territories: "WHERE WE WORK",
+    navbar_about_us: "WHO WE ARE",
+    navbar_agenda: "AGENDA",
+    navbar_calendar: "EVENT CALENDAR",
+    navbar_media: "MEDIA",
+};
@@ -0,0 +1,221 @@
+//! pt-BR / en localization.
+//!
+//! Portuguese is the default and lives at the site root; English pages are the
+//! same routes under `/en`. The locale is derived from the URL, so SSR and
+//! hydration always agree on it.
+
+pub mod catalog;
+
+use chrono::{Datelike, NaiveDate};
+use leptos::prelude::*;
+use leptos_router::hooks::use_location;
+use serde::{Deserialize, Serialize};
+
+pub use catalog::Catalog;
+
+/// Name of the cookie that remembers an explicit language choice.
+pub const COOKIE: &str = "lang";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Locale {
+    #[default]
+    PtBr,
+    En,
+}
+
+impl Locale {
+    pub const ALL: [Locale; 2] = [Locale::PtBr, Locale::En];
+
+    /// BCP 47 tag, used for `lang` and `hreflang`.
+    pub fn tag(self) -> &'static str {
+        match self {
+            Locale::PtBr => "pt-BR",
+            Locale::En => "en",
+        }
+    }
+
+    /// `og:locale` value.
+    pub fn og_tag(self) -> &'static str {
+        match self {
+            Locale::PtBr => "pt_BR",
+            Locale::En => "en_US",
+        }
+    }
+
+    /// Short label for the language switcher.
+    pub fn label(self) -> &'static str {
+        match self {
+            Locale::PtBr => "PT",
+            Locale::En => "EN",
+        }
+    }
+
+    /// Parses a language tag such as `pt`, `pt-PT`, `en-US` or `en_GB`.
+    pub fn from_tag(tag: &str) -> Option<Locale> {
+        let primary = tag.trim().split(['-', '_']).next()?.to_ascii_lowercase();
+        match primary.as_str() {
+            "pt" => Some(Locale::PtBr),
+            "en" => Some(Locale::En),
+            _ => None,
+        }
+    }
+
+    /// URL prefix of the locale: empty for the default one.
+    pub fn prefix(self) -> &'static str {
+        match self {
+            Locale::PtBr => "",
+            Locale::En => "/en",
+        }
+    }
+
+    /// The locale a path belongs to.
+    pub fn from_path(path: &str) -> Locale {
+        if path == "/en" || path.starts_with("/en/") {
+            Locale::En
+        } else {
+            Locale::PtBr
+        }
+    }
+
+    /// Removes the locale prefix: `/en/blog` -> `/blog`, `/en` -> `/`.
+    pub fn strip_prefix(path: &str) -> &str {
+        match Locale::from_path(path) {
+            Locale::En => match &path[3..] {
+                "" => "/",
+                rest => rest,
+            },
+            Locale::PtBr => path,
+        }
+    }
+
+    /// Path of the same page in this locale. Fragments and absolute URLs are
+    /// left untouched.
+    pub fn localize(self, path: &str) -> String {
+        if !path.starts_with('/') || path.starts_with("//") {
+            return path.to_string();
+        }
+        let path = Locale::strip_prefix(path);
+        match (self.prefix(), path) {
+            ("", path) => path.to_string(),
+            (prefix, "/") => prefix.to_string(),
+            (prefix, path) if path.starts_with("/#") => format!("{prefix}{}", &path[1..]),
+            (prefix, path) => format!("{prefix}{path}"),
+        }
+    }
+
+    pub fn t(self) -> &'static Catalog {
+        match self {
+            Locale::PtBr => &catalog::PT_BR,
+            Locale::En => &catalog::EN,
+        }
+    }
+
+    /// "18 de outubro de 2026" / "October 18, 2026".
+    pub fn format_date(self, date: NaiveDate) -> String {
+        let month = self.t().months[date.month0() as usize];
+        match self {
+            Locale::PtBr => format!("{} de {month} de {}", date.day(), date.year()),
+            Locale::En => format!("{month} {}, {}", date.day(), date.year()),
+        }
+    }
+
+    /// "out 2026" / "Oct 2026".
+    pub fn format_month(self, date: NaiveDate) -> String {
+        format!("{} {}", self.t().months_short[date.month0() as usize], date.year())
+    }
+
+    /// Thousands separators: "1.234" / "1,234".
+    pub fn format_number(self, n: u64) -> String {
+        let separator = match self {
+            Locale::PtBr => '.',
+            Locale::En => ',',
+        };
+        let digits = n.to_string();
+        let mut out = String::with_capacity(digits.len() + digits.len() / 3);
+        for (i, c) in digits.chars().enumerate() {
+            if i > 0 && (digits.len() - i) % 3 == 0 {
+                out.push(separator);
+            }
+            out.push(c);
+        }
+        out
+    }
+
+    /// "2 anos e 3 meses" / "2 years and 3 months".
+    pub fn format_duration(self, months: u32) -> String {
+        let (years, months) = (months / 12, months % 12);
+        let unit = |n: u32, one: &str, many: &str| match n {
+            0 => None,
+            1 => Some(format!("1 {one}")),
+            n => Some(format!("{} {many}", self.format_number(n as u64))),
+        };
+        let (years, months, and, less) = match self {
+            Locale::PtBr => (
+                unit(years, "ano", "anos"),
+                unit(months, "mês", "meses"),
+                "e",
+                "menos de 1 mês",
+            ),
+            Locale::En => (
+                unit(years, "year", "years"),
+                unit(months, "month", "months"),
+                "and",
+                "less than 1 month",
+            ),
+        };
+        match (years, months) {
+            (Some(y), Some(m)) => format!("{y} {and} {m}"),
+            (Some(y), None) => y,
+            (None, Some(m)) => m,
+            (None, None) => less.to_string(),
+        }
+    }
+
+    /// "2 anos de experiência" / "2 years of experience".
+    pub fn format_experience(self, months: u32) -> String {
+        match self {
+            Locale::PtBr => format!("{} de experiência", self.format_duration(months)),
+            Locale::En => format!("{} of experience", self.format_duration(months)),
+        }
+    }
+}
+
+/// Picks the preferred supported locale from an `Accept-Language` header,
+/// honouring q-values.
+pub fn negotiate(accept_language: &str) -> Option<Locale> {
+    let mut best: Option<(Locale, f32)> = None;
+    for range in accept_language.split(',') {
+        let mut parts = range.split(';');
+        let Some(locale) = parts.next().and_then(Locale::from_tag) else {
+            continue;
+        };
+        let quality = parts
+            .find_map(|param| param.trim().strip_prefix("q="))
+            .and_then(|q| q.trim().parse::<f32>().ok())
+            .unwrap_or(1.0);
+        if quality > 0.0 && best.is_none_or(|(_, q)| quality > q) {
+            best = Some((locale, quality));
+        }
+    }
+    best.map(|(locale, _)| locale)
+}
+
+/// Current locale, tracked from the router location. Provided by [`provide_i18n`].
+#[derive(Clone, Copy)]
+pub struct LocaleContext(pub Memo<Locale>);
+
+/// Provides the locale context. Must be called inside the `<Router>`.
+pub fn provide_i18n() -> Memo<Locale> {
+    let location = use_location();
+    let locale = Memo::new(move |_| Locale::from_path(&location.pathname.get()));
+    provide_context(LocaleContext(locale));
+    locale
+}
+
+/// Locale of the page being rendered. Routes are rebuilt when the locale
+/// changes, so components can read it once.
+pub fn use_locale() -> Locale {
+    use_context::<LocaleContext>()
+        .map(|LocaleContext(locale)| locale.get_untracked())
+        .unwrap_or_default()
+}
@@ -1,6 +1,16 @@
+// First, so the `asset!` macro is in scope for the modules below
+#[macro_use]
+pub mod assets;
+pub mod admin;
+pub mod antispam;
 pub mod app;
 pub mod components;
+pub mod contact;
+pub mod content;
+pub mod i18n;
 pub mod routes;
+#[cfg(feature = "ssr")]
+pub mod server;
 #[cfg(feature = "hydrate")]
 #[wasm_bindgen::prelude::wasm_bindgen]
 pub fn hydrate() {
@@ -3,71 +3,199 @@
 #[cfg(feature = "ssr")]
 #[actix_web::main]
 async fn main() -> std::io::Result<()> {
-    use actix_files::Files;
     use actix_web::*;
-    use leptos::prelude::*;
-    use leptos::config::get_configuration;
-    use leptos_meta::MetaTags;
-    use leptos_actix::{generate_route_list, LeptosRoutes};
+    use clap::Parser;
+    use leptos_actix::generate_route_list;
     use nicolasalmino_site::app::*;
+    use nicolasalmino_site::content;
+    use nicolasalmino_site::server::config::{Cli, Command, ServerConfig};
+    use nicolasalmino_site::server::site::Site;
+    use nicolasalmino_site::server::{antispam, auth, caching, compression, contact, editor, export, live_reload, locale, metrics, og_image, security, systemd, telemetry};
 
-    let conf = get_configuration(None).unwrap();
-    let addr = conf.leptos_options.site_addr;
+    let cli = Cli::parse();
 
-    HttpServer::new(move || {
-        // Generate the list of routes in your Leptos App
-        let routes = generate_route_list(App);
-        let leptos_options = &conf.leptos_options;
-        let site_root = leptos_options.site_root.clone().to_string();
+    // `hash-password` reads a password from stdin and prints the value for
+    // ADMIN_PASSWORD_HASH
+    if let Some(Command::HashPassword) = cli.command {
+        let mut password = String::new();
+        std::io::stdin().read_line(&mut password)?;
+        match auth::hash_password(password.trim_end_matches(['\r', '\n'])) {
+            Ok(hash) => println!("{hash}"),
+            Err(e) => {
+                eprintln!("hash-password: {e}");
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+    // `export [dir]` writes the site as static files instead of serving it
+    let static_export = matches!(cli.command, Some(Command::Export { .. }));
 
-        println!("listening on http://{}", &addr);
+    let config = match ServerConfig::load(&cli) {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("configuration: {e}");
+            std::process::exit(1);
+        }
+    };
+    let log_level = match telemetry::init(&config.log) {
+        Ok(log_level) => web::Data::new(log_level),
+        Err(e) => {
+            eprintln!("logging: {e}");
+            std::process::exit(1);
+        }
+    };
+    let site_root = config.site_root();
+    let content_dir = &config.content_dir;
 
+    // Content is read once at startup; pages are rendered from the in-memory copy
+    if let Err(e) = content::store::init(content_dir) {
+        tracing::error!(dir = %content_dir.display(), "failed to load content: {e}");
+        std::process::exit(1);
+    }
+    // Reload content on file changes without a rebuild (development only by default)
+    let live_reload = if !static_export && config.watch_content {
+        match live_reload::LiveReload::start(content_dir) {
+            Ok(reload) => Some(web::Data::new(reload)),
+            Err(e) => {
+                tracing::warn!(dir = %content_dir.display(), "not watching content for changes: {e}");
+                None
+            }
+        }
+    } else {
+        None
+    };
+    // `.br`/`.gz` copies of the static files (outside development by default)
+    if config.precompress {
+        match compression::precompress(&site_root) {
+            Ok(0) => {}
+            Ok(written) => tracing::info!(written, "precompressed static files"),
+            Err(e) => tracing::error!("precompressing static files: {e}"),
+        }
+    }
+    let contact_service = match contact::ContactService::new(config.contact.clone()) {
+        Ok(service) => web::Data::new(service),
+        Err(e) => {
+            tracing::error!("contact form: {e}");
+            std::process::exit(1);
+        }
+    };
+    let antispam_service = web::Data::new(antispam::Antispam::new(config.antispam.clone()));
+    let admin_auth = match auth::Auth::new(config.auth.clone()) {
+        Ok(auth) => {
+            if !auth.is_enabled() && !static_export {
+                tracing::warn!("admin: ADMIN_PASSWORD_HASH is not set, logins are disabled");
+            }
+            web::Data::new(auth)
+        }
+        Err(e) => {
+            tracing::error!("admin: {e}");
+            std::process::exit(1);
+        }
+    };
+    // The admin editor writes the same directory the store reads
+    let content_editor = web::Data::new(editor::Editor::new(content_dir, site_root.join("icons")));
+    let site_metrics = (!static_export && config.metrics.is_enabled())
+        .then(|| web::Data::new(metrics::Metrics::new(config.metrics.clone(), config.workers)));
+    let og_images = match og_image::OgImages::new(
+        og_image::FONT,
+        &config.og_cache_dir,
+        config.site_url.as_str().split("://").last().unwrap_or_default(),
+    ) {
+        Ok(og_images) => og_images,
+        Err(e) => {
+            tracing::error!("og images: {e}");
+            std::process::exit(1);
+        }
+    };
+
+    let site = Site {
+        leptos_options: config.leptos_options.clone(),
+        // Generate the list of routes in your Leptos App; the sitemap and the
+        // export reuse it
+        routes: generate_route_list(App),
+        site_url: config.site_url.clone(),
+        feed_config: config.feeds.clone(),
+        robots_config: config.robots.clone(),
+        og_images,
+        security: config.security.clone(),
+        log: config.log.clone(),
+        log_level,
+        contact: contact_service,
+        antispam: antispam_service,
+        csp_reports: web::Data::new(security::CspReports::new(&config.security)),
+        auth: admin_auth,
+        editor: content_editor,
+        live_reload,
+        metrics: site_metrics.clone(),
+        static_export,
+    };
+
+    if let Some(Command::Export { dir }) = &cli.command {
+        match export::export(&site, dir).await {
+            Ok(summary) => println!(
+                "exported {} pages and {} files to {}, copied {} assets",
+                summary.pages,
+                summary.files,
+                dir.display(),
+                summary.copied
+            ),
+            Err(e) => {
+                tracing::error!("export: {e}");
+                std::process::exit(1);
+            }
+        }
+        return Ok(());
+    }
+
+    let addr = config.leptos_options.site_addr;
+    let listeners = systemd::listeners()?;
+    let mut server = HttpServer::new(move || {
         App::new()
-            // serve JS/WASM/CSS from `pkg`
-            .service(Files::new("/pkg", format!("{site_root}/pkg")))
-            // serve other assets from the `assets` directory
-            .service(Files::new("/assets", &site_root))
-            // serve the favicon from /favicon.ico
-            .service(favicon)
-            .leptos_routes(routes, {
-                let leptos_options = leptos_options.clone();
-                move || {
-                    view! {
-                        <!DOCTYPE html>
-                        <html lang="pt-BR">
-                            <head>
-                                <meta charset="utf-8"/>
-                                <meta name="viewport" content="width=device-width, initial-scale=1"/>
-                                <AutoReload options=leptos_options.clone() />
-                                <HydrationScripts options=leptos_options.clone()/>
-                                <MetaTags/>
-                            </head>
-                            <body>
-                                <App/>
-                            </body>
-                        </html>
-                    }
-                }
-            })
-            .app_data(web::Data::new(leptos_options.to_owned()))
-        //.wrap(middleware::Compress::default())
+            .configure(|cfg| site.configure(cfg))
+            .wrap(middleware::from_fn(locale::redirect))
+            .wrap(middleware::from_fn(compression::serve))
+            .wrap(middleware::from_fn(telemetry::render))
+            .wrap(middleware::from_fn(caching::headers))
+            .wrap(middleware::from_fn(security::headers))
+            // Inside `Compress`, whose streamed bodies have no size to log
+            .wrap(middleware::from_fn(metrics::record))
+            .wrap(middleware::from_fn(telemetry::requests))
+            .wrap(middleware::Compress::default())
     })
-    .bind(&addr)?
-    .workers(1)
-    .run()
-    .await
-}
+    .workers(config.workers)
+    .shutdown_timeout(config.shutdown_timeout.as_secs())
+    // `systemd::stop_on_signal` stops the server instead
+    .disable_signals();
+    // Sockets from systemd socket activation, or our own
+    if listeners.is_empty() {
+        server = server.bind(addr)?;
+    } else {
+        for listener in listeners {
+            server = server.listen(listener)?;
+        }
+    }
+    for addr in server.addrs() {
+        tracing::info!(workers = config.workers, "listening on http://{addr}");
+    }
+    let server = server.run();
+    let mut handles = vec![server.handle()];
+    // Metrics on their own address, out of reach of the public
+    if let (Some(site_metrics), Some(addr)) = (site_metrics, config.metrics.addr) {
+        let metrics_server = metrics::server(site_metrics, addr)?;
+        tracing::info!("metrics on http://{addr}/metrics");
+        // Stopped after the site, so its shutdown can still be watched
+        handles.push(metrics_server.handle());
+        rt::spawn(metrics_server);
+    }
 
-#[cfg(feature = "ssr")]
-#[actix_web::get("favicon.ico")]
-async fn favicon(
-    leptos_options: actix_web::web::Data<leptos::config::LeptosOptions>,
-) -> actix_web::Result<actix_files::NamedFile> {
-    let leptos_options = leptos_options.into_inner();
-    let site_root = &leptos_options.site_root;
-    Ok(actix_files::NamedFile::open(format!(
-        "{site_root}/favicon.ico"
-    ))?)
+    let notifier = systemd::Notifier::from_env();
+    rt::spawn(systemd::stop_on_signal(handles, notifier.clone()));
+    if let Some(interval) = systemd::watchdog_interval() {
+        rt::spawn(notifier.clone().watchdog(interval));
+    }
+    notifier.send(&format!("READY=1\nSTATUS=Serving {}", config.site_url.as_str()));
+    server.await
 }
 
 #[cfg(not(any(feature = "ssr", feature = "csr")))]
@@ -0,0 +1,361 @@
+//! Building blocks of the content editor pages: field widgets bound to the
+//! edited document, the save/publish toolbar, the locale switcher and the
+//! page scaffold.
+
+use leptos::prelude::*;
+use leptos_router::hooks::use_query_map;
+
+use super::AdminLayout;
+use crate::admin::api::{discard_draft, load_document, publish, save_draft};
+use crate::admin::editor::{locale_from_param, Document, EditorError, Loaded, Payload, Status, LOCALE_PARAM};
+use crate::admin::AdminSession;
+use crate::i18n::Locale;
+
+pub const INPUT_CLASS: &str = "w-full px-3 py-2 bg-white/5 border border-white/10 rounded-lg text-white text-sm focus:outline-none focus:border-blue-500 transition-colors";
+const BUTTON_CLASS: &str = "px-4 py-2 rounded-full border border-white/20 text-sm font-bold hover:bg-white hover:text-black transition-colors disabled:opacity-40 disabled:pointer-events-none";
+const SMALL_BUTTON_CLASS: &str = "px-2 py-1 rounded border border-white/10 text-xs text-gray-400 hover:text-white hover:border-white/40 transition-colors";
+
+// --- Lenses ---
+
+/// Reads and writes one part of the edited document.
+pub fn lens<T, U>(
+    state: RwSignal<T>,
+    get: impl Fn(&T) -> U + Send + Sync + 'static,
+    set: impl Fn(&mut T, U) + Send + Sync + 'static,
+) -> (Signal<U>, Callback<U>)
+where
+    T: Send + Sync + 'static,
+    U: Send + Sync + 'static,
+{
+    (
+        Signal::derive(move || state.with(|state| get(state))),
+        Callback::new(move |value| state.update(|state| set(state, value))),
+    )
+}
+
+/// [`lens`] onto row `index` of a list. A row being removed reads as the
+/// default and ignores writes.
+pub fn lens_at<T, U>(
+    state: RwSignal<Vec<T>>,
+    index: usize,
+    get: impl Fn(&T) -> U + Send + Sync + 'static,
+    set: impl Fn(&mut T, U) + Send + Sync + 'static,
+) -> (Signal<U>, Callback<U>)
+where
+    T: Send + Sync + 'static,
+    U: Default + Send + Sync + 'static,
+{
+    lens(
+        state,
+        move |rows| rows.get(index).map(&get).unwrap_or_default(),
+        move |rows, value| {
+            if let Some(row) = rows.get_mut(index) {
+                set(row, value)
+            }
+        },
+    )
+}
+
+/// A list edited as text, one entry per `separator`. Blank entries are kept
+/// while typing and dropped on save.
+pub fn split(text: &str, separator: char) -> Vec<String> {
+    text.split(separator).map(str::to_string).collect()
+}
+
+// --- Fields ---
+
+/// Labelled text input, or a textarea when `rows` is set.
+#[component]
+pub fn TextField(
+    label: &'static str,
+    #[prop(into)] value: Signal<String>,
+    #[prop(into)] set: Callback<String>,
+    /// Input type, `text` by default.
+    #[prop(optional)]
+    kind: Option<&'static str>,
+    #[prop(optional)] rows: Option<u32>,
+    #[prop(optional)] placeholder: &'static str,
+    #[prop(optional)] hint: Option<&'static str>,
+) -> impl IntoView {
+    let on_input = move |ev: leptos::ev::Event| set.run(event_target_value(&ev));
+    let input = match rows {
+        Some(rows) => view! {
+            <textarea
+                rows=rows
+                placeholder=placeholder
+                class=format!("{INPUT_CLASS} font-mono")
+                prop:value=value
+                on:input=on_input
+            ></textarea>
+        }
+        .into_any(),
+        None => view! {
+            <input
+                type=kind.unwrap_or("text")
+                placeholder=placeholder
+                class=INPUT_CLASS
+                prop:value=value
+                on:input=on_input
+            />
+        }
+        .into_any(),
+    };
+
+    view! {
+        <label class="block">
+            <span class="block text-xs font-bold tracking-wide text-gray-400 mb-1">{label}</span>
+            {input}
+            {hint.map(|hint| view! { <span class="block mt-1 text-xs text-gray-500">{hint}</span> })}
+        </label>
+    }
+}
+
+/// What the row buttons of a list do.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RowAction {
+    Up,
+    Down,
+    Remove,
+}
+
+impl RowAction {
+    pub fn apply<T>(self, rows: &mut Vec<T>, index: usize) {
+        match self {
+            RowAction::Up if index > 0 && index < rows.len() => rows.swap(index - 1, index),
+            RowAction::Down if index + 1 < rows.len() => rows.swap(index, index + 1),
+            RowAction::Remove if index < rows.len() => {
+                rows.remove(index);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Move up / move down / remove buttons for one row of a list.
+#[component]
+pub fn RowControls(#[prop(into)] on: Callback<RowAction>) -> impl IntoView {
+    view! {
+        <div class="flex gap-2">
+            <button type="button" class=SMALL_BUTTON_CLASS title="Mover para cima" on:click=move |_| on.run(RowAction::Up)>"↑"</button>
+            <button type="button" class=SMALL_BUTTON_CLASS title="Mover para baixo" on:click=move |_| on.run(RowAction::Down)>"↓"</button>
+            <button type="button" class=SMALL_BUTTON_CLASS on:click=move |_| on.run(RowAction::Remove)>"Remover"</button>
+        </div>
+    }
+}
+
+#[component]
+pub fn AddButton(label: &'static str, #[prop(into)] on: Callback<()>) -> impl IntoView {
+    view! {
+        <button type="button" class=SMALL_BUTTON_CLASS on:click=move |_| on.run(())>{label}</button>
+    }
+}
+
+// --- Page ---
+
+/// Status, validation problems and the save / publish / discard buttons.
+/// Validation runs on every change; saving is blocked until it passes.
+#[component]
+pub fn EditorActions(
+    #[prop(into)] payload: Signal<Payload>,
+    status: Status,
+    locale: Locale,
+    icons: Vec<String>,
+) -> impl IntoView {
+    let csrf = StoredValue::new(expect_context::<AdminSession>().csrf);
+    let icons = StoredValue::new(icons);
+    let status = RwSignal::new(status);
+    let current = Memo::new(move |_| payload.get().normalized());
+    let saved = RwSignal::new(current.get_untracked());
+    let issues = Memo::new(move |_| icons.with_value(|icons| current.with(|p| p.validate(icons))));
+    let dirty = move || current.with(|current| saved.with(|saved| current != saved));
+    let error = RwSignal::new(None::<EditorError>);
+    let published = RwSignal::new(false);
+
+    let save_action = Action::new(move |payload: &Payload| {
+        let payload = payload.clone();
+        async move {
+            match save_draft(csrf.get_value(), locale, payload.clone()).await {
+                Ok(new_status) => {
+                    status.set(new_status);
+                    saved.set(payload);
+                    error.set(None);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        }
+    });
+    let publish_action = Action::new(move |document: &Document| {
+        let document = document.clone();
+        async move {
+            match publish(csrf.get_value(), locale, document).await {
+                Ok(()) => {
+                    status.set(Status::Published);
+                    published.set(true);
+                    error.set(None);
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        }
+    });
+    let discard_action = Action::new(move |document: &Document| {
+        let document = document.clone();
+        async move {
+            match discard_draft(csrf.get_value(), locale, document).await {
+                // The form holds the draft; start over from the live file
+                Ok(()) => {
+                    let _ = window().location().reload();
+                }
+                Err(e) => error.set(Some(e)),
+            }
+        }
+    });
+    let busy = move || save_action.pending().get() || publish_action.pending().get() || discard_action.pending().get();
+    let has_draft = move || matches!(status.get(), Status::Draft | Status::New);
+
+    view! {
+        <div class="sticky top-0 z-20 -mx-6 px-6 py-4 mb-8 bg-[#0a0a0a]/90 backdrop-blur border-b border-white/10">
+            <div class="flex flex-wrap items-center gap-3">
+                <span class="px-3 py-1 rounded-full bg-white/5 border border-white/10 text-xs font-bold">
+                    {move || status.get().label()}
+                </span>
+                <Show when=dirty>
+                    <span class="text-xs text-yellow-400">"Alterações não salvas"</span>
+                </Show>
+                <div class="flex-1"></div>
+                <button
+                    type="button"
+                    class=BUTTON_CLASS
+                    disabled=move || busy() || !dirty() || issues.with(|i| !i.is_empty())
+                    on:click=move |_| {
+                        published.set(false);
+                        save_action.dispatch(current.get());
+                    }
+                >
+                    "Salvar rascunho"
+                </button>
+                <button
+                    type="button"
+                    class=format!("{BUTTON_CLASS} bg-white text-black")
+                    disabled=move || busy() || dirty() || !has_draft()
+                    on:click=move |_| {
+                        publish_action.dispatch(current.with(|p| p.document()));
+                    }
+                >
+                    "Publicar"
+                </button>
+                <button
+                    type="button"
+                    class=BUTTON_CLASS
+                    disabled=move || busy() || !has_draft()
+                    on:click=move |_| {
+                        discard_action.dispatch(current.with(|p| p.document()));
+                    }
+                >
+                    "Descartar rascunho"
+                </button>
+            </div>
+
+            {move || error.get().map(|e| view! {
+                <p class="mt-3 text-sm text-red-400" role="alert">{e.message()}</p>
+            })}
+            <Show when=move || published.get()>
+                <p class="mt-3 text-sm text-blue-300" role="status">"Publicado. O site já mostra a nova versão."</p>
+            </Show>
+            <Show when=move || issues.with(|i| !i.is_empty())>
+                <ul class="mt-3 space-y-1 text-sm text-red-400">
+                    {move || issues.get().into_iter().map(|issue| view! {
+                        <li><strong>{issue.field}</strong>": "{issue.message}</li>
+                    }).collect_view()}
+                </ul>
+            </Show>
+        </div>
+    }
+}
+
+/// The locale being edited, from the page's [`LOCALE_PARAM`].
+pub fn use_editor_locale() -> Memo<Locale> {
+    let query = use_query_map();
+    Memo::new(move |_| query.with(|query| locale_from_param(query.get_str(LOCALE_PARAM))))
+}
+
+/// Links to the same admin page in each locale. `href` builds the path of
+/// the page for a locale.
+#[component]
+pub fn LocaleTabs(
+    #[prop(into)] current: Signal<Locale>,
+    #[prop(into)] href: Callback<Locale, String>,
+) -> impl IntoView {
+    view! {
+        <nav class="flex gap-2 mb-8" aria-label="Idioma">
+            {Locale::ALL.into_iter().map(|locale| {
+                let active = move || current.get() == locale;
+                view! {
+                    <a
+                        href=move || href.run(locale)
+                        class=move || if active() {
+                            "px-3 py-1 rounded-full bg-white text-black text-xs font-bold"
+                        } else {
+                            "px-3 py-1 rounded-full border border-white/20 text-xs font-bold text-gray-400 hover:text-white"
+                        }
+                        aria-current=move || active().then_some("page")
+                    >
+                        {locale.label()}
+                    </a>
+                }
+            }).collect_view()}
+        </nav>
+    }
+}
+
+/// The form beside a live preview drawn with the site's own components.
+#[component]
+pub fn EditorColumns(children: Children, #[prop(into)] preview: ViewFn) -> impl IntoView {
+    view! {
+        <div class="grid grid-cols-1 xl:grid-cols-2 gap-8 items-start">
+            <div class="space-y-6">{children()}</div>
+            <div class="xl:sticky xl:top-28 max-h-[80vh] overflow-auto rounded-2xl border border-white/10">
+                <p class="px-4 py-2 text-xs font-bold tracking-widest text-gray-500 border-b border-white/10">"PRÉ-VISUALIZAÇÃO"</p>
+                {move || preview.run()}
+            </div>
+        </div>
+    }
+}
+
+/// Loads `document` (its draft, if any) in the locale of the page and
+/// renders `form` with it, under links to the other locales.
+#[component]
+pub fn EditorPage<F, V>(
+    #[prop(into)] document: Signal<Document>,
+    title: &'static str,
+    form: F,
+) -> impl IntoView
+where
+    F: Fn(Loaded) -> V + Clone + Send + Sync + 'static,
+    V: IntoView + 'static,
+{
+    let locale = use_editor_locale();
+    let loaded = Resource::new(
+        move || (locale.get(), document.get()),
+        |(locale, document)| load_document(locale, document),
+    );
+
+    view! {
+        <AdminLayout title=title>
+            <LocaleTabs current=locale href=move |locale: Locale| document.with(|d| d.editor_path(locale))/>
+            <Suspense fallback=|| view! { <p class="text-gray-500">"Carregando..."</p> }>
+                {move || {
+                    let form = form.clone();
+                    Suspend::new(async move {
+                        match loaded.await {
+                            Ok(loaded) => form(loaded).into_any(),
+                            Err(e) => view! {
+                                <p class="text-red-400" role="alert">{e.message()}</p>
+                            }
+                            .into_any(),
+                        }
+                    })
+                }}
+            </Suspense>
+        </AdminLayout>
+    }
+}
@@ -0,0 +1,89 @@
+use chrono::NaiveDate;
+use leptos::prelude::*;
+
+use super::editor::{
+    lens_at, split, AddButton, EditorActions, EditorColumns, EditorPage, RowAction, RowControls,
+    TextField,
+};
+use crate::admin::editor::{Document, Loaded, Payload};
+use crate::content::Experience;
+use crate::routes::home::CareerTimeline;
+
+fn parse_date(value: &str) -> Option<NaiveDate> {
+    NaiveDate::parse_from_str(value, "%Y-%m-%d").ok()
+}
+
+#[component]
+fn ExperienceFields(experiences: RwSignal<Vec<Experience>>, index: usize) -> impl IntoView {
+    let (title, set_title) = lens_at(experiences, index, |e| e.title.clone(), |e, v| e.title = v);
+    let (company, set_company) = lens_at(experiences, index, |e| e.company.clone(), |e, v| e.company = v);
+    let (start, set_start) = lens_at(
+        experiences,
+        index,
+        |e| e.start.to_string(),
+        |e, v: String| {
+            if let Some(date) = parse_date(&v) {
+                e.start = date
+            }
+        },
+    );
+    // Empty while the role is ongoing
+    let (end, set_end) = lens_at(
+        experiences,
+        index,
+        |e| e.end.map(|d| d.to_string()).unwrap_or_default(),
+        |e, v: String| e.end = parse_date(&v),
+    );
+    let (tags, set_tags) = lens_at(experiences, index, |e| e.tags.join(","), |e, v: String| e.tags = split(&v, ','));
+    let (tasks, set_tasks) = lens_at(experiences, index, |e| e.tasks.join("\n"), |e, v: String| e.tasks = split(&v, '\n'));
+
+    view! {
+        <fieldset class="p-4 rounded-xl border border-white/10 space-y-4">
+            <div class="flex justify-end">
+                <RowControls on=move |action: RowAction| experiences.update(|e| action.apply(e, index))/>
+            </div>
+            <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+                <TextField label="Cargo" value=title set=set_title/>
+                <TextField label="Empresa" value=company set=set_company/>
+                <TextField label="Início" kind="date" value=start set=set_start/>
+                <TextField label="Fim" kind="date" value=end set=set_end hint="Vazio enquanto o cargo estiver em andamento."/>
+            </div>
+            <TextField label="Habilidades" value=tags set=set_tags hint="Separadas por vírgula; alimentam o filtro da linha do tempo."/>
+            <TextField label="Atividades" rows=4 value=tasks set=set_tasks hint="Uma por linha."/>
+        </fieldset>
+    }
+}
+
+fn experience_form(loaded: Loaded) -> AnyView {
+    let Payload::Experience(experiences) = loaded.payload else {
+        return ().into_any();
+    };
+    let experiences = RwSignal::new(experiences);
+    let today = loaded.today;
+
+    view! {
+        <EditorActions
+            payload=Signal::derive(move || Payload::Experience(experiences.get()))
+            status=loaded.status
+            locale=loaded.locale
+            icons=loaded.icons
+        />
+        <EditorColumns preview=move || view! { <CareerTimeline experiences=experiences.get() today=today/> }>
+            <For
+                each=move || 0..experiences.with(Vec::len)
+                key=|index| *index
+                children=move |index| view! { <ExperienceFields experiences=experiences index=index/> }
+            />
+            <AddButton
+                label="+ Experiência"
+                on=move |_| experiences.update(|e| e.push(Experience { start: today, ..Experience::default() }))
+            />
+        </EditorColumns>
+    }
+    .into_any()
+}
+
+#[component]
+pub fn ExperienceEditor() -> impl IntoView {
+    view! { <EditorPage document=Document::Experience title="Experiência" form=experience_form/> }
+}
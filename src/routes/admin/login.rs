@@ -0,0 +1,61 @@
+use leptos::prelude::*;
+
+use crate::admin::api::{login_csrf, Login};
+use crate::components::page_meta::PageMeta;
+
+const INPUT_CLASS: &str = "w-full px-4 py-3 bg-white/5 border border-white/10 rounded-xl text-white focus:outline-none focus:border-blue-500 transition-colors";
+
+#[component]
+pub fn LoginPage() -> impl IntoView {
+    #[cfg(feature = "ssr")]
+    {
+        use actix_web::http::header::{HeaderValue, CACHE_CONTROL};
+        let resp = expect_context::<leptos_actix::ResponseOptions>();
+        resp.insert_header(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    }
+
+    let action = ServerAction::<Login>::new();
+    // Resolved before the headers go out, the route being async, so the
+    // cookie is set with the page
+    let csrf = Resource::new(|| (), |_| login_csrf());
+    let error = move || {
+        action
+            .value()
+            .get()
+            .and_then(|result| result.err())
+            .map(|e| e.message())
+    };
+
+    view! {
+        <PageMeta title="Entrar - Admin" noindex=true/>
+        <div class="min-h-screen flex items-center justify-center px-6">
+            <ActionForm action=action attr:class="w-full max-w-sm space-y-6">
+                <h1 class="text-3xl font-black">"Admin"</h1>
+                <Suspense>
+                    {move || Suspend::new(async move {
+                        let token = csrf.await.unwrap_or_default();
+                        view! { <input type="hidden" name="csrf" value=token/> }
+                    })}
+                </Suspense>
+                <label class="block">
+                    <span class="block text-sm font-bold text-gray-300 mb-2">"Usuário"</span>
+                    <input type="text" name="username" required=true autocomplete="username" class=INPUT_CLASS/>
+                </label>
+                <label class="block">
+                    <span class="block text-sm font-bold text-gray-300 mb-2">"Senha"</span>
+                    <input type="password" name="password" required=true autocomplete="current-password" class=INPUT_CLASS/>
+                </label>
+                {move || error().map(|message| view! {
+                    <p class="text-sm text-red-400" role="alert">{message}</p>
+                })}
+                <button
+                    type="submit"
+                    class="w-full px-8 py-4 rounded-full bg-white text-black font-bold hover:bg-blue-500 hover:text-white transition-colors disabled:opacity-50"
+                    disabled=move || action.pending().get()
+                >
+                    {move || if action.pending().get() { "ENTRANDO..." } else { "ENTRAR" }}
+                </button>
+            </ActionForm>
+        </div>
+    }
+}
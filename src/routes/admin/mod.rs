@@ -0,0 +1,214 @@
+//! The `/admin` pages. Everything but the login page sits behind
+//! [`AdminGuard`]; the server functions check the session again on their own.
+
+mod editor;
+mod experience;
+mod login;
+mod profile;
+mod project;
+mod skills;
+
+use leptos::prelude::*;
+use leptos_router::{
+    components::{Redirect, Route},
+    hooks::use_navigate,
+    MatchNestedRoutes, ParamSegment, SsrMode, StaticSegment,
+};
+
+use crate::admin::api::{current_session, editor_overview, log_filter, Logout, SetLogFilter};
+use crate::admin::editor::{locale_path, Document};
+use crate::admin::AdminSession;
+use crate::components::page_meta::PageMeta;
+use crate::content::slugify;
+use crate::i18n::Locale;
+use editor::{use_editor_locale, LocaleTabs, INPUT_CLASS};
+use experience::ExperienceEditor;
+use profile::ProfileEditor;
+use project::ProjectEditor;
+use skills::SkillsEditor;
+
+pub use login::LoginPage;
+
+/// Renders `children` only for a logged-in admin, who is provided as
+/// [`AdminSession`] context; everyone else is sent to the login page.
+#[component]
+pub fn AdminGuard(children: ChildrenFn) -> impl IntoView {
+    // Admin pages are personal: keep them out of shared caches
+    #[cfg(feature = "ssr")]
+    {
+        use actix_web::http::header::{HeaderValue, CACHE_CONTROL};
+        let resp = expect_context::<leptos_actix::ResponseOptions>();
+        resp.insert_header(CACHE_CONTROL, HeaderValue::from_static("no-store"));
+    }
+
+    let session = Resource::new(|| (), |_| current_session());
+    let children = StoredValue::new(children);
+
+    view! {
+        <Suspense>
+            {move || Suspend::new(async move {
+                match session.await {
+                    Ok(Some(session)) => {
+                        provide_context(session);
+                        children.with_value(|children| children()).into_any()
+                    }
+                    _ => view! { <Redirect path="/admin/login"/> }.into_any(),
+                }
+            })}
+        </Suspense>
+    }
+}
+
+/// Shell around every admin page: title bar with the user and logout.
+#[component]
+pub fn AdminLayout(#[prop(into)] title: String, children: Children) -> impl IntoView {
+    let session = expect_context::<AdminSession>();
+    let logout = ServerAction::<Logout>::new();
+
+    view! {
+        <PageMeta title=format!("{title} - Admin") noindex=true/>
+        <div class="max-w-7xl mx-auto px-6 py-12">
+            <header class="flex items-center justify-between gap-6 mb-12 pb-6 border-b border-white/10">
+                <div>
+                    <a href="/admin" class="text-sm font-bold tracking-widest text-gray-500 hover:text-white">"ADMIN"</a>
+                    <h1 class="text-3xl font-black">{title}</h1>
+                </div>
+                <ActionForm action=logout attr:class="flex items-center gap-4">
+                    <input type="hidden" name="csrf" value=session.csrf/>
+                    <span class="text-sm text-gray-400">{session.username}</span>
+                    <button type="submit" class="px-4 py-2 rounded-full border border-white/20 text-sm hover:bg-white hover:text-black transition-colors">
+                        "Sair"
+                    </button>
+                </ActionForm>
+            </header>
+            {children()}
+        </div>
+    }
+}
+
+/// Lists the editable content of one locale with its state, and starts new
+/// projects.
+#[component]
+fn Dashboard() -> impl IntoView {
+    let locale = use_editor_locale();
+    let entries = Resource::new(move || locale.get(), editor_overview);
+    let new_slug = RwSignal::new(String::new());
+    let navigate = use_navigate();
+    let create = move |ev: leptos::ev::SubmitEvent| {
+        ev.prevent_default();
+        let slug = slugify(&new_slug.get());
+        if !slug.is_empty() {
+            navigate(&Document::Project(slug).editor_path(locale.get()), Default::default());
+        }
+    };
+
+    view! {
+        <AdminLayout title="Conteúdo">
+            <LocaleTabs current=locale href=|locale: Locale| locale_path("/admin", locale)/>
+            <Suspense fallback=|| view! { <p class="text-gray-500">"Carregando..."</p> }>
+                {move || Suspend::new(async move {
+                    let locale = locale.get();
+                    entries.await.map(|entries| view! {
+                        <ul class="divide-y divide-white/10 border-y border-white/10 mb-12">
+                            {entries.into_iter().map(|entry| view! {
+                                <li>
+                                    <a href=entry.document.editor_path(locale) class="flex items-center justify-between gap-4 py-4 hover:text-blue-300 transition-colors">
+                                        <span class="font-bold">{entry.title}</span>
+                                        <span class="text-xs text-gray-400">{entry.status.label()}</span>
+                                    </a>
+                                </li>
+                            }).collect_view()}
+                        </ul>
+                    })
+                })}
+            </Suspense>
+
+            <form class="flex items-end gap-3 max-w-md" on:submit=create>
+                <label class="block flex-1">
+                    <span class="block text-xs font-bold tracking-wide text-gray-400 mb-1">"Novo projeto (slug)"</span>
+                    <input
+                        type="text"
+                        class=INPUT_CLASS
+                        placeholder="meu-projeto"
+                        prop:value=new_slug
+                        on:input=move |ev| new_slug.set(event_target_value(&ev))
+                    />
+                </label>
+                <button type="submit" class="px-4 py-2 rounded-full bg-white text-black text-sm font-bold">"Criar"</button>
+            </form>
+
+            <LogFilterForm/>
+        </AdminLayout>
+    }
+}
+
+/// Changes the server's log filter, until the next restart.
+#[component]
+fn LogFilterForm() -> impl IntoView {
+    let session = expect_context::<AdminSession>();
+    let set_filter = ServerAction::<SetLogFilter>::new();
+    // Shows the filter in effect again after each change
+    let filter = Resource::new(move || set_filter.version().get(), |_| log_filter());
+    let message = move || {
+        set_filter.value().get().map(|result| match result {
+            Ok(()) => "Filtro aplicado.".to_string(),
+            Err(e) => e.message(),
+        })
+    };
+
+    view! {
+        <ActionForm action=set_filter attr:class="flex items-end gap-3 max-w-md mt-12">
+            <input type="hidden" name="csrf" value=session.csrf/>
+            <label class="block flex-1">
+                <span class="block text-xs font-bold tracking-wide text-gray-400 mb-1">"Filtro de log"</span>
+                <Suspense>
+                    {move || Suspend::new(async move {
+                        let current = filter.await.unwrap_or_default();
+                        view! {
+                            <input type="text" name="filter" class=INPUT_CLASS placeholder="info" value=current/>
+                        }
+                    })}
+                </Suspense>
+            </label>
+            <button type="submit" class="px-4 py-2 rounded-full border border-white/20 text-sm font-bold hover:bg-white hover:text-black transition-colors">
+                "Aplicar"
+            </button>
+        </ActionForm>
+        {move || message().map(|message| view! { <p class="text-sm text-gray-400 mt-2" role="status">{message}</p> })}
+    }
+}
+
+/// The `/admin` routes. Not localized and not in the sitemap.
+#[component(transparent)]
+pub fn AdminRoutes() -> impl MatchNestedRoutes + Clone {
+    view! {
+        // Async so the guard decides before anything is sent
+        <Route
+            path=StaticSegment("admin")
+            view=|| view! { <AdminGuard><Dashboard/></AdminGuard> }
+            ssr=SsrMode::Async
+        />
+        <Route path=(StaticSegment("admin"), StaticSegment("login")) view=LoginPage ssr=SsrMode::Async/>
+        <Route
+            path=(StaticSegment("admin"), StaticSegment("perfil"))
+            view=|| view! { <AdminGuard><ProfileEditor/></AdminGuard> }
+            ssr=SsrMode::Async
+        />
+        <Route
+            path=(StaticSegment("admin"), StaticSegment("habilidades"))
+            view=|| view! { <AdminGuard><SkillsEditor/></AdminGuard> }
+            ssr=SsrMode::Async
+        />
+        <Route
+            path=(StaticSegment("admin"), StaticSegment("experiencia"))
+            view=|| view! { <AdminGuard><ExperienceEditor/></AdminGuard> }
+            ssr=SsrMode::Async
+        />
+        <Route
+            path=(StaticSegment("admin"), StaticSegment("projetos"), ParamSegment("slug"))
+            view=|| view! { <AdminGuard><ProjectEditor/></AdminGuard> }
+            ssr=SsrMode::Async
+        />
+    }
+    .into_inner()
+}
@@ -0,0 +1,39 @@
+use leptos::prelude::*;
+
+use super::editor::{lens, EditorActions, EditorColumns, EditorPage, TextField};
+use crate::admin::editor::{Document, Loaded, Payload};
+use crate::routes::home::Hero;
+
+fn profile_form(loaded: Loaded) -> AnyView {
+    let Payload::Profile(profile) = loaded.payload else {
+        return ().into_any();
+    };
+    let profile = RwSignal::new(profile);
+    let (name, set_name) = lens(profile, |p| p.name.clone(), |p, v| p.name = v);
+    let (title, set_title) = lens(profile, |p| p.title.clone(), |p, v| p.title = v);
+    let (tagline, set_tagline) = lens(profile, |p| p.tagline.clone(), |p, v| p.tagline = v);
+    let (email, set_email) = lens(profile, |p| p.email.clone(), |p, v| p.email = v);
+    let (linkedin, set_linkedin) = lens(profile, |p| p.linkedin.clone(), |p, v| p.linkedin = v);
+
+    view! {
+        <EditorActions
+            payload=Signal::derive(move || Payload::Profile(profile.get()))
+            status=loaded.status
+            locale=loaded.locale
+            icons=loaded.icons
+        />
+        <EditorColumns preview=move || view! { <Hero profile=profile.get()/> }>
+            <TextField label="Nome" value=name set=set_name hint="O primeiro nome fica na primeira linha."/>
+            <TextField label="Título" value=title set=set_title/>
+            <TextField label="Chamada" value=tagline set=set_tagline/>
+            <TextField label="E-mail" kind="email" value=email set=set_email/>
+            <TextField label="LinkedIn" kind="url" value=linkedin set=set_linkedin/>
+        </EditorColumns>
+    }
+    .into_any()
+}
+
+#[component]
+pub fn ProfileEditor() -> impl IntoView {
+    view! { <EditorPage document=Document::Profile title="Perfil" form=profile_form/> }
+}
@@ -0,0 +1,171 @@
+use chrono::NaiveDate;
+use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
+
+use super::editor::{
+    lens, lens_at, split, AddButton, EditorActions, EditorColumns, EditorPage, RowAction,
+    RowControls, TextField,
+};
+use crate::admin::api::render_markdown;
+use crate::admin::editor::{Document, Loaded, Payload};
+use crate::content::{GalleryItem, Project, VideoSource};
+use crate::routes::project::ProjectArticle;
+
+#[component]
+fn VideoFields(videos: RwSignal<Vec<VideoSource>>, index: usize) -> impl IntoView {
+    let (src, set_src) = lens_at(videos, index, |v| v.src.clone(), |v, x| v.src = x);
+    let (mime, set_mime) = lens_at(videos, index, |v| v.mime.clone(), |v, x| v.mime = x);
+    let (media, set_media) = lens_at(videos, index, |v| v.media.clone().unwrap_or_default(), |v, x| v.media = Some(x));
+
+    view! {
+        <div class="grid grid-cols-1 md:grid-cols-[2fr_1fr_1fr_auto] gap-3 items-end">
+            <TextField label="Arquivo" value=src set=set_src placeholder="/assets/video_360p.mp4"/>
+            <TextField label="Tipo" value=mime set=set_mime/>
+            <TextField label="Media query" value=media set=set_media placeholder="(max-width: 640px)"/>
+            <RowControls on=move |action: RowAction| videos.update(|v| action.apply(v, index))/>
+        </div>
+    }
+}
+
+#[component]
+fn GalleryFields(gallery: RwSignal<Vec<GalleryItem>>, index: usize) -> impl IntoView {
+    let (src, set_src) = lens_at(gallery, index, |g| g.src.clone(), |g, x| g.src = x);
+    let (alt, set_alt) = lens_at(gallery, index, |g| g.alt.clone(), |g, x| g.alt = x);
+    let (caption, set_caption) = lens_at(gallery, index, |g| g.caption.clone().unwrap_or_default(), |g, x| g.caption = Some(x));
+
+    view! {
+        <div class="grid grid-cols-1 md:grid-cols-[1fr_1fr_1fr_auto] gap-3 items-end">
+            <TextField label="Imagem" value=src set=set_src/>
+            <TextField label="Texto alternativo" value=alt set=set_alt/>
+            <TextField label="Legenda" value=caption set=set_caption/>
+            <RowControls on=move |action: RowAction| gallery.update(|g| action.apply(g, index))/>
+        </div>
+    }
+}
+
+fn project_form(loaded: Loaded) -> AnyView {
+    let Payload::Project { project, body } = loaded.payload else {
+        return ().into_any();
+    };
+    let slug = project.slug.clone();
+    let videos = RwSignal::new(project.videos.clone());
+    let gallery = RwSignal::new(project.gallery.clone());
+    let project = RwSignal::new(project);
+    let body = RwSignal::new(body);
+    // Rows live in their own signals so the lenses can index them; merged back here
+    let current = move || Project {
+        videos: videos.get(),
+        gallery: gallery.get(),
+        ..project.get()
+    };
+
+    let text = |get: fn(&Project) -> &String, set: fn(&mut Project, String)| {
+        lens(project, move |p| get(p).clone(), set)
+    };
+    let optional = |get: fn(&Project) -> &Option<String>, set: fn(&mut Project, Option<String>)| {
+        lens(project, move |p| get(p).clone().unwrap_or_default(), move |p, v| set(p, Some(v)))
+    };
+    let (title, set_title) = text(|p| &p.title, |p, v| p.title = v);
+    let (summary, set_summary) = text(|p| &p.summary, |p, v| p.summary = v);
+    let (challenge, set_challenge) = optional(|p| &p.challenge, |p, v| p.challenge = v);
+    let (solution, set_solution) = optional(|p| &p.solution, |p, v| p.solution = v);
+    let (results, set_results) = optional(|p| &p.results, |p, v| p.results = v);
+    let (url, set_url) = optional(|p| &p.url, |p, v| p.url = v);
+    let (url_label, set_url_label) = optional(|p| &p.url_label, |p, v| p.url_label = v);
+    let (poster, set_poster) = optional(|p| &p.poster, |p, v| p.poster = v);
+    let (tags, set_tags) = lens(project, |p| p.tags.join(","), |p, v: String| p.tags = split(&v, ','));
+    let (date, set_date) = lens(
+        project,
+        |p| p.date.map(|d| d.to_string()).unwrap_or_default(),
+        |p, v: String| p.date = NaiveDate::parse_from_str(&v, "%Y-%m-%d").ok(),
+    );
+    let (order, set_order) = lens(
+        project,
+        |p| p.order.to_string(),
+        |p, v: String| {
+            if let Ok(order) = v.trim().parse() {
+                p.order = order
+            }
+        },
+    );
+    let (markdown, set_markdown) = lens(body, String::clone, |b, v| *b = v);
+
+    // Rendered on the server, by the same code that renders the site
+    let body_html = Resource::new(move || body.get(), render_markdown);
+    let preview = move || {
+        let project = current();
+        view! {
+            <Transition>
+                {move || {
+                    let project = project.clone();
+                    Suspend::new(async move {
+                        let body_html = body_html.await.unwrap_or_default();
+                        view! { <ProjectArticle project=Project { body_html, ..project }/> }
+                    })
+                }}
+            </Transition>
+        }
+    };
+
+    view! {
+        <EditorActions
+            payload=Signal::derive(move || Payload::Project { project: current(), body: body.get() })
+            status=loaded.status
+            locale=loaded.locale
+            icons=loaded.icons
+        />
+        <EditorColumns preview=preview>
+            <p class="text-sm text-gray-400">"Endereço: "<code>{format!("/projetos/{slug}")}</code></p>
+            <TextField label="Título" value=title set=set_title/>
+            <TextField label="Resumo" rows=3 value=summary set=set_summary/>
+            <div class="grid grid-cols-1 md:grid-cols-3 gap-4">
+                <TextField label="Tags" value=tags set=set_tags hint="Separadas por vírgula."/>
+                <TextField label="Data" kind="date" value=date set=set_date/>
+                <TextField label="Ordem" kind="number" value=order set=set_order hint="Menores aparecem primeiro."/>
+            </div>
+            <TextField label="Desafio" rows=3 value=challenge set=set_challenge/>
+            <TextField label="Solução" rows=3 value=solution set=set_solution/>
+            <TextField label="Resultados" rows=3 value=results set=set_results/>
+            <div class="grid grid-cols-1 md:grid-cols-2 gap-4">
+                <TextField label="Link" kind="url" value=url set=set_url/>
+                <TextField label="Texto do link" value=url_label set=set_url_label/>
+            </div>
+            <TextField label="Capa" value=poster set=set_poster placeholder="/assets/capa.jpg" hint="Imagem da capa e pôster do vídeo."/>
+
+            <fieldset class="space-y-3">
+                <legend class="text-xs font-bold tracking-wide text-gray-400 mb-1">"Vídeos da capa"</legend>
+                <For
+                    each=move || 0..videos.with(Vec::len)
+                    key=|index| *index
+                    children=move |index| view! { <VideoFields videos=videos index=index/> }
+                />
+                <AddButton
+                    label="+ Vídeo"
+                    on=move |_| videos.update(|v| v.push(VideoSource { mime: "video/mp4".to_string(), ..VideoSource::default() }))
+                />
+            </fieldset>
+
+            <fieldset class="space-y-3">
+                <legend class="text-xs font-bold tracking-wide text-gray-400 mb-1">"Galeria"</legend>
+                <For
+                    each=move || 0..gallery.with(Vec::len)
+                    key=|index| *index
+                    children=move |index| view! { <GalleryFields gallery=gallery index=index/> }
+                />
+                <AddButton label="+ Imagem" on=move |_| gallery.update(|g| g.push(GalleryItem::default()))/>
+            </fieldset>
+
+            <TextField label="Texto (Markdown)" rows=16 value=markdown set=set_markdown/>
+        </EditorColumns>
+    }
+    .into_any()
+}
+
+/// Editor for `/admin/projetos/:slug`. An unknown slug starts a new project.
+#[component]
+pub fn ProjectEditor() -> impl IntoView {
+    let params = use_params_map();
+    let document = Signal::derive(move || Document::Project(params.read().get("slug").unwrap_or_default()));
+
+    view! { <EditorPage document=document title="Projeto" form=project_form/> }
+}
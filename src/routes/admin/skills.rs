@@ -0,0 +1,127 @@
+use leptos::prelude::*;
+
+use super::editor::{
+    lens, lens_at, AddButton, EditorActions, EditorColumns, EditorPage, RowAction, RowControls, TextField,
+    INPUT_CLASS,
+};
+use crate::admin::editor::{Document, Loaded, Payload};
+use crate::assets;
+use crate::content::{SkillCategory, SkillItem};
+use crate::routes::home::Skills;
+
+/// A select over the icons in `assets/icons`, with the chosen one beside it.
+#[component]
+fn IconPicker(
+    icons: StoredValue<Vec<String>>,
+    #[prop(into)] value: Signal<String>,
+    #[prop(into)] set: Callback<String>,
+) -> impl IntoView {
+    view! {
+        <label class="flex items-center gap-3">
+            <span class="sr-only">"Ícone"</span>
+            <img
+                src=move || assets::url(&format!("/assets/icons/{}.png", value.get()))
+                alt=""
+                class="w-8 h-8 object-contain rounded-full bg-white/5"
+                class:invisible=move || value.with(|v| v.is_empty())
+            />
+            <select class=INPUT_CLASS prop:value=value on:change=move |ev| set.run(event_target_value(&ev))>
+                <option value="" selected=move || value.with(|v| v.is_empty())>"Escolha um ícone"</option>
+                {icons.with_value(|icons| icons.iter().map(|icon| {
+                    let name = icon.clone();
+                    view! {
+                        <option value=icon.clone() selected=move || value.with(|v| *v == name)>{icon.clone()}</option>
+                    }
+                }).collect_view())}
+            </select>
+        </label>
+    }
+}
+
+#[component]
+fn CategoryFields(categories: RwSignal<Vec<SkillCategory>>, index: usize, icons: StoredValue<Vec<String>>) -> impl IntoView {
+    let (name, set_name) = lens_at(categories, index, |c| c.category.clone(), |c, v| c.category = v);
+    let items = move || categories.with(|c| c.get(index).map_or(0, |c| c.items.len()));
+    // Runs `f` on the items of this category
+    let update_items = move |f: &dyn Fn(&mut Vec<SkillItem>)| {
+        categories.update(|c| {
+            if let Some(c) = c.get_mut(index) {
+                f(&mut c.items)
+            }
+        })
+    };
+
+    view! {
+        <fieldset class="p-4 rounded-xl border border-white/10 space-y-4">
+            <div class="flex items-end gap-3">
+                <div class="flex-1">
+                    <TextField label="Categoria" value=name set=set_name/>
+                </div>
+                <RowControls on=move |action: RowAction| categories.update(|c| action.apply(c, index))/>
+            </div>
+            <For
+                each=move || 0..items()
+                key=|item| *item
+                children=move |item| {
+                    let (item_name, set_item_name) = lens(
+                        categories,
+                        move |c| c.get(index).and_then(|c| c.items.get(item)).map(|i| i.name.clone()).unwrap_or_default(),
+                        move |c, v| {
+                            if let Some(i) = c.get_mut(index).and_then(|c| c.items.get_mut(item)) {
+                                i.name = v
+                            }
+                        },
+                    );
+                    let (icon, set_icon) = lens(
+                        categories,
+                        move |c| c.get(index).and_then(|c| c.items.get(item)).map(|i| i.icon.clone()).unwrap_or_default(),
+                        move |c, v| {
+                            if let Some(i) = c.get_mut(index).and_then(|c| c.items.get_mut(item)) {
+                                i.icon = v
+                            }
+                        },
+                    );
+                    view! {
+                        <div class="grid grid-cols-1 md:grid-cols-[1fr_1fr_auto] gap-3 items-end pl-4 border-l border-white/10">
+                            <TextField label="Habilidade" value=item_name set=set_item_name/>
+                            <IconPicker icons=icons value=icon set=set_icon/>
+                            <RowControls on=move |action: RowAction| update_items(&|items| action.apply(items, item))/>
+                        </div>
+                    }
+                }
+            />
+            <AddButton label="+ Habilidade" on=move |_| update_items(&|items| items.push(SkillItem::default()))/>
+        </fieldset>
+    }
+}
+
+fn skills_form(loaded: Loaded) -> AnyView {
+    let Payload::Skills(categories) = loaded.payload else {
+        return ().into_any();
+    };
+    let categories = RwSignal::new(categories);
+    let icons = StoredValue::new(loaded.icons.clone());
+
+    view! {
+        <EditorActions
+            payload=Signal::derive(move || Payload::Skills(categories.get()))
+            status=loaded.status
+            locale=loaded.locale
+            icons=loaded.icons
+        />
+        <EditorColumns preview=move || view! { <Skills skills=categories.get()/> }>
+            <For
+                each=move || 0..categories.with(Vec::len)
+                key=|index| *index
+                children=move |index| view! { <CategoryFields categories=categories index=index icons=icons/> }
+            />
+            <AddButton label="+ Categoria" on=move |_| categories.update(|c| c.push(SkillCategory::default()))/>
+        </EditorColumns>
+    }
+    .into_any()
+}
+
+#[component]
+pub fn SkillsEditor() -> impl IntoView {
+    view! { <EditorPage document=Document::Skills title="Habilidades" form=skills_form/> }
+}
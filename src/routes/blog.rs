@@ -0,0 +1,187 @@
+use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
+
+use crate::app::NotFound;
+use crate::components::json_ld::JsonLd;
+use crate::components::page_meta::{OgType, PageMeta, SiteMeta};
+use crate::content::schema::{BlogPosting, JsonLdDocument, Person, Thing};
+use crate::content::api::{get_post, get_posts};
+use crate::content::{tag_path, Post, PostPage};
+use crate::i18n::use_locale;
+
+#[component]
+fn TagList(tags: Vec<String>) -> impl IntoView {
+    let locale = use_locale();
+    view! {
+        <div class="flex flex-wrap gap-2">
+            {tags.into_iter().map(|tag| view! {
+                <a href=locale.localize(&tag_path(&tag)) class="px-3 py-1 bg-white/5 border border-white/10 text-gray-300 text-xs font-bold rounded-full hover:border-blue-500/50 transition-colors">
+                    {tag}
+                </a>
+            }).collect_view()}
+        </div>
+    }
+}
+
+#[component]
+fn PostCard(post: Post) -> impl IntoView {
+    let locale = use_locale();
+    view! {
+        <article class="border-b border-white/5 py-10">
+            <time datetime=post.date.to_string() class="text-sm font-mono text-gray-500">{locale.format_date(post.date)}</time>
+            <h2 class="text-3xl font-bold text-white mt-2 mb-3 font-science-gothic">
+                <a href=locale.localize(&post.path()) class="hover-underline">{post.title}</a>
+            </h2>
+            <p class="text-gray-400 mb-4 leading-relaxed">{post.summary}</p>
+            <TagList tags=post.tags/>
+        </article>
+    }
+}
+
+#[component]
+fn Pagination(page: usize, total_pages: usize, base: String) -> impl IntoView {
+    let t = use_locale().t();
+    // Page 1 lives at the base path, the rest under `/pagina/:n`
+    let href = move |n: usize| {
+        if n == 1 {
+            base.clone()
+        } else {
+            format!("{}/pagina/{n}", base.trim_end_matches('/'))
+        }
+    };
+
+    (total_pages > 1).then(|| {
+        view! {
+            <nav class="flex justify-between items-center mt-12 text-sm font-bold" aria-label=t.pagination>
+                {(page > 1).then(|| view! {
+                    <a href=href(page - 1) rel="prev" class="text-blue-400 hover-underline">{t.newer}</a>
+                })}
+                <span class="text-gray-500 font-mono">{format!("{page} / {total_pages}")}</span>
+                {(page < total_pages).then(|| view! {
+                    <a href=href(page + 1) rel="next" class="text-blue-400 hover-underline">{t.older}</a>
+                })}
+            </nav>
+        }
+    })
+}
+
+#[component]
+fn PostList(listing: PostPage) -> impl IntoView {
+    let locale = use_locale();
+    let t = locale.t();
+    let (title, base) = match &listing.tag {
+        Some(tag) => (format!("#{tag}"), locale.localize(&tag_path(tag))),
+        None => (t.nav_blog.to_string(), locale.localize("/blog")),
+    };
+
+    view! {
+        <PageMeta
+            title=format!("{title} - Nicolas Almino")
+            description=t.blog_description
+        />
+
+        <h1 class="text-5xl md:text-7xl font-black tracking-tighter text-white mb-8 font-science-gothic">{title}</h1>
+        {listing.tag.is_some().then(|| view! {
+            <a href=locale.localize("/blog") class="text-sm text-gray-400 hover:text-white transition-colors hover-underline">{t.blog_all_posts}</a>
+        })}
+
+        {listing.posts.is_empty().then(|| view! {
+            <p class="text-gray-400 mt-12">{t.blog_empty}</p>
+        })}
+        {listing.posts.into_iter().map(|post| view! { <PostCard post=post/> }).collect_view()}
+
+        <Pagination page=listing.page total_pages=listing.total_pages base=base/>
+    }
+}
+
+/// Blog index and tag archives: `/blog`, `/blog/pagina/:page`,
+/// `/blog/tag/:tag` and `/blog/tag/:tag/pagina/:page`.
+#[component]
+pub fn BlogIndex() -> impl IntoView {
+    let locale = use_locale();
+    let t = locale.t();
+    let params = use_params_map();
+    let listing = Resource::new(
+        move || {
+            params.with(|p| {
+                let page = p.get("page").map(|n| n.parse().unwrap_or(0)).unwrap_or(1);
+                (page, p.get("tag"))
+            })
+        },
+        move |(page, tag)| get_posts(locale, page, tag),
+    );
+
+    view! {
+        <Suspense fallback=|| view! { <div class="min-h-screen bg-[#0a0a0a]"></div> }>
+            {move || Suspend::new(async move {
+                listing.await.map(|listing| match listing {
+                    Some(listing) => view! {
+                        <section class="bg-[#0a0a0a] min-h-screen pt-32 pb-24 px-4">
+                            <div class="max-w-3xl mx-auto">
+                                <a href=locale.localize("/") class="inline-flex items-center gap-2 text-sm text-gray-400 hover:text-white transition-colors hover-underline mb-12">
+                                    {t.back_home}
+                                </a>
+                                <PostList listing=listing/>
+                            </div>
+                        </section>
+                    }.into_any(),
+                    None => view! { <NotFound/> }.into_any(),
+                })
+            })}
+        </Suspense>
+    }
+}
+
+/// A single post at `/blog/:slug`.
+#[component]
+pub fn BlogPost() -> impl IntoView {
+    let locale = use_locale();
+    let t = locale.t();
+    let params = use_params_map();
+    let post = Resource::new(
+        move || params.read().get("slug").unwrap_or_default(),
+        move |slug| get_post(locale, slug),
+    );
+
+    view! {
+        <Suspense fallback=|| view! { <div class="min-h-screen bg-[#0a0a0a]"></div> }>
+            {move || Suspend::new(async move {
+                post.await.map(|post| match post {
+                    Some(post) => view! {
+                        <PageMeta
+                            title=format!("{} - Nicolas Almino", post.title)
+                            description=post.summary.clone()
+                            path=locale.localize(&post.path())
+                            og_type=OgType::Article
+                            published=post.date
+                        />
+                        <JsonLd document={
+                            let site = SiteMeta::current();
+                            let author = Person::reference(&site.site_name, &site);
+                            let mut posting = BlogPosting::from_post(&post, author, &site);
+                            posting.url = site.absolute(&locale.localize(&post.path()));
+                            posting.main_entity_of_page = posting.url.clone();
+                            JsonLdDocument::new(vec![Thing::BlogPosting(posting)])
+                        }/>
+
+                        <article class="bg-[#0a0a0a] min-h-screen pt-32 pb-24 px-4">
+                            <div class="max-w-3xl mx-auto">
+                                <a href=locale.localize("/blog") class="inline-flex items-center gap-2 text-sm text-gray-400 hover:text-white transition-colors hover-underline mb-12">
+                                    {t.back_to_blog}
+                                </a>
+                                <header class="mb-12">
+                                    <time datetime=post.date.to_string() class="text-sm font-mono text-gray-500">{locale.format_date(post.date)}</time>
+                                    <h1 class="text-4xl md:text-6xl font-black tracking-tighter text-white mt-2 mb-6 font-science-gothic">{post.title.clone()}</h1>
+                                    <TagList tags=post.tags.clone()/>
+                                </header>
+                                // Rendered from Markdown on the server; the hydrate bundle has no parser
+                                <div class="prose prose-invert max-w-none" inner_html=post.body_html.clone()></div>
+                            </div>
+                        </article>
+                    }.into_any(),
+                    None => view! { <NotFound/> }.into_any(),
+                })
+            })}
+        </Suspense>
+    }
+}
@@ -1,86 +1,26 @@
 use crate::components::stacks::hstack::{HStack, AlignItems as HAlign};
 use crate::components::stacks::vstack::{VStack, AlignItems as VAlign};
+use crate::assets;
+use crate::components::json_ld::JsonLd;
+use crate::components::page_meta::{OgType, PageMeta, SiteMeta};
+use crate::content::schema::{CreativeWork, JsonLdDocument, Person, Thing};
 use leptos::html::{Div, Video, Section};
 use leptos::prelude::*;
 use leptos::ev::MouseEvent;
-use leptos_meta::Style;
+use crate::content::{
+    total_months, Experience, HomeContent, ProfileData, Project, SkillCategory,
+};
+use chrono::NaiveDate;
+use crate::content::api::get_home_content;
+use crate::components::contact_form::ContactForm;
+use crate::components::language_switcher::LanguageSwitcher;
+use crate::i18n::use_locale;
 use leptos_use::{
     use_intersection_observer_with_options,
     UseIntersectionObserverOptions,
     UseIntersectionObserverReturn,
 };
 
-// --- Data Structs ---
-
-#[derive(Clone)]
-struct ProfileData {
-    name: &'static str,
-    title: &'static str,
-    email: &'static str,
-    linkedin: &'static str,
-}
-
-#[derive(Clone)]
-struct Experience {
-    title: &'static str,
-    company: &'static str,
-    duration: &'static str,
-    tasks: &'static [&'static str],
-}
-
-#[derive(Clone)]
-struct SkillItem {
-    name: &'static str,
-    // Changed to icon_path for SVG placeholders
-    icon_path: &'static str, 
-}
-
-#[derive(Clone)]
-struct SkillCategory {
-    category: &'static str,
-    items: &'static [SkillItem],
-}
-
-// --- Data Definitions ---
-
-const PROFILE_DATA: ProfileData = ProfileData {
-    name: "NICOLAS ALMINO",
-    title: "FULLSTACK & AUDIOVISUAL",
-    email: "nicolas.almino@hotmail.com",
-    linkedin: "https://www.linkedin.com/in/nicolasalmino",
-};
-
-// Refactored Skills Data with Image Placeholders
-const SKILLS: [SkillCategory; 3] = [
-    SkillCategory {
-        category: "Engenharia de Software",
-        items: &[
-            SkillItem { name: "Rust (Leptos/Wasm)", icon_path: "rust_logo" },
-            SkillItem { name: "React / Node.js", icon_path: "react_logo" },
-            SkillItem { name: "Python / Flask", icon_path: "python_logo" },
-            SkillItem { name: "Linux / VPS", icon_path: "linux_logo" },
-            SkillItem { name: "Docker / CI/CD", icon_path: "docker_logo" },
-        ],
-    },
-    SkillCategory {
-        category: "Audiovisual & Design",
-        items: &[
-            SkillItem { name: "Blender (VSE/3D)", icon_path: "blender" },
-            SkillItem { name: "CapCut Pro", icon_path: "capcut" },
-            SkillItem { name: "UI/UX Design", icon_path: "figma" },
-            SkillItem { name: "DaVinci Resolve", icon_path: "davinci" },
-        ],
-    },
-    SkillCategory {
-        category: "Estratégia",
-        items: &[
-            SkillItem { name: "Gestão de Projetos", icon_path: "jira" },
-            SkillItem { name: "Comunicação", icon_path: "communication" },
-            SkillItem { name: "Inglês Técnico", icon_path: "english" },
-        ],
-    },
-];
-
 // --- Components ---
 
 #[component]
@@ -141,7 +81,14 @@ fn SpotlightCard(children: Children, #[prop(optional)] class: &'static str) -> i
 }
 
 #[component]
-fn Hero() -> impl IntoView {
+pub fn Hero(profile: ProfileData) -> impl IntoView {
+    let t = use_locale().t();
+    // The name is split over two lines, first name on top
+    let (first_name, last_name) = match profile.name.split_once(' ') {
+        Some((first, rest)) => (first.to_string(), rest.to_string()),
+        None => (profile.name.clone(), String::new()),
+    };
+
     // Hero-specific mouse tracking for the Grid Spotlight
     // Changed Div to Section to match the <section> element below
     let container_ref = NodeRef::<Section>::new();
@@ -195,23 +142,23 @@ fn Hero() -> impl IntoView {
             >
                 <div class="relative">
                     <h1 class="text-7xl md:text-9xl font-black tracking-tighter text-transparent bg-clip-text bg-gradient-to-b from-white to-gray-600 select-none font-science-gothic">
-                        "NICOLAS"
+                        {first_name}
                     </h1>
                     <h1 class="text-7xl md:text-9xl font-black tracking-tighter text-transparent bg-clip-text bg-gradient-to-b from-gray-400 to-gray-800 select-none font-science-gothic -mt-4 md:-mt-8">
-                        "ALMINO"
+                        {last_name}
                     </h1>
                 </div>
 
                 <p class="text-xl md:text-2xl text-blue-300 font-light tracking-widest uppercase select-none mix-blend-plus-lighter">
-                    "Web Developer • Audiovisual • High Perf"
+                    {profile.tagline}
                 </p>
 
                 <div class="flex gap-4 mt-8">
                     // Updated with .hover-underline logic via custom CSS or utility
                     <a href="#projects" class="group relative px-8 py-4 rounded-full bg-white text-black font-bold text-lg overflow-hidden transition-all hover:scale-105">
-                        <span class="relative z-10">"VER PROJETOS"</span>
+                        <span class="relative z-10">{t.hero_cta}</span>
                         <div class="absolute inset-0 bg-blue-500 transform scale-x-0 group-hover:scale-x-100 transition-transform origin-left duration-300 -z-0"></div>
-                        <span class="absolute inset-0 z-10 text-white opacity-0 group-hover:opacity-100 flex items-center justify-center transition-opacity duration-300">"VER PROJETOS"</span>
+                        <span class="absolute inset-0 z-10 text-white opacity-0 group-hover:opacity-100 flex items-center justify-center transition-opacity duration-300">{t.hero_cta}</span>
                     </a>
                 </div>
             </VStack>
@@ -220,7 +167,8 @@ fn Hero() -> impl IntoView {
 }
 
 #[component]
-fn Skills() -> impl IntoView {
+pub fn Skills(skills: Vec<SkillCategory>) -> impl IntoView {
+    let t = use_locale().t();
     view! {
         <section id="skills" class="relative py-32 px-4 overflow-hidden bg-[#0a0a0a]">
             // Engrenagem Gigante Giratória (Background)
@@ -229,26 +177,30 @@ fn Skills() -> impl IntoView {
             </div>
 
             <div class="max-w-6xl mx-auto relative z-10">
-                <h2 class="text-5xl font-bold mb-16 text-white select-none font-science-gothic">"ARSENAL TÉCNICO"</h2>
+                <h2 class="text-5xl font-bold mb-16 text-white select-none font-science-gothic">{t.skills_title}</h2>
                 
                 // MouseSpotlight container removed; SpotlightCard now handles itself
                 <div class="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 gap-6">
-                    {SKILLS.iter().map(|cat| {
+                    {skills.into_iter().map(|cat| {
                         view! {
                             <SpotlightCard class="h-full">
                                 <h3 class="text-2xl font-bold mb-6 text-blue-400 select-none">{cat.category}</h3>
                                 <div class="flex flex-wrap gap-3">
-                                    {cat.items.iter().map(|item| {
+                                    {cat.items.into_iter().map(|item| {
+                                        // Hidden when the icon fails to load; an inline
+                                        // `onerror` would be blocked by the CSP
+                                        let broken = RwSignal::new(false);
                                         view! {
                                             <div class="flex items-center gap-2 px-4 py-2 bg-white/5 rounded-full border border-white/5 hover:bg-white/10 hover:border-blue-500/50 transition-colors group/badge cursor-default">
                                                 // SVG Placeholder Image
                                                 <div class="w-5 h-5 flex items-center justify-center text-gray-400 group-hover/badge:text-blue-400 transition-colors">
                                                     // Fallback to text if image fails or for dev
                                                     <img 
-                                                        src=format!("/assets/icons/{}.png", item.icon_path) 
-                                                        alt=item.name
+                                                        src=assets::url(&format!("/assets/icons/{}.png", item.icon))
+                                                        alt=item.name.clone()
                                                         class="w-full h-full object-contain filter grayscale group-hover/badge:grayscale-0 transition-all rounded-full"
-                                                        onerror="this.style.display='none'"
+                                                        class:hidden=move || broken.get()
+                                                        on:error=move |_| broken.set(true)
                                                     />
                                                 </div>
                                                 <span class="text-sm font-medium text-gray-200 hover-underline">{item.name}</span>
@@ -265,8 +217,11 @@ fn Skills() -> impl IntoView {
     }
 }
 
+/// Large showcase layout for projects with a cover video.
 #[component]
-fn ProjectShowcase() -> impl IntoView {
+fn FeaturedProject(project: Project) -> impl IntoView {
+    let locale = use_locale();
+    let t = locale.t();
     let video_ref = NodeRef::<Video>::new();
     let (is_visible, set_visible) = signal(false);
 
@@ -295,151 +250,346 @@ fn ProjectShowcase() -> impl IntoView {
     });
 
     view! {
-        <section id="projects" class="py-32 px-4 bg-[#0c0c0c]">
-            <div class="max-w-7xl mx-auto">
-                <h2 class="text-5xl font-bold mb-20 text-white text-right select-none font-science-gothic">"PROJETOS SELECIONADOS"</h2>
-
-                // Projeto 1: Camará
-                <div class="grid grid-cols-1 lg:grid-cols-2 gap-12 items-center mb-32">
-                    <div class="order-2 lg:order-1">
-                        <div class="relative rounded-3xl overflow-hidden border border-white/10 shadow-2xl group cursor-pointer">
-                            <video
-                                node_ref=video_ref
-                                class="w-full h-auto object-cover transform group-hover:scale-105 transition-transform duration-700"
-                                loop=true
-                                muted=true
-                                playsinline=true
-                                poster="/assets/camara_poster.jpg" 
-                            >
-                                <source src="/assets/camaracapoeira_720p.mp4" type="video/mp4"/>
-                            </video>
-                            <div class="absolute inset-0 bg-gradient-to-t from-black/80 to-transparent opacity-60"></div>
-                            
-                            <div class="absolute bottom-6 left-6 flex gap-2">
-                                <span class="px-3 py-1 bg-orange-600/90 text-white text-xs font-bold rounded-full backdrop-blur-md">"RUST"</span>
-                                <span class="px-3 py-1 bg-blue-600/90 text-white text-xs font-bold rounded-full backdrop-blur-md">"LEPTOS"</span>
-                            </div>
-                        </div>
-                    </div>
-                    
-                    <div class="order-1 lg:order-2 text-left lg:pl-10">
-                        <h3 class="text-4xl font-bold text-white mb-4 select-none font-science-gothic">"ASSOCIAÇÃO CAMARÁ"</h3>
-                        <p class="text-xl text-gray-400 mb-8 leading-relaxed select-none">
-                            "Transformação digital completa para uma ONG de 25 anos. Desenvolvi uma plataforma de alta performance capaz de rodar em hardware de baixo custo, garantindo inclusão digital na fronteira."
-                        </p>
-                        
-                        <div class="flex flex-col gap-4 border-l-2 border-blue-500 pl-6 mb-8">
-                            <div class="text-sm text-gray-300">
-                                <strong class="text-white block text-lg mb-1">"Desafio"</strong>
-                                "Infraestrutura limitada e necessidade de custo zero de manutenção."
-                            </div>
-                            <div class="text-sm text-gray-300">
-                                <strong class="text-white block text-lg mb-1">"Solução"</strong>
-                                "Arquitetura Server-Side em Rust com binário de 6MB."
-                            </div>
-                        </div>
-
-                        // // Link with new animation
-                        // <a href="#" class="inline-flex items-center gap-2 text-blue-400 font-bold tracking-wider hover-underline select-none">
-                        //     "VER CASE COMPLETO" 
-                        //     <svg class="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24"><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M17 8l4 4m0 0l-4 4m4-4H3"></path></svg>
-                        // </a>
+        <div class="grid grid-cols-1 lg:grid-cols-2 gap-12 items-center mb-32">
+            <div class="order-2 lg:order-1">
+                <div class="relative rounded-3xl overflow-hidden border border-white/10 shadow-2xl group cursor-pointer">
+                    <video
+                        node_ref=video_ref
+                        class="w-full h-auto object-cover transform group-hover:scale-105 transition-transform duration-700"
+                        loop=true
+                        muted=true
+                        playsinline=true
+                        poster=project.poster.as_deref().map(assets::url)
+                    >
+                        {project.videos.iter().map(|video| view! {
+                            <source src=assets::url(&video.src) type=video.mime.clone() media=video.media.clone()/>
+                        }).collect_view()}
+                    </video>
+                    <div class="absolute inset-0 bg-gradient-to-t from-black/80 to-transparent opacity-60"></div>
+
+                    <div class="absolute bottom-6 left-6 flex gap-2">
+                        {project.tags.iter().enumerate().map(|(i, tag)| {
+                            let color = if i == 0 { "bg-orange-600/90" } else { "bg-blue-600/90" };
+                            view! {
+                                <span class=format!("px-3 py-1 {color} text-white text-xs font-bold rounded-full backdrop-blur-md")>{tag.clone()}</span>
+                            }
+                        }).collect_view()}
                     </div>
                 </div>
+            </div>
+
+            <div class="order-1 lg:order-2 text-left lg:pl-10">
+                <h3 class="text-4xl font-bold text-white mb-4 select-none font-science-gothic">{project.title.clone()}</h3>
+                <p class="text-xl text-gray-400 mb-8 leading-relaxed select-none">
+                    {project.summary.clone()}
+                </p>
 
-                // Projeto 2: Esportes
-                <div class="bg-[#151515] rounded-3xl p-8 md:p-12 border border-white/5">
-                    <HStack align=HAlign::Center justify=crate::components::stacks::hstack::JustifyContent::SpaceBetween wrap=crate::components::stacks::hstack::FlexWrap::Wrap spacing="2rem".to_string()>
-                        <div class="max-w-xl">
-                            <h3 class="text-3xl font-bold text-white mb-4 select-none">"ESPORTES NA TV"</h3>
-                            <p class="text-gray-400 mb-6 select-none">
-                                "Portal de guias esportivos com alto volume de tráfego. Foco em SEO e retenção de usuário."
-                            </p>
+                <div class="flex flex-col gap-4 border-l-2 border-blue-500 pl-6 mb-8">
+                    {project.challenge.clone().map(|challenge| view! {
+                        <div class="text-sm text-gray-300">
+                            <strong class="text-white block text-lg mb-1">{t.challenge}</strong>
+                            {challenge}
+                        </div>
+                    })}
+                    {project.solution.clone().map(|solution| view! {
+                        <div class="text-sm text-gray-300">
+                            <strong class="text-white block text-lg mb-1">{t.solution}</strong>
+                            {solution}
                         </div>
-                        <a href="https://esportesnatv.com.br" target="_blank" class="px-6 py-3 bg-white/5 hover:bg-white/10 rounded-xl text-white font-medium transition-colors select-none border border-white/10 hover-underline">
-                            "Acessar Portal"
-                        </a>
-                    </HStack>
+                    })}
                 </div>
+
+                // Link with new animation
+                <a href=locale.localize(&project.path()) class="inline-flex items-center gap-2 text-blue-400 font-bold tracking-wider hover-underline select-none">
+                    {t.view_case}
+                    <svg class="w-4 h-4" fill="none" stroke="currentColor" viewBox="0 0 24 24"><path stroke-linecap="round" stroke-linejoin="round" stroke-width="2" d="M17 8l4 4m0 0l-4 4m4-4H3"></path></svg>
+                </a>
+            </div>
+        </div>
+    }
+}
+
+/// Compact card for projects without cover media.
+#[component]
+fn CompactProject(project: Project) -> impl IntoView {
+    let locale = use_locale();
+    view! {
+        <div class="bg-[#151515] rounded-3xl p-8 md:p-12 border border-white/5 mb-12">
+            <HStack align=HAlign::Center justify=crate::components::stacks::hstack::JustifyContent::SpaceBetween wrap=crate::components::stacks::hstack::FlexWrap::Wrap spacing="2rem".to_string()>
+                <div class="max-w-xl">
+                    <h3 class="text-3xl font-bold text-white mb-4 select-none">
+                        <a href=locale.localize(&project.path()) class="hover-underline">{project.title}</a>
+                    </h3>
+                    <p class="text-gray-400 mb-6 select-none">
+                        {project.summary}
+                    </p>
+                </div>
+                {project.url.map(|url| view! {
+                    <a href=url target="_blank" class="px-6 py-3 bg-white/5 hover:bg-white/10 rounded-xl text-white font-medium transition-colors select-none border border-white/10 hover-underline">
+                        {project.url_label.unwrap_or_else(|| locale.t().visit.to_string())}
+                    </a>
+                })}
+            </HStack>
+        </div>
+    }
+}
+
+#[component]
+fn ProjectShowcase(projects: Vec<Project>) -> impl IntoView {
+    let t = use_locale().t();
+    view! {
+        <section id="projects" class="py-32 px-4 bg-[#0c0c0c]">
+            <div class="max-w-7xl mx-auto">
+                <h2 class="text-5xl font-bold mb-20 text-white text-right select-none font-science-gothic">{t.projects_title}</h2>
+
+                {projects.into_iter().map(|project| {
+                    if project.is_featured() {
+                        view! { <FeaturedProject project=project/> }.into_any()
+                    } else {
+                        view! { <CompactProject project=project/> }.into_any()
+                    }
+                }).collect_view()}
             </div>
         </section>
     }
 }
 
-// --- Main Page ---
+/// A single role on the timeline. Long task lists start collapsed.
+#[component]
+fn TimelineEntry(
+    experience: Experience,
+    today: NaiveDate,
+    filter: RwSignal<Option<String>>,
+) -> impl IntoView {
+    const COLLAPSED_TASKS: usize = 2;
+
+    let locale = use_locale();
+    let t = locale.t();
+    let expanded = RwSignal::new(false);
+    let duration = locale.format_duration(experience.months(today));
+    let period = format!(
+        "{} — {}",
+        locale.format_month(experience.start),
+        experience
+            .end
+            .map(|end| locale.format_month(end))
+            .unwrap_or_else(|| t.timeline_present.to_string()),
+    );
+    let tags = StoredValue::new(experience.tags.clone());
+    let matches = move || {
+        filter
+            .get()
+            .map(|tag| tags.with_value(|tags| tags.contains(&tag)))
+            .unwrap_or(true)
+    };
+    let tasks = StoredValue::new(experience.tasks);
+    let hidden_tasks = tasks.with_value(|t| t.len().saturating_sub(COLLAPSED_TASKS));
+
+    view! {
+        <li class="relative pl-10 pb-12" class:hidden=move || !matches()>
+            // Marker on the vertical line
+            <span class="absolute left-0 top-1.5 w-4 h-4 rounded-full bg-blue-500 ring-4 ring-blue-500/20"></span>
+
+            <SpotlightCard>
+                <div class="flex flex-wrap items-baseline justify-between gap-2 mb-2">
+                    <h3 class="text-2xl font-bold text-white select-none">{experience.title}</h3>
+                    <span class="text-sm font-mono text-gray-500">{duration}</span>
+                </div>
+                <p class="text-blue-400 font-medium mb-1">{experience.company}</p>
+                <p class="text-sm text-gray-500 mb-4">{period}</p>
+
+                <ul class="list-disc list-inside space-y-1 text-gray-300">
+                    {move || {
+                        let limit = if expanded.get() { usize::MAX } else { COLLAPSED_TASKS };
+                        tasks.with_value(|tasks| {
+                            tasks.iter().take(limit).map(|task| view! { <li>{task.clone()}</li> }).collect_view()
+                        })
+                    }}
+                </ul>
+
+                <Show when=move || { hidden_tasks > 0 }>
+                    <button
+                        type="button"
+                        class="mt-3 text-sm font-bold text-blue-400 hover-underline"
+                        aria-expanded=move || expanded.get().to_string()
+                        on:click=move |_| expanded.update(|v| *v = !*v)
+                    >
+                        {move || if expanded.get() {
+                            t.show_less.to_string()
+                        } else {
+                            format!("{} ({hidden_tasks})", t.show_more)
+                        }}
+                    </button>
+                </Show>
+
+                <div class="flex flex-wrap gap-2 mt-4">
+                    {tags.with_value(|tags| tags.iter().map(|tag| view! {
+                        <span class="px-3 py-1 bg-white/5 border border-white/10 text-gray-300 text-xs font-bold rounded-full">{tag.clone()}</span>
+                    }).collect_view())}
+                </div>
+            </SpotlightCard>
+        </li>
+    }
+}
 
+/// Career timeline for the "SOBRE" section, filterable by skill tag.
 #[component]
-pub fn HomePage() -> impl IntoView {
+pub fn CareerTimeline(experiences: Vec<Experience>, today: NaiveDate) -> impl IntoView {
+    let locale = use_locale();
+    let t = locale.t();
+    let filter = RwSignal::new(None::<String>);
+    let total = locale.format_experience(total_months(&experiences, today));
+    let has_roles = !experiences.is_empty();
+
+    let mut tags = experiences
+        .iter()
+        .flat_map(|e| e.tags.iter().cloned())
+        .collect::<Vec<_>>();
+    tags.sort();
+    tags.dedup();
+
+    let chip_class = move |active: bool| {
+        if active {
+            "px-4 py-2 rounded-full text-sm font-bold bg-blue-600 text-white border border-blue-500 transition-colors"
+        } else {
+            "px-4 py-2 rounded-full text-sm font-bold bg-white/5 text-gray-300 border border-white/10 hover:bg-white/10 transition-colors"
+        }
+    };
+
     view! {
-        <Style>
-            {
-                r#"
-                /* Font Settings */
-                .font-science-gothic {
-                    font-family: 'Science Gothic', sans-serif;
-                    font-variation-settings: 'wght' 700, 'wdth' 100;
-                }
+        <section id="about" class="py-32 px-4 bg-[#0a0a0a]">
+            <div class="max-w-4xl mx-auto">
+                <h2 class="text-5xl font-bold mb-4 text-white select-none font-science-gothic">{t.timeline_title}</h2>
+                {has_roles.then(|| view! {
+                    <p class="text-gray-400 mb-12 select-none">{total}</p>
+                })}
+
+                <div class="flex flex-wrap gap-3 mb-12">
+                    <button type="button" class=move || chip_class(filter.get().is_none()) on:click=move |_| filter.set(None)>
+                        {t.timeline_all}
+                    </button>
+                    {tags.into_iter().map(|tag| {
+                        let value = tag.clone();
+                        let selected = tag.clone();
+                        view! {
+                            <button
+                                type="button"
+                                class=move || chip_class(filter.with(|f| f.as_deref() == Some(selected.as_str())))
+                                on:click=move |_| filter.set(Some(value.clone()))
+                            >
+                                {tag}
+                            </button>
+                        }
+                    }).collect_view()}
+                </div>
 
-                /* Disable Selection Global */
-                * {
-                    user-select: none;
-                    -webkit-user-select: none;
-                }
+                <ol class="relative border-l border-white/10 ml-2">
+                    {experiences.into_iter().map(|experience| view! {
+                        <TimelineEntry experience=experience today=today filter=filter/>
+                    }).collect_view()}
+                </ol>
+            </div>
+        </section>
+    }
+}
 
-                /* Custom Scrollbar */
-                ::-webkit-scrollbar {
-                    width: 8px;
-                }
-                ::-webkit-scrollbar-track {
-                    background: #0a0a0a;
-                }
-                ::-webkit-scrollbar-thumb {
-                    background: #333;
-                    border-radius: 4px;
-                }
-                ::-webkit-scrollbar-thumb:hover {
-                    background: #555;
-                }
-                
-                .bg-radial-fade {
-                    background: radial-gradient(circle at center, transparent 0%, #0a0a0a 100%);
-                }
+/// Contact form, with the address for visitors who prefer their mail client.
+#[component]
+fn Contact(email: String) -> impl IntoView {
+    let t = use_locale().t();
 
-                @keyframes spin-slow {
-                    from { transform: rotate(0deg); }
-                    to { transform: rotate(360deg); }
-                }
-                .animate-spin-slow {
-                    animation: spin-slow 60s linear infinite;
-                }
-                "#
-            }
-        </Style>
+    view! {
+        <section id="contact" class="py-32 px-4 bg-[#0c0c0c]">
+            <div class="max-w-3xl mx-auto">
+                <h2 class="text-5xl font-bold mb-4 text-white select-none font-science-gothic">{t.nav_contact}</h2>
+                <p class="text-gray-400 mb-12">{t.contact_intro}</p>
+                <ContactForm/>
+                <p class="mt-12 text-sm text-gray-500">
+                    {t.contact_direct}" "
+                    <a href=format!("mailto:{email}") class="text-blue-400 hover-underline">{email.clone()}</a>
+                </p>
+            </div>
+        </section>
+    }
+}
 
-        <header class="fixed top-0 left-0 right-0 z-50 px-6 py-4 transition-all duration-300">
+// --- Main Page ---
+
+/// Preview tags for the home page; the featured project's video becomes `og:video`.
+#[component]
+fn HomeMeta(profile: ProfileData, projects: Vec<Project>) -> impl IntoView {
+    let locale = use_locale();
+    let site = SiteMeta::current();
+    let mut person = Person::from_profile(&profile, &site);
+    person.url = site.absolute(&locale.localize("/"));
+    let mut graph = vec![Thing::Person(person.clone())];
+    graph.extend(projects.iter().map(|project| {
+        let mut work = CreativeWork::from_project(project, person.clone(), &site);
+        work.url = site.absolute(&locale.localize(&project.path()));
+        Thing::CreativeWork(work)
+    }));
+    let featured = projects.into_iter().find(Project::is_featured);
+    let name = profile.name.split_whitespace().map(|word| {
+        let mut chars = word.chars();
+        chars.next().map(|c| c.to_string() + &chars.as_str().to_lowercase()).unwrap_or_default()
+    }).collect::<Vec<_>>().join(" ");
+
+    view! {
+        <PageMeta
+            title=format!("{name} - {}", profile.title)
+            description=profile.tagline
+            path=locale.localize("/")
+            og_type=OgType::Profile
+            video=featured.as_ref().and_then(|p| p.videos.first()).map(|v| v.src.clone())
+            video_type=featured.as_ref().and_then(|p| p.videos.first()).map(|v| v.mime.clone())
+        />
+        <JsonLd document=JsonLdDocument::new(graph)/>
+    }
+}
+
+#[component]
+pub fn HomePage() -> impl IntoView {
+    let locale = use_locale();
+    let t = locale.t();
+    let content = Resource::new(move || locale, get_home_content);
+
+    view! {
+        <header class="fixed top-0 left-0 right-0 z-50 px-6 py-4 transition-all duration-300 select-none">
             <div class="max-w-7xl mx-auto bg-black/50 backdrop-blur-xl border border-white/10 rounded-full px-6 py-3 flex justify-between items-center shadow-lg">
                 <span class="font-science-gothic font-bold text-xl tracking-wider text-white">"NA."</span>
                 
                 <nav class="hidden md:flex gap-8">
-                    <a href="#projects" class="text-sm font-medium text-gray-300 hover:text-white transition-colors hover-underline">"PROJETOS"</a>
-                    <a href="#skills" class="text-sm font-medium text-gray-300 hover:text-white transition-colors hover-underline">"SKILLS"</a>
-                    <a href="#about" class="text-sm font-medium text-gray-300 hover:text-white transition-colors hover-underline">"SOBRE"</a>
+                    <a href="#projects" class="text-sm font-medium text-gray-300 hover:text-white transition-colors hover-underline">{t.nav_projects}</a>
+                    <a href="#skills" class="text-sm font-medium text-gray-300 hover:text-white transition-colors hover-underline">{t.nav_skills}</a>
+                    <a href="#about" class="text-sm font-medium text-gray-300 hover:text-white transition-colors hover-underline">{t.nav_about}</a>
+                    <a href=locale.localize("/blog") class="text-sm font-medium text-gray-300 hover:text-white transition-colors hover-underline">{t.nav_blog}</a>
                 </nav>
 
-                <a href="mailto:nicolas.almino@hotmail.com" class="bg-white text-black px-4 py-1.5 rounded-full text-sm font-bold hover:bg-gray-200 transition-colors">
-                    "CONTATO"
+                <LanguageSwitcher/>
+
+                <a href="#contact" class="bg-white text-black px-4 py-1.5 rounded-full text-sm font-bold hover:bg-gray-200 transition-colors">
+                    {t.nav_contact}
                 </a>
             </div>
         </header>
 
-        <main class="bg-[#0a0a0a]">
-            <Hero/>
-            <ProjectShowcase/>
-            <Skills/>
+        <main class="bg-[#0a0a0a] select-none">
+            <Suspense fallback=|| view! { <div class="min-h-[90vh] bg-[#050505]"></div> }>
+                {move || Suspend::new(async move {
+                    content.await.map(|HomeContent { profile, skills, experiences, projects, today }| view! {
+                        <HomeMeta profile=profile.clone() projects=projects.clone()/>
+                        <Hero profile=profile.clone()/>
+                        <ProjectShowcase projects=projects/>
+                        <Skills skills=skills/>
+                        <CareerTimeline experiences=experiences today=today/>
+                        <Contact email=profile.email.clone()/>
+                    })
+                })}
+            </Suspense>
             
             <footer class="py-12 text-center text-gray-600 text-sm font-mono border-t border-white/5">
-                <p>"DESIGNED & CODED BY NICOLAS ALMINO"</p>
-                <p class="mt-2">"POWERED BY RUST & LEPTOS"</p>
+                <p>{t.footer_credits}</p>
+                <p class="mt-2">{t.footer_powered}</p>
             </footer>
         </main>
     }
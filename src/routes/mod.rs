@@ -1 +1,4 @@
-pub mod home;
\ No newline at end of file
+pub mod admin;
+pub mod blog;
+pub mod home;
+pub mod project;
@@ -0,0 +1,150 @@
+use leptos::prelude::*;
+use leptos_router::hooks::use_params_map;
+
+use crate::app::NotFound;
+use crate::assets;
+use crate::components::json_ld::JsonLd;
+use crate::components::page_meta::{OgType, PageMeta, SiteMeta};
+use crate::content::schema::{CreativeWork, JsonLdDocument, Person, Thing};
+use crate::content::api::get_project;
+use crate::content::Project;
+use crate::i18n::use_locale;
+
+/// A titled block of the case study (challenge / solution / results).
+#[component]
+fn CaseSection(title: &'static str, text: Option<String>) -> impl IntoView {
+    text.map(|text| {
+        view! {
+            <div class="border-l-2 border-blue-500 pl-6">
+                <h2 class="text-2xl font-bold text-white mb-3 select-none font-science-gothic">{title}</h2>
+                <p class="text-lg text-gray-300 leading-relaxed">{text}</p>
+            </div>
+        }
+    })
+}
+
+/// The case study itself. Also used by the admin editor's preview.
+#[component]
+pub fn ProjectArticle(project: Project) -> impl IntoView {
+    let locale = use_locale();
+    let t = locale.t();
+    let cover = if project.is_featured() {
+        view! {
+            <video
+                class="w-full h-auto object-cover"
+                controls=true
+                muted=true
+                playsinline=true
+                preload="metadata"
+                poster=project.poster.as_deref().map(assets::url)
+            >
+                {project.videos.iter().map(|video| view! {
+                    <source src=assets::url(&video.src) type=video.mime.clone() media=video.media.clone()/>
+                }).collect_view()}
+            </video>
+        }
+        .into_any()
+    } else if let Some(poster) = project.poster.as_deref() {
+        view! { <img src=assets::url(poster) alt=project.title.clone() class="w-full h-auto object-cover"/> }.into_any()
+    } else {
+        ().into_any()
+    };
+
+    view! {
+        <article class="bg-[#0a0a0a] pt-32 pb-24 px-4">
+            <div class="max-w-5xl mx-auto">
+                <a href=locale.localize("/#projects") class="inline-flex items-center gap-2 text-sm text-gray-400 hover:text-white transition-colors hover-underline mb-12">
+                    {t.back_to_projects}
+                </a>
+
+                <header class="mb-12">
+                    <div class="flex flex-wrap gap-2 mb-6">
+                        {project.tags.iter().map(|tag| view! {
+                            <span class="px-3 py-1 bg-blue-600/90 text-white text-xs font-bold rounded-full">{tag.clone()}</span>
+                        }).collect_view()}
+                    </div>
+                    <h1 class="text-5xl md:text-7xl font-black tracking-tighter text-white mb-6 font-science-gothic">{project.title.clone()}</h1>
+                    <p class="text-xl text-gray-400 leading-relaxed max-w-3xl">{project.summary.clone()}</p>
+                </header>
+
+                <div class="rounded-3xl overflow-hidden border border-white/10 shadow-2xl mb-16">
+                    {cover}
+                </div>
+
+                <div class="flex flex-col gap-10 mb-16">
+                    <CaseSection title=t.challenge text=project.challenge.clone()/>
+                    <CaseSection title=t.solution text=project.solution.clone()/>
+                    <CaseSection title=t.results text=project.results.clone()/>
+                </div>
+
+                // Markdown body, rendered to HTML on the server when content is loaded
+                <div class="prose prose-invert max-w-none mb-16" inner_html=project.body_html.clone()></div>
+
+                {(!project.gallery.is_empty()).then(|| view! {
+                    <div class="grid grid-cols-1 md:grid-cols-2 gap-6 mb-16">
+                        {project.gallery.iter().map(|item| view! {
+                            <figure class="rounded-3xl overflow-hidden border border-white/10 bg-[#151515]">
+                                <img src=assets::url(&item.src) alt=item.alt.clone() loading="lazy" class="w-full h-auto object-cover"/>
+                                {item.caption.clone().map(|caption| view! {
+                                    <figcaption class="p-4 text-sm text-gray-400">{caption}</figcaption>
+                                })}
+                            </figure>
+                        }).collect_view()}
+                    </div>
+                })}
+
+                {project.url.clone().map(|url| view! {
+                    <a href=url target="_blank" class="px-6 py-3 bg-white/5 hover:bg-white/10 rounded-xl text-white font-medium transition-colors select-none border border-white/10 hover-underline">
+                        {project.url_label.clone().unwrap_or_else(|| t.visit.to_string())}
+                    </a>
+                })}
+            </div>
+        </article>
+    }
+}
+
+/// The case study with its meta tags and structured data.
+#[component]
+fn ProjectCase(project: Project) -> impl IntoView {
+    let locale = use_locale();
+    let path = locale.localize(&project.path());
+    let site = SiteMeta::current();
+    let mut work = CreativeWork::from_project(&project, Person::reference(&site.site_name, &site), &site);
+    work.url = site.absolute(&path);
+
+    view! {
+        <PageMeta
+            title=format!("{} - Nicolas Almino", project.title)
+            description=project.summary.clone()
+            path=path
+            video=project.videos.first().map(|video| video.src.clone())
+            video_type=project.videos.first().map(|video| video.mime.clone())
+            og_type=OgType::Article
+            published=project.date
+        />
+        <JsonLd document=JsonLdDocument::new(vec![Thing::CreativeWork(work)])/>
+        <ProjectArticle project=project/>
+    }
+}
+
+/// Case study page at `/projetos/:slug`. Unknown slugs render [`NotFound`].
+#[component]
+pub fn ProjectPage() -> impl IntoView {
+    let locale = use_locale();
+    let params = use_params_map();
+    let project = Resource::new(
+        move || params.read().get("slug").unwrap_or_default(),
+        move |slug| get_project(locale, slug),
+    );
+
+    view! {
+        <Suspense fallback=|| view! { <div class="min-h-screen bg-[#0a0a0a]"></div> }>
+            {move || Suspend::new(async move {
+                project.await.map(|project| match project {
+                    Some(project) => view! { <ProjectCase project=project/> }.into_any(),
+                    None => view! { <NotFound/> }.into_any(),
+                })
+            })}
+        </Suspense>
+    }
+}
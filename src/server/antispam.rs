@@ -0,0 +1,328 @@
+//! Issues and verifies proof-of-work challenges, and limits submissions per IP
+//! (see [`RateLimit`], also used for other public endpoints).
+
+use std::collections::{HashMap, VecDeque};
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use actix_web::HttpRequest;
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+use super::{client_ip, hex, random_bytes};
+use crate::antispam::{Challenge, Rejection};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Accepted difficulties; every extra bit doubles the client's work.
+pub const DIFFICULTY_RANGE: std::ops::RangeInclusive<u32> = 1..=32;
+
+#[derive(Clone, Debug)]
+pub struct AntispamConfig {
+    /// HMAC key. Without one a random key is generated at startup, which
+    /// invalidates outstanding challenges on restart.
+    pub secret: Option<String>,
+    /// Leading zero bits required, within [`DIFFICULTY_RANGE`]. 18 takes a
+    /// fraction of a second in WASM.
+    pub difficulty: u32,
+    /// How long a challenge stays valid.
+    pub ttl: Duration,
+    /// Submissions allowed per IP within `window`, at least 1.
+    pub max_submissions: usize,
+    pub window: Duration,
+    /// Take the client IP from `Forwarded` / `X-Forwarded-For`. Only enable
+    /// behind a reverse proxy that sets them.
+    pub trust_proxy: bool,
+}
+
+impl Default for AntispamConfig {
+    fn default() -> Self {
+        AntispamConfig {
+            secret: None,
+            difficulty: 18,
+            ttl: Duration::from_secs(600),
+            max_submissions: 5,
+            window: Duration::from_secs(600),
+            trust_proxy: false,
+        }
+    }
+}
+
+/// At most `max` hits per client address within a sliding `window`.
+pub struct RateLimit {
+    max: usize,
+    window: Duration,
+    /// Recent hit times per client.
+    hits: Mutex<HashMap<IpAddr, VecDeque<Instant>>>,
+}
+
+impl RateLimit {
+    pub fn new(max: usize, window: Duration) -> Self {
+        RateLimit {
+            max,
+            window,
+            hits: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Counts a hit from `ip`, `false` past the limit. Requests without a
+    /// known address share one bucket.
+    pub fn check(&self, ip: Option<IpAddr>) -> bool {
+        let ip = ip.unwrap_or(IpAddr::from([0, 0, 0, 0]));
+        let now = Instant::now();
+        let window = self.window;
+        let mut hits = self.hits.lock().unwrap_or_else(|e| e.into_inner());
+
+        // Drop idle clients now and then so the map does not grow forever
+        if hits.len() > 10_000 {
+            hits.retain(|_, times| times.back().is_some_and(|t| now - *t < window));
+        }
+        let times = hits.entry(ip).or_default();
+        while times.front().is_some_and(|t| now - *t >= window) {
+            times.pop_front();
+        }
+        if times.len() >= self.max {
+            return false;
+        }
+        times.push_back(now);
+        true
+    }
+}
+
+/// Shared by the workers through `web::Data`.
+pub struct Antispam {
+    key: Vec<u8>,
+    config: AntispamConfig,
+    /// Salts of used challenges, with their expiry, to refuse replays.
+    spent: Mutex<HashMap<String, i64>>,
+    submissions: RateLimit,
+}
+
+impl Antispam {
+    pub fn new(config: AntispamConfig) -> Self {
+        let key = match &config.secret {
+            Some(secret) => secret.as_bytes().to_vec(),
+            None => random_bytes(),
+        };
+        Antispam {
+            key,
+            submissions: RateLimit::new(config.max_submissions, config.window),
+            config,
+            spent: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn mac(&self, challenge: &Challenge) -> HmacSha256 {
+        let mut mac = HmacSha256::new_from_slice(&self.key).expect("HMAC accepts any key length");
+        mac.update(challenge.signed_part().as_bytes());
+        mac
+    }
+
+    pub fn issue(&self) -> Challenge {
+        let mut challenge = Challenge {
+            salt: hex(&random_bytes()),
+            difficulty: self.config.difficulty,
+            expires: Utc::now().timestamp() + self.config.ttl.as_secs() as i64,
+            signature: String::new(),
+        };
+        challenge.signature = hex(&self.mac(&challenge).finalize().into_bytes());
+        challenge
+    }
+
+    /// Checks a submitted token and nonce, and marks the challenge as used.
+    pub fn verify(&self, token: &str, nonce: &str) -> Result<(), Rejection> {
+        if token.is_empty() || nonce.is_empty() {
+            return Err(Rejection::Missing);
+        }
+        let challenge = Challenge::from_token(token).ok_or(Rejection::Forged)?;
+        let signature = unhex(&challenge.signature).ok_or(Rejection::Forged)?;
+        self.mac(&challenge)
+            .verify_slice(&signature)
+            .map_err(|_| Rejection::Forged)?;
+
+        let now = Utc::now().timestamp();
+        if challenge.expires < now {
+            return Err(Rejection::Expired);
+        }
+        let nonce = nonce.trim().parse::<u64>().map_err(|_| Rejection::Unsolved)?;
+        if !challenge.is_solved_by(nonce) {
+            return Err(Rejection::Unsolved);
+        }
+
+        let mut spent = self.spent.lock().unwrap_or_else(|e| e.into_inner());
+        spent.retain(|_, expires| *expires >= now);
+        if spent.insert(challenge.salt, challenge.expires).is_some() {
+            return Err(Rejection::Replayed);
+        }
+        Ok(())
+    }
+
+    /// Counts a submission from `ip`, refusing it past the limit. Requests
+    /// without a known address share one bucket.
+    pub fn check_rate(&self, ip: Option<IpAddr>) -> Result<(), Rejection> {
+        match self.submissions.check(ip) {
+            true => Ok(()),
+            false => Err(Rejection::RateLimited),
+        }
+    }
+
+    /// The client address used for rate limiting.
+    pub fn client_ip(&self, req: &HttpRequest) -> Option<IpAddr> {
+        client_ip(req, self.config.trust_proxy)
+    }
+}
+
+fn unhex(text: &str) -> Option<Vec<u8>> {
+    if !text.len().is_multiple_of(2) {
+        return None;
+    }
+    (0..text.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(text.get(i..i + 2)?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread::sleep;
+
+    use super::*;
+
+    fn antispam(config: AntispamConfig) -> Antispam {
+        Antispam::new(AntispamConfig {
+            secret: Some("test secret".to_string()),
+            difficulty: 8,
+            ..config
+        })
+    }
+
+    /// A token for `challenge` signed by `antispam`, and the nonce solving it.
+    fn signed(antispam: &Antispam, mut challenge: Challenge) -> (String, String) {
+        challenge.signature = hex(&antispam.mac(&challenge).finalize().into_bytes());
+        let nonce = challenge.solve().to_string();
+        (challenge.to_token(), nonce)
+    }
+
+    #[test]
+    fn accepts_a_solved_challenge() {
+        let antispam = antispam(AntispamConfig::default());
+        let challenge = antispam.issue();
+        let nonce = challenge.solve().to_string();
+        assert_eq!(antispam.verify(&challenge.to_token(), &nonce), Ok(()));
+    }
+
+    #[test]
+    fn missing_fields() {
+        let antispam = antispam(AntispamConfig::default());
+        let token = antispam.issue().to_token();
+        assert_eq!(antispam.verify("", "1"), Err(Rejection::Missing));
+        assert_eq!(antispam.verify(&token, ""), Err(Rejection::Missing));
+    }
+
+    #[test]
+    fn forged_signature() {
+        let antispam = antispam(AntispamConfig::default());
+        let mut challenge = antispam.issue();
+        let nonce = challenge.solve().to_string();
+        let last = challenge.signature.pop().unwrap();
+        challenge.signature.push(if last == '0' { '1' } else { '0' });
+        assert_eq!(antispam.verify(&challenge.to_token(), &nonce), Err(Rejection::Forged));
+        assert_eq!(antispam.verify("not a token", &nonce), Err(Rejection::Forged));
+    }
+
+    #[test]
+    fn signed_with_another_key() {
+        let ours = antispam(AntispamConfig::default());
+        let theirs = Antispam::new(AntispamConfig {
+            secret: Some("another secret".to_string()),
+            ..AntispamConfig::default()
+        });
+        let (token, nonce) = signed(&theirs, ours.issue());
+        assert_eq!(ours.verify(&token, &nonce), Err(Rejection::Forged));
+    }
+
+    #[test]
+    fn wrong_difficulty() {
+        let antispam = antispam(AntispamConfig::default());
+        let mut challenge = antispam.issue();
+        // Lowering the difficulty breaks the signature
+        challenge.difficulty = 1;
+        let nonce = challenge.solve().to_string();
+        assert_eq!(antispam.verify(&challenge.to_token(), &nonce), Err(Rejection::Forged));
+    }
+
+    #[test]
+    fn difficulty_comes_from_the_config() {
+        for difficulty in [1, 12, 20] {
+            let antispam = Antispam::new(AntispamConfig {
+                difficulty,
+                ..AntispamConfig::default()
+            });
+            assert_eq!(antispam.issue().difficulty, difficulty);
+        }
+    }
+
+    #[test]
+    fn expired_challenge() {
+        let antispam = antispam(AntispamConfig::default());
+        let challenge = Challenge {
+            expires: Utc::now().timestamp() - 1,
+            ..antispam.issue()
+        };
+        let (token, nonce) = signed(&antispam, challenge);
+        assert_eq!(antispam.verify(&token, &nonce), Err(Rejection::Expired));
+    }
+
+    #[test]
+    fn replayed_solution() {
+        let antispam = antispam(AntispamConfig::default());
+        let challenge = antispam.issue();
+        let (token, nonce) = (challenge.to_token(), challenge.solve().to_string());
+        assert_eq!(antispam.verify(&token, &nonce), Ok(()));
+        assert_eq!(antispam.verify(&token, &nonce), Err(Rejection::Replayed));
+    }
+
+    #[test]
+    fn unsolved_nonce() {
+        let antispam = antispam(AntispamConfig::default());
+        let challenge = antispam.issue();
+        let wrong = (0..).find(|n| !challenge.is_solved_by(*n)).unwrap();
+        let token = challenge.to_token();
+        assert_eq!(antispam.verify(&token, &wrong.to_string()), Err(Rejection::Unsolved));
+        assert_eq!(antispam.verify(&token, "not-a-nonce"), Err(Rejection::Unsolved));
+        // A failed attempt does not use the challenge up
+        assert_eq!(antispam.verify(&token, &challenge.solve().to_string()), Ok(()));
+    }
+
+    #[test]
+    fn rate_limit_per_ip_and_window() {
+        let antispam = antispam(AntispamConfig {
+            max_submissions: 2,
+            window: Duration::from_millis(200),
+            ..AntispamConfig::default()
+        });
+        let first = Some(IpAddr::from([192, 0, 2, 1]));
+        let second = Some(IpAddr::from([192, 0, 2, 2]));
+
+        assert_eq!(antispam.check_rate(first), Ok(()));
+        assert_eq!(antispam.check_rate(first), Ok(()));
+        assert_eq!(antispam.check_rate(first), Err(Rejection::RateLimited));
+        // Each address has its own count
+        assert_eq!(antispam.check_rate(second), Ok(()));
+
+        sleep(Duration::from_millis(250));
+        assert_eq!(antispam.check_rate(first), Ok(()));
+    }
+
+    #[test]
+    fn unknown_addresses_share_a_bucket() {
+        let antispam = antispam(AntispamConfig {
+            max_submissions: 1,
+            ..AntispamConfig::default()
+        });
+        assert_eq!(antispam.check_rate(None), Ok(()));
+        assert_eq!(antispam.check_rate(None), Err(Rejection::RateLimited));
+    }
+}
@@ -0,0 +1,449 @@
+//! Admin authentication: argon2 password check, in-memory sessions behind an
+//! HttpOnly cookie, per-session CSRF tokens and lockout after failed logins.
+//! The login form itself is covered by a double-submit token: a cookie set
+//! with the form, echoed in a hidden field.
+//!
+//! There is a single admin account, configured through the environment.
+//! Sessions live in memory, so a restart logs the admin out.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use actix_web::cookie::{time, Cookie, SameSite};
+use actix_web::http::header::{self, HeaderValue};
+use actix_web::{web, HttpRequest};
+use argon2::password_hash::{rand_core::OsRng, SaltString};
+use argon2::{Argon2, PasswordHash, PasswordHasher, PasswordVerifier};
+
+use super::{client_ip, hex, random_bytes};
+use crate::admin::{AdminSession, AuthError, LOGIN_CSRF_COOKIE, SESSION_COOKIE};
+
+/// How long the login form's token stays valid.
+const LOGIN_CSRF_TTL: time::Duration = time::Duration::hours(1);
+
+#[derive(Clone, Debug)]
+pub struct AuthConfig {
+    pub username: String,
+    /// Argon2 PHC string (`$argon2id$v=19$...`), see `hash-password`. Without
+    /// it nobody can log in.
+    pub password_hash: Option<String>,
+    /// Idle time after which a session ends.
+    pub session_ttl: Duration,
+    /// Failed logins allowed from one address before it is locked out, at
+    /// least 1.
+    pub max_failures: u32,
+    pub lockout: Duration,
+    /// Adds `Secure` to the cookie. Browsers accept it on `http://localhost` too.
+    pub secure_cookie: bool,
+    /// See [`client_ip`].
+    pub trust_proxy: bool,
+}
+
+impl Default for AuthConfig {
+    fn default() -> Self {
+        AuthConfig {
+            username: "admin".to_string(),
+            password_hash: None,
+            session_ttl: Duration::from_secs(8 * 3600),
+            max_failures: 5,
+            lockout: Duration::from_secs(900),
+            secure_cookie: true,
+            trust_proxy: false,
+        }
+    }
+}
+
+#[derive(Debug)]
+pub struct SetupError(String);
+
+impl fmt::Display for SetupError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid ADMIN_PASSWORD_HASH: {}", self.0)
+    }
+}
+
+impl std::error::Error for SetupError {}
+
+/// Argon2id hash of `password` in PHC format, for `ADMIN_PASSWORD_HASH`.
+pub fn hash_password(password: &str) -> Result<String, String> {
+    let salt = SaltString::generate(&mut OsRng);
+    Argon2::default()
+        .hash_password(password.as_bytes(), &salt)
+        .map(|hash| hash.to_string())
+        .map_err(|e| e.to_string())
+}
+
+fn verify_password(hash: &str, password: &str) -> bool {
+    PasswordHash::new(hash)
+        .and_then(|hash| Argon2::default().verify_password(password.as_bytes(), &hash))
+        .is_ok()
+}
+
+/// Compares without stopping at the first difference.
+pub(super) fn constant_time_eq(a: &str, b: &str) -> bool {
+    a.len() == b.len()
+        && a.bytes()
+            .zip(b.bytes())
+            .fold(0, |acc, (x, y)| acc | (x ^ y))
+            == 0
+}
+
+struct Session {
+    username: String,
+    csrf: String,
+    expires: Instant,
+}
+
+#[derive(Default)]
+struct Failures {
+    count: u32,
+    last: Option<Instant>,
+    locked_until: Option<Instant>,
+}
+
+/// Shared by the workers through `web::Data`.
+pub struct Auth {
+    config: AuthConfig,
+    /// Checked when the username is wrong or no hash is configured, so every
+    /// attempt costs the same time.
+    decoy_hash: String,
+    sessions: Mutex<HashMap<String, Session>>,
+    failures: Mutex<HashMap<IpAddr, Failures>>,
+}
+
+impl Auth {
+    pub fn new(config: AuthConfig) -> Result<Self, SetupError> {
+        if let Some(hash) = &config.password_hash {
+            PasswordHash::new(hash).map_err(|e| SetupError(e.to_string()))?;
+        }
+        Ok(Auth {
+            decoy_hash: hash_password(&hex(&random_bytes())).map_err(SetupError)?,
+            config,
+            sessions: Mutex::new(HashMap::new()),
+            failures: Mutex::new(HashMap::new()),
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.password_hash.is_some()
+    }
+
+    pub fn client_ip(&self, req: &HttpRequest) -> Option<IpAddr> {
+        client_ip(req, self.config.trust_proxy)
+    }
+
+    fn check_lockout(&self, ip: IpAddr) -> Result<(), AuthError> {
+        let failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        match failures.get(&ip).and_then(|f| f.locked_until) {
+            Some(until) if until > Instant::now() => Err(AuthError::LockedOut {
+                retry_after: (until - Instant::now()).as_secs().max(1),
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    fn record_failure(&self, ip: IpAddr) {
+        let now = Instant::now();
+        let mut failures = self.failures.lock().unwrap_or_else(|e| e.into_inner());
+        failures.retain(|_, f| f.last.is_some_and(|last| now - last < self.config.lockout));
+        let entry = failures.entry(ip).or_default();
+        entry.count += 1;
+        entry.last = Some(now);
+        if entry.count >= self.config.max_failures {
+            tracing::warn!(%ip, failures = entry.count, "admin: locking out after failed logins");
+            entry.count = 0;
+            entry.locked_until = Some(now + self.config.lockout);
+        }
+    }
+
+    /// Checks the credentials and opens a session, returning its token.
+    pub async fn login(
+        &self,
+        ip: Option<IpAddr>,
+        username: &str,
+        password: &str,
+    ) -> Result<String, AuthError> {
+        // Requests without a known address share one bucket
+        let ip = ip.unwrap_or(IpAddr::from([0, 0, 0, 0]));
+        self.check_lockout(ip)?;
+
+        let user_matches = constant_time_eq(username, &self.config.username);
+        let hash = match (&self.config.password_hash, user_matches) {
+            (Some(hash), true) => hash.clone(),
+            _ => self.decoy_hash.clone(),
+        };
+        let password = password.to_string();
+        // Argon2 is deliberately slow; keep it off the async workers
+        let password_ok = web::block(move || verify_password(&hash, &password))
+            .await
+            .map_err(|e| AuthError::Server(e.to_string()))?;
+
+        if !(password_ok && user_matches && self.is_enabled()) {
+            self.record_failure(ip);
+            return Err(AuthError::InvalidCredentials);
+        }
+        self.failures
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(&ip);
+
+        let token = hex(&random_bytes());
+        let now = Instant::now();
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        sessions.retain(|_, session| session.expires > now);
+        sessions.insert(
+            token.clone(),
+            Session {
+                username: self.config.username.clone(),
+                csrf: hex(&random_bytes()),
+                expires: now + self.config.session_ttl,
+            },
+        );
+        Ok(token)
+    }
+
+    /// Looks up a session and extends it.
+    pub fn session(&self, token: &str) -> Option<AdminSession> {
+        let now = Instant::now();
+        let mut sessions = self.sessions.lock().unwrap_or_else(|e| e.into_inner());
+        let session = sessions.get_mut(token).filter(|s| s.expires > now)?;
+        session.expires = now + self.config.session_ttl;
+        Some(AdminSession {
+            username: session.username.clone(),
+            csrf: session.csrf.clone(),
+        })
+    }
+
+    pub fn logout(&self, token: &str) {
+        self.sessions
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .remove(token);
+    }
+
+    pub fn cookie(&self, token: &str) -> Cookie<'static> {
+        Cookie::build(SESSION_COOKIE, token.to_string())
+            .path("/")
+            .http_only(true)
+            .secure(self.config.secure_cookie)
+            .same_site(SameSite::Strict)
+            .max_age(time::Duration::seconds(self.config.session_ttl.as_secs() as i64))
+            .finish()
+    }
+
+    pub fn removal_cookie(&self) -> Cookie<'static> {
+        let mut cookie = self.cookie("");
+        cookie.make_removal();
+        cookie
+    }
+
+    /// Carries the login form's CSRF token until the login succeeds.
+    pub fn login_csrf_cookie(&self, token: &str) -> Cookie<'static> {
+        Cookie::build(LOGIN_CSRF_COOKIE, token.to_string())
+            .path("/")
+            .http_only(true)
+            .secure(self.config.secure_cookie)
+            .same_site(SameSite::Strict)
+            .max_age(LOGIN_CSRF_TTL)
+            .finish()
+    }
+
+    pub fn login_csrf_removal_cookie(&self) -> Cookie<'static> {
+        let mut cookie = self.login_csrf_cookie("");
+        cookie.make_removal();
+        cookie
+    }
+}
+
+// --- Server function helpers ---
+
+/// The request being handled and the [`Auth`] state.
+pub async fn request() -> Result<(HttpRequest, web::Data<Auth>), AuthError> {
+    let req: HttpRequest = leptos_actix::extract()
+        .await
+        .map_err(|e| AuthError::Server(e.to_string()))?;
+    let auth = req
+        .app_data::<web::Data<Auth>>()
+        .cloned()
+        .ok_or_else(|| AuthError::Server("admin is not configured".to_string()))?;
+    Ok((req, auth))
+}
+
+/// The session of the current request, if any.
+pub async fn current() -> Result<Option<AdminSession>, AuthError> {
+    let (req, auth) = request().await?;
+    Ok(req
+        .cookie(SESSION_COOKIE)
+        .and_then(|cookie| auth.session(cookie.value())))
+}
+
+/// For mutating server functions: requires a session and its CSRF token.
+pub async fn require_admin(csrf: &str) -> Result<AdminSession, AuthError> {
+    let session = current().await?.ok_or(AuthError::Unauthorized)?;
+    if !constant_time_eq(csrf, &session.csrf) {
+        return Err(AuthError::Csrf);
+    }
+    Ok(session)
+}
+
+/// The token for the login form, reusing the one in the request's cookie so
+/// several open login pages all stay valid. Sets the cookie either way to
+/// renew it.
+pub async fn login_csrf() -> Result<String, AuthError> {
+    let (req, auth) = request().await?;
+    let token = req
+        .cookie(LOGIN_CSRF_COOKIE)
+        .map(|cookie| cookie.value().to_string())
+        .filter(|token| !token.is_empty())
+        .unwrap_or_else(|| hex(&random_bytes()));
+    set_cookie(auth.login_csrf_cookie(&token));
+    Ok(token)
+}
+
+/// For the login itself: the form's token must match its cookie. A
+/// cross-site form cannot read the cookie, nor send it under `SameSite`.
+pub async fn require_login_csrf(csrf: &str) -> Result<(), AuthError> {
+    let (req, _) = request().await?;
+    check_login_csrf(&req, csrf)
+}
+
+fn check_login_csrf(req: &HttpRequest, csrf: &str) -> Result<(), AuthError> {
+    match req.cookie(LOGIN_CSRF_COOKIE) {
+        Some(cookie) if !csrf.is_empty() && constant_time_eq(csrf, cookie.value()) => Ok(()),
+        _ => Err(AuthError::Csrf),
+    }
+}
+
+/// Adds a `Set-Cookie` header to the server function response.
+pub fn set_cookie(cookie: Cookie<'static>) {
+    if let (Some(response), Ok(value)) = (
+        leptos::prelude::use_context::<leptos_actix::ResponseOptions>(),
+        HeaderValue::from_str(&cookie.to_string()),
+    ) {
+        response.append_header(header::SET_COOKIE, value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::thread::sleep;
+
+    use actix_web::test::TestRequest;
+    use argon2::{Algorithm, Params, Version};
+
+    use super::*;
+
+    const PASSWORD: &str = "senha-de-teste";
+    const IP: Option<IpAddr> = Some(IpAddr::V4(std::net::Ipv4Addr::new(192, 0, 2, 1)));
+
+    /// A hash of [`PASSWORD`] with the lowest cost argon2 allows, so the
+    /// tests stay fast.
+    fn cheap_hash() -> String {
+        let params = Params::new(8, 1, 1, None).unwrap();
+        Argon2::new(Algorithm::Argon2id, Version::V0x13, params)
+            .hash_password(PASSWORD.as_bytes(), &SaltString::generate(&mut OsRng))
+            .unwrap()
+            .to_string()
+    }
+
+    fn auth(config: AuthConfig) -> Auth {
+        Auth::new(AuthConfig {
+            password_hash: Some(cheap_hash()),
+            ..config
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn compares_in_constant_time() {
+        assert!(constant_time_eq("", ""));
+        assert!(constant_time_eq("token", "token"));
+        assert!(!constant_time_eq("token", "tokem"));
+        assert!(!constant_time_eq("token", "token2"));
+        assert!(!constant_time_eq("", "token"));
+    }
+
+    #[actix_web::test]
+    async fn logs_in_with_the_right_password() {
+        let auth = auth(AuthConfig::default());
+        let token = auth.login(IP, "admin", PASSWORD).await.unwrap();
+        assert_eq!(auth.session(&token).map(|s| s.username), Some("admin".to_string()));
+        auth.logout(&token);
+        assert!(auth.session(&token).is_none());
+    }
+
+    #[actix_web::test]
+    async fn locks_out_after_max_failures_until_the_lockout_ends() {
+        let auth = auth(AuthConfig {
+            max_failures: 2,
+            lockout: Duration::from_millis(300),
+            ..AuthConfig::default()
+        });
+        assert_eq!(auth.login(IP, "admin", "errada").await, Err(AuthError::InvalidCredentials));
+        // One failure below the limit does not lock
+        assert!(auth.login(IP, "admin", PASSWORD).await.is_ok());
+
+        for _ in 0..2 {
+            assert_eq!(auth.login(IP, "admin", "errada").await, Err(AuthError::InvalidCredentials));
+        }
+        // Even the right password is refused while locked out
+        assert!(matches!(
+            auth.login(IP, "admin", PASSWORD).await,
+            Err(AuthError::LockedOut { retry_after: 1 })
+        ));
+        // Other addresses are not affected
+        let other = Some(IpAddr::from([192, 0, 2, 2]));
+        assert!(auth.login(other, "admin", PASSWORD).await.is_ok());
+
+        sleep(Duration::from_millis(350));
+        assert!(auth.login(IP, "admin", PASSWORD).await.is_ok());
+    }
+
+    #[actix_web::test]
+    async fn a_single_failure_locks_out_with_max_failures_1() {
+        let auth = auth(AuthConfig {
+            max_failures: 1,
+            ..AuthConfig::default()
+        });
+        assert_eq!(auth.login(IP, "admin", "errada").await, Err(AuthError::InvalidCredentials));
+        assert!(matches!(auth.login(IP, "admin", PASSWORD).await, Err(AuthError::LockedOut { .. })));
+    }
+
+    #[actix_web::test]
+    async fn an_unknown_user_is_checked_against_the_decoy() {
+        let auth = auth(AuthConfig {
+            max_failures: 2,
+            ..AuthConfig::default()
+        });
+        // The right password for another user is still wrong, and counts
+        for _ in 0..2 {
+            assert_eq!(auth.login(IP, "root", PASSWORD).await, Err(AuthError::InvalidCredentials));
+        }
+        assert!(matches!(auth.login(IP, "admin", PASSWORD).await, Err(AuthError::LockedOut { .. })));
+
+        // Without a hash nobody gets in, whatever they send
+        let disabled = Auth::new(AuthConfig::default()).unwrap();
+        assert!(!disabled.is_enabled());
+        assert_eq!(disabled.login(IP, "admin", "").await, Err(AuthError::InvalidCredentials));
+    }
+
+    #[test]
+    fn the_login_form_token_must_match_its_cookie() {
+        let with_cookie = |value: &str| {
+            TestRequest::default()
+                .cookie(Cookie::new(LOGIN_CSRF_COOKIE, value.to_string()))
+                .to_http_request()
+        };
+        assert_eq!(check_login_csrf(&with_cookie("abc123"), "abc123"), Ok(()));
+        assert_eq!(check_login_csrf(&with_cookie("abc123"), "abc124"), Err(AuthError::Csrf));
+        // An empty token never matches, not even an empty cookie
+        assert_eq!(check_login_csrf(&with_cookie(""), ""), Err(AuthError::Csrf));
+        assert_eq!(check_login_csrf(&with_cookie("abc123"), ""), Err(AuthError::Csrf));
+        // A form posted from another site comes without the cookie
+        let without_cookie = TestRequest::default().to_http_request();
+        assert_eq!(check_login_csrf(&without_cookie, "abc123"), Err(AuthError::Csrf));
+    }
+}
@@ -0,0 +1,122 @@
+//! HTTP caching. Fingerprinted assets ([`crate::assets`]) and the hashed
+//! `pkg` files never change under the same URL, so browsers keep them for a
+//! year; pages are revalidated on every visit against an `ETag`.
+
+use actix_web::body::{self, BoxBody, EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{self, HeaderValue};
+use actix_web::http::{Method, StatusCode, Uri};
+use actix_web::middleware::Next;
+use actix_web::web;
+use leptos::config::LeptosOptions;
+use sha2::{Digest, Sha256};
+
+use super::hex;
+use crate::assets;
+
+const IMMUTABLE: &str = "public, max-age=31536000, immutable";
+/// May be stored, but must be revalidated before each use.
+const REVALIDATE: &str = "no-cache";
+
+/// With `hash-files`, cargo-leptos puts a hash in every `pkg` file name.
+fn is_hashed_pkg(req: &ServiceRequest) -> bool {
+    req.path().starts_with("/pkg/")
+        && req
+            .app_data::<web::Data<LeptosOptions>>()
+            .is_some_and(|options| options.hash_files)
+}
+
+/// The CSP nonce of a page. It changes on every request, so it is left out
+/// of the tag.
+fn nonce(res: &ServiceResponse<impl MessageBody>) -> Option<String> {
+    [header::CONTENT_SECURITY_POLICY, header::CONTENT_SECURITY_POLICY_REPORT_ONLY]
+        .iter()
+        .filter_map(|name| res.headers().get(name)?.to_str().ok())
+        .find_map(|policy| {
+            let rest = policy.split("'nonce-").nth(1)?;
+            rest.split('\'').next().map(str::to_string)
+        })
+}
+
+fn is_html(res: &ServiceResponse<impl MessageBody>) -> bool {
+    res.headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/html"))
+}
+
+/// Serves fingerprinted asset URLs from the real file and sets the cache
+/// policy of static files and pages. Pages that set their own
+/// `Cache-Control` (the admin area) are left alone. Register with
+/// `middleware::from_fn(caching::headers)`, outside `compression::serve`,
+/// which has to see the real path.
+pub async fn headers(
+    mut req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody, BoxBody>>, actix_web::Error> {
+    let mut immutable = is_hashed_pkg(&req);
+    if let Some(original) = assets::original(req.path()) {
+        // Same file under its real name; the hash only changes the URL
+        let uri = Uri::from_static(original);
+        req.match_info_mut().get_mut().update(&uri);
+        req.head_mut().uri = uri;
+        immutable = true;
+    }
+    let is_static = immutable || req.path().starts_with("/pkg/") || req.path().starts_with("/assets/");
+    let is_get = matches!(*req.method(), Method::GET | Method::HEAD);
+    let if_none_match = req.headers().get(header::IF_NONE_MATCH).cloned();
+
+    let mut res = next.call(req).await?;
+    if is_static {
+        if res.status().is_success() || res.status() == StatusCode::NOT_MODIFIED {
+            let policy = if immutable { IMMUTABLE } else { REVALIDATE };
+            res.headers_mut()
+                .insert(header::CACHE_CONTROL, HeaderValue::from_static(policy));
+        }
+        return Ok(res.map_into_left_body());
+    }
+    let is_page = is_get
+        && res.status() == StatusCode::OK
+        && is_html(&res)
+        && !res.headers().contains_key(header::CACHE_CONTROL);
+    if !is_page {
+        return Ok(res.map_into_left_body());
+    }
+
+    // The tag is a hash of the page, so the whole body is needed first
+    let nonce = nonce(&res);
+    let (req, res) = res.into_parts();
+    let (res, page) = res.into_parts();
+    let page = body::to_bytes(page).await.map_err(|e| {
+        let e: Box<dyn std::error::Error> = e.into();
+        actix_web::error::ErrorInternalServerError(e.to_string())
+    })?;
+    let mut hasher = Sha256::new();
+    match (&nonce, std::str::from_utf8(&page)) {
+        (Some(nonce), Ok(text)) => text.split(nonce.as_str()).for_each(|part| hasher.update(part)),
+        _ => hasher.update(&page),
+    }
+    // Weak: `Compress` sends the same tag for every encoding
+    let etag = format!("W/\"{}\"", hex(&hasher.finalize()[..12]));
+    let fresh = if_none_match
+        .as_ref()
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.split(',').any(|tag| tag.trim() == "*" || tag.trim() == etag));
+
+    let mut res = if fresh {
+        let mut res = res.set_body(web::Bytes::new());
+        *res.status_mut() = StatusCode::NOT_MODIFIED;
+        // The cached copy keeps the policy that matches its nonce
+        res.headers_mut().remove(header::CONTENT_SECURITY_POLICY);
+        res.headers_mut().remove(header::CONTENT_SECURITY_POLICY_REPORT_ONLY);
+        res
+    } else {
+        res.set_body(page)
+    };
+    let headers = res.headers_mut();
+    headers.insert(header::CACHE_CONTROL, HeaderValue::from_static(REVALIDATE));
+    if let Ok(etag) = HeaderValue::from_str(&etag) {
+        headers.insert(header::ETAG, etag);
+    }
+    Ok(ServiceResponse::new(req, res.map_into_boxed_body()).map_into_right_body())
+}
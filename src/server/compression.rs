@@ -0,0 +1,231 @@
+//! Response compression. Pages and other dynamic responses go through
+//! `middleware::Compress`; the static files under `/pkg` and `/assets` get
+//! `.br`/`.gz` siblings made ahead of time, served in their place when the
+//! client accepts them.
+
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Component, Path, PathBuf};
+
+use actix_files::{file_extension_to_mime, NamedFile};
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{self, ContentEncoding, HeaderMap, HeaderValue};
+use actix_web::http::Method;
+use actix_web::middleware::Next;
+use actix_web::web;
+use leptos::config::LeptosOptions;
+
+/// Text formats worth compressing. Images, video and fonts like woff2 are
+/// compressed already.
+const COMPRESSIBLE: [&str; 13] = [
+    "css", "html", "ico", "js", "json", "map", "mjs", "otf", "svg", "ttf", "txt", "wasm", "xml",
+];
+/// Brotli's best: slow, but every file is compressed once.
+const BROTLI_QUALITY: u32 = 11;
+const BROTLI_WINDOW: u32 = 22;
+
+/// Precompressed variants, in order of preference.
+#[derive(Clone, Copy)]
+enum Encoding {
+    Brotli,
+    Gzip,
+}
+
+impl Encoding {
+    const ALL: [Encoding; 2] = [Encoding::Brotli, Encoding::Gzip];
+
+    fn extension(self) -> &'static str {
+        match self {
+            Encoding::Brotli => "br",
+            Encoding::Gzip => "gz",
+        }
+    }
+
+    fn token(self) -> &'static str {
+        match self {
+            Encoding::Brotli => "br",
+            Encoding::Gzip => "gzip",
+        }
+    }
+
+    fn header(self) -> ContentEncoding {
+        match self {
+            Encoding::Brotli => ContentEncoding::Brotli,
+            Encoding::Gzip => ContentEncoding::Gzip,
+        }
+    }
+
+    fn compress(self, data: &[u8]) -> io::Result<Vec<u8>> {
+        match self {
+            Encoding::Brotli => {
+                let mut writer =
+                    brotli::CompressorWriter::new(Vec::new(), 4096, BROTLI_QUALITY, BROTLI_WINDOW);
+                writer.write_all(data)?;
+                Ok(writer.into_inner())
+            }
+            Encoding::Gzip => {
+                let mut encoder =
+                    flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::best());
+                encoder.write_all(data)?;
+                encoder.finish()
+            }
+        }
+    }
+}
+
+fn is_compressible(path: &Path) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| COMPRESSIBLE.contains(&e))
+}
+
+/// `app.js` -> `app.js.br`.
+fn sibling(path: &Path, encoding: Encoding) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".");
+    name.push(encoding.extension());
+    PathBuf::from(name)
+}
+
+/// Whether `sibling` exists and was made after `source` last changed.
+fn is_fresh(source: &Path, sibling: &Path) -> bool {
+    let modified = |path: &Path| fs::metadata(path).and_then(|m| m.modified());
+    match (modified(source), modified(sibling)) {
+        (Ok(source), Ok(sibling)) => sibling >= source,
+        _ => false,
+    }
+}
+
+/// Writes the missing or stale `.br`/`.gz` siblings of the compressible files
+/// under `dir`. Returns how many were written.
+pub fn precompress(dir: &Path) -> io::Result<usize> {
+    let mut written = 0;
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            written += precompress(&path)?;
+            continue;
+        }
+        if !is_compressible(&path) {
+            continue;
+        }
+
+        let mut source = None;
+        for encoding in Encoding::ALL {
+            let target = sibling(&path, encoding);
+            if is_fresh(&path, &target) {
+                continue;
+            }
+            let data = match &source {
+                Some(data) => data,
+                None => source.insert(fs::read(&path)?),
+            };
+            let compressed = encoding.compress(data)?;
+            // Tiny files can grow; the original is served then
+            if compressed.len() >= data.len() {
+                let _ = fs::remove_file(&target);
+                continue;
+            }
+            let tmp = target.with_extension("tmp");
+            fs::write(&tmp, &compressed)?;
+            fs::rename(&tmp, &target)?;
+            written += 1;
+        }
+    }
+    Ok(written)
+}
+
+/// Whether `Accept-Encoding` lists `token` without `q=0`.
+fn accepts(headers: &HeaderMap, token: &str) -> bool {
+    headers
+        .get_all(header::ACCEPT_ENCODING)
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .any(|item| {
+            let mut parts = item.split(';');
+            let name = parts.next().unwrap_or_default().trim();
+            let refused = parts.any(|param| {
+                param
+                    .trim()
+                    .strip_prefix("q=")
+                    .and_then(|q| q.trim().parse::<f32>().ok())
+                    .is_some_and(|q| q <= 0.0)
+            });
+            name.eq_ignore_ascii_case(token) && !refused
+        })
+}
+
+/// The file behind a `/pkg` or `/assets` request, laid out as in [`super::site`].
+/// Anything unusual in the path is left to `actix_files`.
+fn static_file(req: &ServiceRequest) -> Option<PathBuf> {
+    if !matches!(*req.method(), Method::GET | Method::HEAD) {
+        return None;
+    }
+    let options = req.app_data::<web::Data<LeptosOptions>>()?;
+    let root = Path::new(&*options.site_root);
+    let path = req.path();
+    let (dir, rest) = if let Some(rest) = path.strip_prefix("/pkg/") {
+        (root.join("pkg"), rest)
+    } else {
+        (root.to_path_buf(), path.strip_prefix("/assets/")?)
+    };
+    let rest = Path::new(rest);
+    let plain = !rest.as_os_str().is_empty()
+        && !path.contains(['%', '\\'])
+        && rest.components().all(|c| match c {
+            Component::Normal(name) => !name.to_string_lossy().starts_with('.'),
+            _ => false,
+        });
+    plain.then(|| dir.join(rest))
+}
+
+/// Content types already compressed, which `Compress` must leave alone.
+fn is_media(content_type: &str) -> bool {
+    (content_type.starts_with("image/") && !content_type.starts_with("image/svg"))
+        || content_type.starts_with("video/")
+        || content_type.starts_with("audio/")
+        || content_type.starts_with("font/woff")
+}
+
+/// Serves precompressed siblings of static files, and marks every response
+/// whose body depends on `Accept-Encoding` with `Vary`. Register with
+/// `middleware::from_fn(compression::serve)`, inside `middleware::Compress`.
+pub async fn serve(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, actix_web::Error> {
+    let vary = HeaderValue::from_static("Accept-Encoding");
+
+    if let Some(file) = static_file(&req).filter(|file| is_compressible(file)) {
+        let encoding = Encoding::ALL.into_iter().find(|encoding| {
+            let target = sibling(&file, *encoding);
+            accepts(req.headers(), encoding.token()) && is_fresh(&file, &target)
+        });
+        if let Some(encoding) = encoding {
+            let extension = file.extension().and_then(|e| e.to_str()).unwrap_or_default();
+            let named = NamedFile::open(sibling(&file, encoding))?
+                .set_content_type(file_extension_to_mime(extension))
+                .set_content_encoding(encoding.header())
+                .disable_content_disposition();
+            let mut response = named.into_response(req.request());
+            response.headers_mut().append(header::VARY, vary);
+            return Ok(req.into_response(response).map_into_right_body());
+        }
+    }
+
+    let mut res = next.call(req).await?;
+    let headers = res.headers_mut();
+    if !headers.contains_key(header::CONTENT_ENCODING) {
+        let content_type = headers
+            .get(header::CONTENT_TYPE)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or_default();
+        if is_media(content_type) {
+            headers.insert(header::CONTENT_ENCODING, HeaderValue::from_static("identity"));
+        } else {
+            headers.append(header::VARY, vary);
+        }
+    }
+    Ok(res.map_into_left_body())
+}
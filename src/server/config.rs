@@ -0,0 +1,557 @@
+//! Startup configuration. Each setting comes from, the first found winning:
+//! command-line flags, environment variables, the `.env` file, the Leptos
+//! config file given with `--config` (such as `Cargo.toml`) and the defaults.
+//!
+//! Every setting is parsed here, once, into the typed config of the module
+//! that uses it; the modules never read the environment themselves. A value
+//! that does not parse or is out of range stops the server at startup with
+//! the variable's name instead of falling back to a default without a word.
+//! Secrets (`SMTP_URL`, `ADMIN_PASSWORD_HASH`, `ANTISPAM_SECRET`,
+//! `METRICS_TOKEN`) have no flag, so they never show up in the process list.
+
+use std::fmt;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::time::Duration;
+
+use clap::{Parser, Subcommand};
+use leptos::config::{get_configuration, Env, LeptosOptions};
+
+use super::feeds::FeedMode;
+use super::security::CspMode;
+use super::telemetry::{AccessLog, LogFormat};
+use super::{antispam, auth, contact, feeds, metrics, security, sitemap, telemetry, SiteUrl};
+
+#[derive(Parser, Debug)]
+#[command(version, about = "Serves nicolasalmino.com")]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+    /// Address to listen on [env: LEPTOS_SITE_ADDR]
+    #[arg(long, global = true)]
+    pub bind: Option<SocketAddr>,
+    /// Worker threads [env: WORKERS, default: one per CPU]
+    #[arg(long, global = true)]
+    pub workers: Option<usize>,
+    /// Public base URL, for absolute links [env: SITE_URL]
+    #[arg(long, global = true)]
+    pub site_url: Option<String>,
+    /// Seconds given to requests in flight on SIGTERM [env: SHUTDOWN_TIMEOUT, default: 30]
+    #[arg(long, global = true)]
+    pub shutdown_timeout: Option<u64>,
+    /// Markdown and TOML content [env: CONTENT_DIR, default: content]
+    #[arg(long, global = true)]
+    pub content_dir: Option<PathBuf>,
+    /// Reload content when its files change [env: CONTENT_WATCH]
+    #[arg(long, global = true)]
+    pub watch_content: Option<bool>,
+    /// Write `.br`/`.gz` copies of the static files at startup [env: PRECOMPRESS]
+    #[arg(long, global = true)]
+    pub precompress: Option<bool>,
+    /// Cache of the generated preview images [env: OG_CACHE_DIR, default: target/og-cache]
+    #[arg(long, global = true)]
+    pub og_cache_dir: Option<PathBuf>,
+    /// Take client addresses from `Forwarded` / `X-Forwarded-For` [env: TRUST_PROXY]
+    #[arg(long, global = true)]
+    pub trust_proxy: Option<bool>,
+    /// Log filter, such as `info` or `warn,nicolasalmino_site=debug` [env: LOG_LEVEL]
+    #[arg(long, global = true)]
+    pub log_level: Option<String>,
+    /// `pretty` or `json` [env: LOG_FORMAT, default: pretty in development]
+    #[arg(long, global = true)]
+    pub log_format: Option<LogFormat>,
+    /// `off`, `common` or `combined` [env: ACCESS_LOG, default: combined]
+    #[arg(long, global = true)]
+    pub access_log: Option<AccessLog>,
+    /// Separate address serving only `/metrics` [env: METRICS_ADDR]
+    #[arg(long, global = true)]
+    pub metrics_addr: Option<SocketAddr>,
+    /// Log of contact form submissions [env: CONTACT_LOG, default: data/contact.jsonl]
+    #[arg(long, global = true)]
+    pub contact_log: Option<PathBuf>,
+    /// Recipient of contact messages [env: CONTACT_TO, default: the profile email]
+    #[arg(long, global = true)]
+    pub contact_to: Option<String>,
+    /// Sender of contact messages [env: CONTACT_FROM, default: the recipient]
+    #[arg(long, global = true)]
+    pub contact_from: Option<String>,
+    /// Leading zero bits of the contact form's proof of work, 1 to 32 [env: ANTISPAM_DIFFICULTY, default: 18]
+    #[arg(long, global = true)]
+    pub antispam_difficulty: Option<u32>,
+    /// Seconds a proof-of-work challenge stays valid [env: ANTISPAM_TTL, default: 600]
+    #[arg(long, global = true)]
+    pub antispam_ttl: Option<u64>,
+    /// Contact messages allowed per IP and window [env: ANTISPAM_MAX_SUBMISSIONS, default: 5]
+    #[arg(long, global = true)]
+    pub antispam_max_submissions: Option<usize>,
+    /// Seconds of the rate limit window [env: ANTISPAM_WINDOW, default: 600]
+    #[arg(long, global = true)]
+    pub antispam_window: Option<u64>,
+    /// Admin username [env: ADMIN_USER, default: admin]
+    #[arg(long, global = true)]
+    pub admin_user: Option<String>,
+    /// Seconds of inactivity before an admin session ends [env: ADMIN_SESSION_TTL, default: 28800]
+    #[arg(long, global = true)]
+    pub admin_session_ttl: Option<u64>,
+    /// Failed logins from one IP before a lockout, at least 1 [env: ADMIN_MAX_FAILURES, default: 5]
+    #[arg(long, global = true)]
+    pub admin_max_failures: Option<u32>,
+    /// Seconds an IP stays locked out [env: ADMIN_LOCKOUT, default: 900]
+    #[arg(long, global = true)]
+    pub admin_lockout: Option<u64>,
+    /// Mark the admin cookies `Secure` [env: ADMIN_COOKIE_SECURE, default: true]
+    #[arg(long, global = true)]
+    pub admin_cookie_secure: Option<bool>,
+    /// `full` or `summary` feed entries [env: FEED_MODE, default: full]
+    #[arg(long, global = true)]
+    pub feed_mode: Option<FeedMode>,
+    /// `Cache-Control` max-age of the feeds, in seconds [env: FEED_MAX_AGE, default: 3600]
+    #[arg(long, global = true)]
+    pub feed_max_age: Option<u32>,
+    /// Let crawlers index the site [env: ROBOTS_ALLOW_INDEXING, default: true]
+    #[arg(long, global = true)]
+    pub robots_allow_indexing: Option<bool>,
+    /// Comma separated paths robots.txt disallows [env: ROBOTS_DISALLOW, default: /api/,/admin]
+    #[arg(long, global = true, value_delimiter = ',')]
+    pub robots_disallow: Option<Vec<String>>,
+    /// `enforce`, `report-only` or `off` [env: CSP_MODE, default: enforce]
+    #[arg(long, global = true)]
+    pub csp_mode: Option<CspMode>,
+    /// Leptos config file, such as Cargo.toml [default: the LEPTOS_* variables]
+    #[arg(long, global = true)]
+    pub config: Option<String>,
+    /// Variables to load when the environment does not set them
+    #[arg(long, global = true, default_value = ".env")]
+    pub env_file: PathBuf,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Serve the site (the default)
+    Serve,
+    /// Read a password from stdin and print the value for ADMIN_PASSWORD_HASH
+    HashPassword,
+    /// Write the site as static files
+    Export {
+        /// Output directory
+        #[arg(default_value = super::export::DEFAULT_DIR)]
+        dir: PathBuf,
+    },
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    EnvFile(PathBuf, String),
+    Leptos(String),
+    Invalid {
+        name: &'static str,
+        value: String,
+        expected: String,
+    },
+}
+
+impl ConfigError {
+    fn invalid(name: &'static str, value: &str, expected: impl Into<String>) -> Self {
+        ConfigError::Invalid {
+            name,
+            value: value.to_string(),
+            expected: expected.into(),
+        }
+    }
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::EnvFile(path, e) => write!(f, "cannot read {}: {e}", path.display()),
+            ConfigError::Leptos(e) => write!(f, "Leptos config: {e}"),
+            ConfigError::Invalid {
+                name,
+                value,
+                expected,
+            } => write!(f, "invalid {name} `{value}`: expected {expected}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+/// Everything the server needs to start, resolved and checked.
+#[derive(Clone, Debug)]
+pub struct ServerConfig {
+    /// `site_addr` is the address to bind.
+    pub leptos_options: LeptosOptions,
+    pub dev: bool,
+    pub workers: usize,
+    pub shutdown_timeout: Duration,
+    pub site_url: SiteUrl,
+    pub content_dir: PathBuf,
+    pub watch_content: bool,
+    pub precompress: bool,
+    pub og_cache_dir: PathBuf,
+    pub contact: contact::ContactConfig,
+    pub antispam: antispam::AntispamConfig,
+    pub auth: auth::AuthConfig,
+    pub feeds: feeds::FeedConfig,
+    pub robots: sitemap::RobotsConfig,
+    pub security: security::SecurityConfig,
+    pub log: telemetry::LogConfig,
+    pub metrics: metrics::MetricsConfig,
+}
+
+impl ServerConfig {
+    pub fn load(cli: &Cli) -> Result<Self, ConfigError> {
+        load_env_file(cli)?;
+
+        let mut leptos_options = get_configuration(cli.config.as_deref())
+            .map_err(|e| ConfigError::Leptos(e.to_string()))?
+            .leptos_options;
+        if let Some(bind) = cli.bind {
+            leptos_options.site_addr = bind;
+        }
+        let dev = leptos_options.env == Env::DEV;
+
+        let workers = match setting(cli.workers, "WORKERS", "a number of threads")? {
+            Some(0) => return Err(ConfigError::invalid("WORKERS", "0", "at least 1")),
+            Some(workers) => workers,
+            None => std::thread::available_parallelism().map_or(1, |n| n.get()),
+        };
+
+        let site_url = SiteUrl::new(
+            text(cli.site_url.clone(), "SITE_URL")
+                .unwrap_or_else(|| format!("http://{}", leptos_options.site_addr)),
+        );
+        if !["http://", "https://"]
+            .iter()
+            .any(|scheme| site_url.as_str().starts_with(scheme))
+        {
+            return Err(ConfigError::invalid(
+                "SITE_URL",
+                site_url.as_str(),
+                "an http:// or https:// URL",
+            ));
+        }
+
+        let content_dir = setting(cli.content_dir.clone(), "CONTENT_DIR", "a directory")?
+            .unwrap_or_else(|| PathBuf::from("content"));
+        if !content_dir.is_dir() {
+            return Err(ConfigError::invalid(
+                "CONTENT_DIR",
+                &content_dir.to_string_lossy(),
+                "a directory",
+            ));
+        }
+
+        let trust_proxy = switch(cli.trust_proxy, "TRUST_PROXY")?.unwrap_or(false);
+
+        Ok(ServerConfig {
+            dev,
+            workers,
+            shutdown_timeout: seconds(cli.shutdown_timeout, "SHUTDOWN_TIMEOUT")?
+                .unwrap_or(Duration::from_secs(30)),
+            site_url,
+            content_dir,
+            // `cargo leptos watch` rewrites `pkg` all the time, so development
+            // watches the content and skips precompression
+            watch_content: switch(cli.watch_content, "CONTENT_WATCH")?.unwrap_or(dev),
+            precompress: switch(cli.precompress, "PRECOMPRESS")?.unwrap_or(!dev),
+            og_cache_dir: setting(cli.og_cache_dir.clone(), "OG_CACHE_DIR", "a directory")?
+                .unwrap_or_else(|| PathBuf::from("target/og-cache")),
+            contact: contact_config(cli)?,
+            antispam: antispam_config(cli, trust_proxy)?,
+            auth: auth_config(cli, trust_proxy)?,
+            feeds: feed_config(cli)?,
+            robots: robots_config(cli)?,
+            security: security_config(cli, dev, trust_proxy)?,
+            log: log_config(cli, dev, trust_proxy)?,
+            metrics: metrics_config(cli, leptos_options.site_addr)?,
+            leptos_options,
+        })
+    }
+
+    /// Root of the built site: `pkg` and the assets.
+    pub fn site_root(&self) -> PathBuf {
+        PathBuf::from(&*self.leptos_options.site_root)
+    }
+}
+
+/// A missing `.env` is fine unless it was asked for by name.
+fn load_env_file(cli: &Cli) -> Result<(), ConfigError> {
+    match dotenvy::from_path(&cli.env_file) {
+        Ok(()) => Ok(()),
+        Err(e) if e.not_found() && cli.env_file.as_os_str() == ".env" => Ok(()),
+        Err(e) => Err(ConfigError::EnvFile(cli.env_file.clone(), e.to_string())),
+    }
+}
+
+// --- Reading settings ---
+
+/// A variable's value, `None` when unset or blank.
+fn var(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|v| !v.trim().is_empty())
+}
+
+/// The flag if given, otherwise the variable parsed as `T`. `expected`
+/// describes the valid values for the error.
+fn setting<T: FromStr>(
+    flag: Option<T>,
+    name: &'static str,
+    expected: &str,
+) -> Result<Option<T>, ConfigError> {
+    if flag.is_some() {
+        return Ok(flag);
+    }
+    var(name)
+        .map(|value| {
+            value
+                .trim()
+                .parse()
+                .map_err(|_| ConfigError::invalid(name, &value, expected))
+        })
+        .transpose()
+}
+
+/// [`setting`] for a switch: `true`/`false`, `yes`/`no` or `1`/`0`.
+fn switch(flag: Option<bool>, name: &'static str) -> Result<Option<bool>, ConfigError> {
+    if flag.is_some() {
+        return Ok(flag);
+    }
+    var(name)
+        .map(|value| match value.trim().to_ascii_lowercase().as_str() {
+            "1" | "true" | "yes" => Ok(true),
+            "0" | "false" | "no" => Ok(false),
+            _ => Err(ConfigError::invalid(name, &value, "true or false")),
+        })
+        .transpose()
+}
+
+/// [`setting`] for a number of seconds.
+fn seconds(flag: Option<u64>, name: &'static str) -> Result<Option<Duration>, ConfigError> {
+    Ok(setting(flag, name, "a number of seconds")?.map(Duration::from_secs))
+}
+
+/// [`setting`] for free text, trimmed.
+fn text(flag: Option<String>, name: &'static str) -> Option<String> {
+    flag.or_else(|| var(name)).map(|value| value.trim().to_string())
+}
+
+/// Refuses zero for counts that must allow at least one.
+fn at_least_one<T: PartialEq + From<u8>>(name: &'static str, value: T) -> Result<T, ConfigError> {
+    if value == T::from(0) {
+        return Err(ConfigError::invalid(name, "0", "at least 1"));
+    }
+    Ok(value)
+}
+
+// --- Per module ---
+
+fn contact_config(cli: &Cli) -> Result<contact::ContactConfig, ConfigError> {
+    let defaults = contact::ContactConfig::default();
+    Ok(contact::ContactConfig {
+        log_path: setting(cli.contact_log.clone(), "CONTACT_LOG", "a file path")?
+            .unwrap_or(defaults.log_path),
+        smtp_url: var("SMTP_URL").map(|url| url.trim().to_string()),
+        to: text(cli.contact_to.clone(), "CONTACT_TO"),
+        from: text(cli.contact_from.clone(), "CONTACT_FROM"),
+    })
+}
+
+fn antispam_config(cli: &Cli, trust_proxy: bool) -> Result<antispam::AntispamConfig, ConfigError> {
+    let defaults = antispam::AntispamConfig::default();
+    let range = antispam::DIFFICULTY_RANGE;
+    let expected = format!("{} to {}", range.start(), range.end());
+    let difficulty = setting(cli.antispam_difficulty, "ANTISPAM_DIFFICULTY", &expected)?
+        .unwrap_or(defaults.difficulty);
+    if !range.contains(&difficulty) {
+        return Err(ConfigError::invalid(
+            "ANTISPAM_DIFFICULTY",
+            &difficulty.to_string(),
+            expected,
+        ));
+    }
+    let max_submissions = setting(
+        cli.antispam_max_submissions,
+        "ANTISPAM_MAX_SUBMISSIONS",
+        "a whole number",
+    )?;
+    Ok(antispam::AntispamConfig {
+        secret: var("ANTISPAM_SECRET"),
+        difficulty,
+        ttl: seconds(cli.antispam_ttl, "ANTISPAM_TTL")?.unwrap_or(defaults.ttl),
+        max_submissions: match max_submissions {
+            Some(max) => at_least_one("ANTISPAM_MAX_SUBMISSIONS", max)?,
+            None => defaults.max_submissions,
+        },
+        window: seconds(cli.antispam_window, "ANTISPAM_WINDOW")?.unwrap_or(defaults.window),
+        trust_proxy: switch(None, "ANTISPAM_TRUST_PROXY")?.unwrap_or(trust_proxy),
+    })
+}
+
+fn auth_config(cli: &Cli, trust_proxy: bool) -> Result<auth::AuthConfig, ConfigError> {
+    let defaults = auth::AuthConfig::default();
+    let max_failures = setting(cli.admin_max_failures, "ADMIN_MAX_FAILURES", "a whole number")?;
+    Ok(auth::AuthConfig {
+        username: text(cli.admin_user.clone(), "ADMIN_USER").unwrap_or(defaults.username),
+        password_hash: var("ADMIN_PASSWORD_HASH"),
+        session_ttl: seconds(cli.admin_session_ttl, "ADMIN_SESSION_TTL")?
+            .unwrap_or(defaults.session_ttl),
+        max_failures: match max_failures {
+            Some(max) => at_least_one("ADMIN_MAX_FAILURES", max)?,
+            None => defaults.max_failures,
+        },
+        lockout: seconds(cli.admin_lockout, "ADMIN_LOCKOUT")?.unwrap_or(defaults.lockout),
+        secure_cookie: switch(cli.admin_cookie_secure, "ADMIN_COOKIE_SECURE")?
+            .unwrap_or(defaults.secure_cookie),
+        trust_proxy: switch(None, "ADMIN_TRUST_PROXY")?.unwrap_or(trust_proxy),
+    })
+}
+
+fn feed_config(cli: &Cli) -> Result<feeds::FeedConfig, ConfigError> {
+    let defaults = feeds::FeedConfig::default();
+    Ok(feeds::FeedConfig {
+        mode: setting(cli.feed_mode, "FEED_MODE", FeedMode::EXPECTED)?.unwrap_or(defaults.mode),
+        max_age: setting(cli.feed_max_age, "FEED_MAX_AGE", "a number of seconds")?
+            .unwrap_or(defaults.max_age),
+        ..defaults
+    })
+}
+
+fn robots_config(cli: &Cli) -> Result<sitemap::RobotsConfig, ConfigError> {
+    let defaults = sitemap::RobotsConfig::default();
+    // Set but empty disallows nothing
+    let disallow = cli.robots_disallow.clone().or_else(|| {
+        std::env::var("ROBOTS_DISALLOW")
+            .ok()
+            .map(|paths| paths.split(',').map(str::to_string).collect())
+    });
+    Ok(sitemap::RobotsConfig {
+        allow_indexing: switch(cli.robots_allow_indexing, "ROBOTS_ALLOW_INDEXING")?
+            .unwrap_or(defaults.allow_indexing),
+        disallow: match disallow {
+            Some(paths) => paths
+                .iter()
+                .map(|path| path.trim())
+                .filter(|path| !path.is_empty())
+                .map(str::to_string)
+                .collect(),
+            None => defaults.disallow,
+        },
+    })
+}
+
+fn security_config(cli: &Cli, dev: bool, trust_proxy: bool) -> Result<security::SecurityConfig, ConfigError> {
+    let defaults = security::SecurityConfig::default();
+    // Set but empty leaves the header out
+    let header = |name: &str, default: String| {
+        std::env::var(name).map_or(default, |value| value.trim().to_string())
+    };
+    Ok(security::SecurityConfig {
+        csp: setting(cli.csp_mode, "CSP_MODE", CspMode::EXPECTED)?.unwrap_or(defaults.csp),
+        dev,
+        hsts: header("HSTS", defaults.hsts),
+        referrer_policy: header("REFERRER_POLICY", defaults.referrer_policy),
+        permissions_policy: header("PERMISSIONS_POLICY", defaults.permissions_policy),
+        trust_proxy,
+    })
+}
+
+fn log_config(cli: &Cli, dev: bool, trust_proxy: bool) -> Result<telemetry::LogConfig, ConfigError> {
+    let defaults = telemetry::LogConfig::default();
+    let config = telemetry::LogConfig {
+        format: setting(cli.log_format, "LOG_FORMAT", LogFormat::EXPECTED)?
+            .unwrap_or(if dev { LogFormat::Pretty } else { LogFormat::Json }),
+        filter: text(cli.log_level.clone(), "LOG_LEVEL")
+            .or_else(|| text(None, "RUST_LOG"))
+            .unwrap_or(defaults.filter),
+        access_log: setting(cli.access_log, "ACCESS_LOG", AccessLog::EXPECTED)?
+            .unwrap_or(defaults.access_log),
+        trust_proxy,
+    };
+    if let Err(e) = tracing_subscriber::EnvFilter::try_new(&config.filter) {
+        return Err(ConfigError::invalid(
+            "LOG_LEVEL",
+            &config.filter,
+            format!("log filter directives ({e})"),
+        ));
+    }
+    Ok(config)
+}
+
+fn metrics_config(cli: &Cli, site_addr: SocketAddr) -> Result<metrics::MetricsConfig, ConfigError> {
+    let addr = setting(cli.metrics_addr, "METRICS_ADDR", "an address such as 127.0.0.1:9100")?;
+    if addr == Some(site_addr) {
+        return Err(ConfigError::invalid(
+            "METRICS_ADDR",
+            &site_addr.to_string(),
+            "an address other than the site's",
+        ));
+    }
+    Ok(metrics::MetricsConfig {
+        addr,
+        token: var("METRICS_TOKEN").map(|token| token.trim().to_string()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Flags only, so the tests do not depend on the environment.
+    fn cli(args: &[&str]) -> Cli {
+        Cli::try_parse_from(std::iter::once("site").chain(args.iter().copied())).unwrap()
+    }
+
+    #[test]
+    fn typed_flags() {
+        let cli = cli(&["--csp-mode", "report-only", "--feed-mode", "SUMMARY", "--access-log", "common"]);
+        assert_eq!(cli.csp_mode, Some(CspMode::ReportOnly));
+        assert_eq!(cli.feed_mode, Some(FeedMode::Summary));
+        assert_eq!(cli.access_log, Some(AccessLog::Common));
+        for args in [
+            ["--csp-mode", "strict"],
+            ["--feed-mode", "partial"],
+            ["--log-format", "xml"],
+            ["--feed-max-age", "4294967296"],
+        ] {
+            assert!(Cli::try_parse_from(std::iter::once("site").chain(args)).is_err(), "{args:?}");
+        }
+    }
+
+    #[test]
+    fn antispam_difficulty_out_of_range() {
+        for difficulty in ["0", "33", "64"] {
+            let error = antispam_config(&cli(&["--antispam-difficulty", difficulty]), false).unwrap_err();
+            assert_eq!(error.to_string(), format!("invalid ANTISPAM_DIFFICULTY `{difficulty}`: expected 1 to 32"));
+        }
+        let config = antispam_config(&cli(&["--antispam-difficulty", "32"]), false).unwrap();
+        assert_eq!(config.difficulty, 32);
+    }
+
+    #[test]
+    fn counts_of_zero_are_refused() {
+        assert!(auth_config(&cli(&["--admin-max-failures", "0"]), false).is_err());
+        assert_eq!(auth_config(&cli(&["--admin-max-failures", "1"]), false).unwrap().max_failures, 1);
+        assert!(antispam_config(&cli(&["--antispam-max-submissions", "0"]), false).is_err());
+    }
+
+    #[test]
+    fn feed_max_age_fits_the_header() {
+        let config = feed_config(&cli(&["--feed-max-age", "4294967295"])).unwrap();
+        assert_eq!(config.max_age, u32::MAX);
+    }
+
+    #[test]
+    fn robots_disallow_list() {
+        let config = robots_config(&cli(&["--robots-disallow", "/api/, /rascunhos,"])).unwrap();
+        assert_eq!(config.disallow, ["/api/", "/rascunhos"]);
+    }
+
+    #[test]
+    fn metrics_need_their_own_address() {
+        let site: SocketAddr = "127.0.0.1:3000".parse().unwrap();
+        assert!(metrics_config(&cli(&["--metrics-addr", "127.0.0.1:3000"]), site).is_err());
+        let config = metrics_config(&cli(&["--metrics-addr", "127.0.0.1:9100"]), site).unwrap();
+        assert_eq!(config.addr, Some("127.0.0.1:9100".parse().unwrap()));
+    }
+}
@@ -0,0 +1,585 @@
+//! Files behind the admin content editor.
+//!
+//! Drafts mirror the content layout under `content/.drafts/`, which the
+//! loader never reads. Publishing moves a draft over the live file and
+//! reloads the store; if the site cannot load the result, the previous file
+//! is put back.
+//!
+//! Other locales are edited in their overlay (`content/en`, drafts in
+//! `content/.drafts/en`). A document without a translation loads the
+//! default-locale file, and saving it creates the overlay file.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::admin::editor::{Document, EditorError, Entry, Loaded, Payload, Status};
+use crate::content::store::{self, ExperienceFile, SkillsFile};
+use crate::content::{markdown, slugify, Project};
+use crate::i18n::Locale;
+
+const DRAFTS_DIR: &str = ".drafts";
+
+/// Shared by the workers through `web::Data`.
+pub struct Editor {
+    dir: PathBuf,
+    icons_dir: PathBuf,
+    /// Serializes writes, so two tabs cannot interleave a publish.
+    lock: Mutex<()>,
+}
+
+fn server_error(path: &Path, e: io::Error) -> EditorError {
+    EditorError::Server(format!("{}: {e}", path.display()))
+}
+
+/// Reads a file, `None` if it does not exist.
+fn read_optional(path: &Path) -> Result<Option<String>, EditorError> {
+    match fs::read_to_string(path) {
+        Ok(source) => Ok(Some(source)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(server_error(path, e)),
+    }
+}
+
+/// Writes through a temporary file so readers never see half a file.
+fn write_atomic(path: &Path, contents: &str) -> Result<(), EditorError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| server_error(parent, e))?;
+    }
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, contents).map_err(|e| server_error(&tmp, e))?;
+    fs::rename(&tmp, path).map_err(|e| server_error(path, e))
+}
+
+fn remove_optional(path: &Path) -> Result<(), EditorError> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => Err(server_error(path, e)),
+        _ => Ok(()),
+    }
+}
+
+/// Directory of the locale's files, relative to the content directory: empty
+/// for the default locale, as in the loader.
+fn locale_dir(locale: Locale) -> &'static Path {
+    Path::new(locale.prefix().trim_start_matches('/'))
+}
+
+/// Stems of the `*.md` files in `dir`.
+fn slugs(dir: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("md"))
+        .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+        .collect()
+}
+
+fn parse(document: &Document, source: &str) -> Result<Payload, String> {
+    Ok(match document {
+        Document::Profile => Payload::Profile(toml::from_str(source).map_err(|e| e.to_string())?),
+        Document::Skills => Payload::Skills(
+            toml::from_str::<SkillsFile>(source)
+                .map_err(|e| e.to_string())?
+                .categories,
+        ),
+        Document::Experience => Payload::Experience(
+            toml::from_str::<ExperienceFile>(source)
+                .map_err(|e| e.to_string())?
+                .experiences,
+        ),
+        Document::Project(slug) => {
+            let (front, body) = markdown::split_front_matter(source)
+                .ok_or("missing `+++` front matter")?;
+            let mut project: Project = toml::from_str(front).map_err(|e| e.to_string())?;
+            project.slug = slug.clone();
+            Payload::Project {
+                project,
+                body: body.to_string(),
+            }
+        }
+    })
+}
+
+/// Serializes in the same format the loader reads. Comments in the original
+/// file are not kept.
+fn serialize(payload: &Payload) -> Result<String, String> {
+    let text = match payload {
+        Payload::Profile(profile) => toml::to_string(profile),
+        Payload::Skills(categories) => toml::to_string(&SkillsFile {
+            categories: categories.clone(),
+        }),
+        Payload::Experience(experiences) => toml::to_string(&ExperienceFile {
+            experiences: experiences.clone(),
+        }),
+        Payload::Project { project, body } => {
+            let toml::Value::Table(mut front) =
+                toml::Value::try_from(project).map_err(|e| e.to_string())?
+            else {
+                return Err("a project must serialize to a table".to_string());
+            };
+            // The slug is the file name and the HTML is rendered on load
+            front.remove("slug");
+            front.remove("body_html");
+            front.retain(|_, value| value.as_array().is_none_or(|a| !a.is_empty()));
+            let front = toml::to_string(&front).map_err(|e| e.to_string())?;
+            return Ok(format!("+++\n{front}+++\n{body}"));
+        }
+    };
+    text.map_err(|e| e.to_string())
+}
+
+impl Editor {
+    /// `dir` is the content directory, `icons_dir` the served `assets/icons`.
+    pub fn new(dir: impl Into<PathBuf>, icons_dir: impl Into<PathBuf>) -> Self {
+        Editor {
+            dir: dir.into(),
+            icons_dir: icons_dir.into(),
+            lock: Mutex::new(()),
+        }
+    }
+
+    /// Icon names in `assets/icons`, sorted.
+    pub fn icons(&self) -> Vec<String> {
+        let Ok(entries) = fs::read_dir(&self.icons_dir) else {
+            return Vec::new();
+        };
+        let mut icons = entries
+            .filter_map(|entry| entry.ok().map(|e| e.path()))
+            .filter(|path| path.extension().and_then(|e| e.to_str()) == Some("png"))
+            .filter_map(|path| path.file_stem()?.to_str().map(str::to_string))
+            .collect::<Vec<_>>();
+        icons.sort();
+        icons
+    }
+
+    /// Path relative to the content directory. Slugs are checked here, since
+    /// they come from the request.
+    fn relative(&self, locale: Locale, document: &Document) -> Result<PathBuf, EditorError> {
+        let file = match document {
+            Document::Profile => PathBuf::from("profile.toml"),
+            Document::Skills => PathBuf::from("skills.toml"),
+            Document::Experience => PathBuf::from("experience.toml"),
+            Document::Project(slug) if !slug.is_empty() && slugify(slug) == *slug => {
+                Path::new("projects").join(format!("{slug}.md"))
+            }
+            Document::Project(_) => return Err(EditorError::NotFound),
+        };
+        Ok(locale_dir(locale).join(file))
+    }
+
+    fn paths(&self, locale: Locale, document: &Document) -> Result<(PathBuf, PathBuf), EditorError> {
+        let relative = self.relative(locale, document)?;
+        Ok((
+            self.dir.join(&relative),
+            self.dir.join(DRAFTS_DIR).join(relative),
+        ))
+    }
+
+    fn status(live: bool, draft: bool) -> Option<Status> {
+        match (live, draft) {
+            (true, false) => Some(Status::Published),
+            (true, true) => Some(Status::Draft),
+            (false, true) => Some(Status::New),
+            (false, false) => None,
+        }
+    }
+
+    /// The state of a document in `locale`. A translation that does not
+    /// exist yet is [`Status::Untranslated`] when there is a default-locale
+    /// file to start from.
+    fn locale_status(&self, locale: Locale, document: &Document) -> Result<Option<Status>, EditorError> {
+        let (live, draft) = self.paths(locale, document)?;
+        Ok(match Self::status(live.exists(), draft.exists()) {
+            None if locale != Locale::default() && self.paths(Locale::default(), document)?.0.exists() => {
+                Some(Status::Untranslated)
+            }
+            status => status,
+        })
+    }
+
+    /// Every editable document in `locale` with its state.
+    pub fn overview(&self, locale: Locale) -> Result<Vec<Entry>, EditorError> {
+        let mut entries = Vec::new();
+        for (document, title) in [
+            (Document::Profile, "Perfil"),
+            (Document::Skills, "Habilidades"),
+            (Document::Experience, "Experiência"),
+        ] {
+            if let Some(status) = self.locale_status(locale, &document)? {
+                entries.push(Entry {
+                    document,
+                    title: title.to_string(),
+                    status,
+                });
+            }
+        }
+
+        let projects_dir = locale_dir(locale).join("projects");
+        let mut projects = slugs(&self.dir.join(&projects_dir));
+        projects.extend(slugs(&self.dir.join(DRAFTS_DIR).join(&projects_dir)));
+        if locale != Locale::default() {
+            // Untranslated projects too
+            projects.extend(slugs(&self.dir.join("projects")));
+        }
+        projects.sort();
+        projects.dedup();
+        for slug in projects {
+            let document = Document::Project(slug.clone());
+            let Ok(loaded) = self.load(locale, &document) else {
+                continue;
+            };
+            let title = match loaded.payload {
+                Payload::Project { project, .. } if !project.title.is_empty() => project.title,
+                _ => slug,
+            };
+            entries.push(Entry {
+                document,
+                title,
+                status: loaded.status,
+            });
+        }
+
+        Ok(entries)
+    }
+
+    /// The draft if there is one, otherwise the live file. A missing
+    /// translation starts from the live default-locale file.
+    pub fn load(&self, locale: Locale, document: &Document) -> Result<Loaded, EditorError> {
+        let (live, draft) = self.paths(locale, document)?;
+        let draft_source = read_optional(&draft)?;
+        let (payload, status) = match self.locale_status(locale, document)? {
+            Some(status) => {
+                let (path, source) = match (draft_source, status) {
+                    (Some(source), _) => (draft, source),
+                    (None, Status::Untranslated) => {
+                        let (original, _) = self.paths(Locale::default(), document)?;
+                        let source = read_optional(&original)?.ok_or(EditorError::NotFound)?;
+                        (original, source)
+                    }
+                    (None, _) => (live.clone(), read_optional(&live)?.ok_or(EditorError::NotFound)?),
+                };
+                let payload = parse(document, &source)
+                    .map_err(|e| EditorError::Server(format!("{}: {e}", path.display())))?;
+                (payload, status)
+            }
+            // A new project starts blank
+            None => match document {
+                Document::Project(slug) => (
+                    Payload::Project {
+                        project: Project {
+                            slug: slug.clone(),
+                            ..Project::default()
+                        },
+                        body: String::new(),
+                    },
+                    Status::Unsaved,
+                ),
+                _ => return Err(EditorError::NotFound),
+            },
+        };
+        Ok(Loaded {
+            payload,
+            status,
+            locale,
+            icons: self.icons(),
+            today: chrono::Local::now().date_naive(),
+        })
+    }
+
+    fn check(&self, payload: Payload) -> Result<(Payload, String), EditorError> {
+        let payload = payload.normalized();
+        let issues = payload.validate(&self.icons());
+        if !issues.is_empty() {
+            return Err(EditorError::Invalid(issues));
+        }
+        let text = serialize(&payload).map_err(EditorError::Server)?;
+        Ok((payload, text))
+    }
+
+    /// Validates and stores a draft. The site is not affected.
+    pub fn save_draft(&self, locale: Locale, payload: Payload) -> Result<Status, EditorError> {
+        let (payload, text) = self.check(payload)?;
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let (live, draft) = self.paths(locale, &payload.document())?;
+        write_atomic(&draft, &text)?;
+        Ok(if live.exists() { Status::Draft } else { Status::New })
+    }
+
+    /// Makes the draft live and reloads the content store, which loads
+    /// every locale, so a broken translation is rolled back too.
+    pub fn publish(&self, locale: Locale, document: &Document) -> Result<(), EditorError> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let (live, draft) = self.paths(locale, document)?;
+        let source = read_optional(&draft)?.ok_or(EditorError::NotFound)?;
+        // Checked again: the rules or the icons may have changed since saving
+        let payload = parse(document, &source).map_err(EditorError::Server)?;
+        let (_, text) = self.check(payload)?;
+
+        let previous = read_optional(&live)?;
+        write_atomic(&live, &text)?;
+        if let Err(e) = store::reload() {
+            match &previous {
+                Some(previous) => write_atomic(&live, previous)?,
+                None => remove_optional(&live)?,
+            }
+            return Err(EditorError::Rejected(e.to_string()));
+        }
+        remove_optional(&draft)
+    }
+
+    /// Drops the draft, going back to the live version.
+    pub fn discard(&self, locale: Locale, document: &Document) -> Result<(), EditorError> {
+        let _guard = self.lock.lock().unwrap_or_else(|e| e.into_inner());
+        let (_, draft) = self.paths(locale, document)?;
+        remove_optional(&draft)
+    }
+}
+
+// --- Server function helpers ---
+
+/// The [`Editor`], once the request has a session. Changes pass the form's
+/// CSRF token too.
+pub async fn authorize(csrf: Option<&str>) -> Result<actix_web::web::Data<Editor>, EditorError> {
+    use crate::admin::AuthError;
+    use crate::server::auth;
+
+    let (req, _) = auth::request().await?;
+    let session = match csrf {
+        Some(csrf) => Some(auth::require_admin(csrf).await?),
+        None => auth::current().await?,
+    };
+    session.ok_or(AuthError::Unauthorized)?;
+    req.app_data::<actix_web::web::Data<Editor>>()
+        .cloned()
+        .ok_or_else(|| EditorError::Server("content editor is not configured".to_string()))
+}
+
+/// Runs blocking file work off the async workers, in the request's span so
+/// what it logs keeps the request ID.
+pub async fn run<T: Send + 'static>(
+    editor: actix_web::web::Data<Editor>,
+    f: impl FnOnce(&Editor) -> Result<T, EditorError> + Send + 'static,
+) -> Result<T, EditorError> {
+    let span = tracing::Span::current();
+    actix_web::web::block(move || span.in_scope(|| f(&editor)))
+        .await
+        .map_err(|e| EditorError::Server(e.to_string()))?
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::{MutexGuard, OnceLock};
+
+    use super::*;
+    use crate::content::{ProfileData, SkillCategory, SkillItem};
+    use crate::server::{hex, random_bytes};
+
+    const PROFILE: &str = r#"name = "Ana"
+title = "Dev"
+email = "ana@example.com"
+linkedin = "https://example.com/ana"
+"#;
+
+    /// A content directory of its own with the files the loader needs, and
+    /// an icons directory holding `rust_logo.png`.
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("editor-{name}-{}", hex(&random_bytes()[..8])));
+        fs::create_dir_all(dir.join("icons")).unwrap();
+        fs::write(dir.join("icons").join("rust_logo.png"), "").unwrap();
+        let content = dir.join("content");
+        fs::create_dir_all(&content).unwrap();
+        fs::write(content.join("profile.toml"), PROFILE).unwrap();
+        fs::write(content.join("skills.toml"), "").unwrap();
+        fs::write(content.join("experience.toml"), "").unwrap();
+        dir
+    }
+
+    fn editor(dir: &Path) -> Editor {
+        Editor::new(dir.join("content"), dir.join("icons"))
+    }
+
+    /// The content store is global, so the tests that publish share one
+    /// directory, loaded once, and take turns with it.
+    fn store_dir() -> (&'static Path, MutexGuard<'static, ()>) {
+        static DIR: OnceLock<PathBuf> = OnceLock::new();
+        static TURN: Mutex<()> = Mutex::new(());
+        let turn = TURN.lock().unwrap_or_else(|e| e.into_inner());
+        let dir = DIR.get_or_init(|| {
+            let dir = temp_dir("store");
+            store::init(dir.join("content")).unwrap();
+            dir
+        });
+        (dir, turn)
+    }
+
+    fn profile(editor: &Editor, locale: Locale) -> ProfileData {
+        match editor.load(locale, &Document::Profile).unwrap().payload {
+            Payload::Profile(profile) => profile,
+            payload => panic!("not a profile: {payload:?}"),
+        }
+    }
+
+    fn with_tagline(editor: &Editor, tagline: &str) -> Payload {
+        Payload::Profile(ProfileData {
+            tagline: tagline.to_string(),
+            ..profile(editor, Locale::default())
+        })
+    }
+
+    fn project(slug: &str) -> Payload {
+        Payload::Project {
+            project: Project {
+                slug: slug.to_string(),
+                title: "Novo".to_string(),
+                summary: "Um projeto.".to_string(),
+                ..Project::default()
+            },
+            body: "Texto.\n".to_string(),
+        }
+    }
+
+    #[test]
+    fn slugs_from_the_request_are_checked() {
+        let dir = temp_dir("slugs");
+        let editor = editor(&dir);
+        for slug in ["", "../profile", "a/b", "Camara", "camara.md", "-camara"] {
+            let document = Document::Project(slug.to_string());
+            assert_eq!(editor.relative(Locale::default(), &document), Err(EditorError::NotFound), "{slug}");
+            assert_eq!(editor.load(Locale::default(), &document), Err(EditorError::NotFound), "{slug}");
+        }
+        let camara = Document::Project("camara".to_string());
+        assert_eq!(
+            editor.relative(Locale::default(), &camara),
+            Ok(Path::new("projects").join("camara.md"))
+        );
+        assert_eq!(
+            editor.relative(Locale::En, &camara),
+            Ok(Path::new("en").join("projects").join("camara.md"))
+        );
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn drafts_do_not_touch_the_live_file() {
+        let dir = temp_dir("drafts");
+        let editor = editor(&dir);
+        let live = dir.join("content").join("profile.toml");
+        assert_eq!(editor.load(Locale::default(), &Document::Profile).unwrap().status, Status::Published);
+
+        let draft = with_tagline(&editor, "Rascunho");
+        assert_eq!(editor.save_draft(Locale::default(), draft.clone()), Ok(Status::Draft));
+        let loaded = editor.load(Locale::default(), &Document::Profile).unwrap();
+        assert_eq!((loaded.status, loaded.payload), (Status::Draft, draft));
+        assert_eq!(fs::read_to_string(&live).unwrap(), PROFILE);
+
+        editor.discard(Locale::default(), &Document::Profile).unwrap();
+        assert_eq!(editor.load(Locale::default(), &Document::Profile).unwrap().status, Status::Published);
+        assert_eq!(profile(&editor, Locale::default()).tagline, "");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn a_new_project_is_a_draft_until_published() {
+        let dir = temp_dir("new");
+        let editor = editor(&dir);
+        let document = Document::Project("novo".to_string());
+        assert_eq!(editor.load(Locale::default(), &document).unwrap().status, Status::Unsaved);
+
+        assert_eq!(editor.save_draft(Locale::default(), project("novo")), Ok(Status::New));
+        assert_eq!(editor.load(Locale::default(), &document).unwrap().status, Status::New);
+        assert!(editor
+            .overview(Locale::default())
+            .unwrap()
+            .iter()
+            .any(|entry| entry.document == document && entry.status == Status::New));
+        assert!(!dir.join("content").join("projects").join("novo.md").exists());
+
+        editor.discard(Locale::default(), &document).unwrap();
+        assert_eq!(editor.load(Locale::default(), &document).unwrap().status, Status::Unsaved);
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn invalid_drafts_are_not_saved() {
+        let dir = temp_dir("invalid");
+        let editor = editor(&dir);
+        let skills = Payload::Skills(vec![SkillCategory {
+            category: "Rust".to_string(),
+            items: vec![SkillItem {
+                name: "Leptos".to_string(),
+                icon: "leptos_logo".to_string(),
+            }],
+        }]);
+        assert!(matches!(
+            editor.save_draft(Locale::default(), skills),
+            Err(EditorError::Invalid(issues)) if issues.len() == 1
+        ));
+        assert!(!dir.join("content").join(DRAFTS_DIR).exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn an_untranslated_document_starts_from_the_default_file() {
+        let dir = temp_dir("untranslated");
+        let editor = editor(&dir);
+        let loaded = editor.load(Locale::En, &Document::Profile).unwrap();
+        assert_eq!(loaded.status, Status::Untranslated);
+        assert_eq!(loaded.locale, Locale::En);
+        assert_eq!(loaded.payload, Payload::Profile(profile(&editor, Locale::default())));
+
+        // Saving creates the translation's draft, not a change to the default
+        let translated = with_tagline(&editor, "Translated");
+        assert_eq!(editor.save_draft(Locale::En, translated.clone()), Ok(Status::New));
+        assert!(dir.join("content").join(DRAFTS_DIR).join("en").join("profile.toml").exists());
+        assert_eq!(editor.load(Locale::En, &Document::Profile).unwrap().payload, translated);
+        assert_eq!(profile(&editor, Locale::default()).tagline, "");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn publishing_makes_the_draft_live() {
+        let (dir, _turn) = store_dir();
+        let editor = editor(dir);
+        editor.save_draft(Locale::default(), with_tagline(&editor, "Publicado")).unwrap();
+        editor.publish(Locale::default(), &Document::Profile).unwrap();
+
+        let loaded = editor.load(Locale::default(), &Document::Profile).unwrap();
+        assert_eq!(loaded.status, Status::Published);
+        assert!(!dir.join("content").join(DRAFTS_DIR).join("profile.toml").exists());
+        assert_eq!(store::get().unwrap().profile.tagline, "Publicado");
+    }
+
+    #[test]
+    fn a_rejected_publish_puts_the_live_file_back() {
+        let (dir, _turn) = store_dir();
+        let editor = editor(dir);
+        let content = dir.join("content");
+        let live = content.join("profile.toml");
+        let previous = fs::read_to_string(&live).unwrap();
+        // Any file the site cannot load makes the reload fail
+        let broken = content.join("projects").join("quebrado.md");
+        fs::create_dir_all(broken.parent().unwrap()).unwrap();
+        fs::write(&broken, "no front matter").unwrap();
+
+        editor.save_draft(Locale::default(), with_tagline(&editor, "Recusado")).unwrap();
+        let published = editor.publish(Locale::default(), &Document::Profile);
+        assert!(matches!(published, Err(EditorError::Rejected(_))));
+        assert_eq!(fs::read_to_string(&live).unwrap(), previous);
+        assert_eq!(editor.load(Locale::default(), &Document::Profile).unwrap().status, Status::Draft);
+        assert_ne!(store::get().unwrap().profile.tagline, "Recusado");
+
+        // A file that did not exist before is removed again
+        editor.save_draft(Locale::default(), project("novo")).unwrap();
+        let document = Document::Project("novo".to_string());
+        let published = editor.publish(Locale::default(), &document);
+        assert!(matches!(published, Err(EditorError::Rejected(_))));
+        assert!(!content.join("projects").join("novo.md").exists());
+
+        fs::remove_file(broken).unwrap();
+        editor.discard(Locale::default(), &Document::Profile).unwrap();
+        editor.discard(Locale::default(), &document).unwrap();
+    }
+}
@@ -0,0 +1,262 @@
+//! Static export: every page rendered through the normal SSR pipeline and
+//! written to disk with the compiled `pkg` and the assets, so the site can be
+//! hosted as plain files. Pages still hydrate; what needs the server (the
+//! contact form, the admin area) does not work there.
+
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use actix_web::http::StatusCode;
+use actix_web::test::{self, TestRequest};
+use actix_web::{middleware, App};
+
+use super::locale;
+use super::site::Site;
+use crate::assets;
+use crate::components::page_meta::og_image_path;
+use crate::content::store::{self, ContentError};
+use crate::content::{slugify, tag_path, POSTS_PER_PAGE};
+use crate::i18n::Locale;
+
+pub const DEFAULT_DIR: &str = "target/static";
+
+/// Inline script for exported pages. There are no server functions behind
+/// them, so links do full page loads instead of client-side navigation, and
+/// the language switcher goes straight to the page in the chosen locale.
+pub const CLIENT_SCRIPT: &str = r##"addEventListener("click", (e) => {
+  const a = e.target instanceof Element && e.target.closest("a[href]");
+  if (!a || a.origin !== location.origin) return;
+  a.rel = "external";
+  const lang = a.pathname.match(/^\/lang\/([^/]+)$/);
+  if (lang) {
+    const next = new URLSearchParams(a.search).get("next") || "/";
+    const path = next.replace(/^\/en(?=[/#]|$)/, "") || "/";
+    a.href = lang[1] === "en" ? "/en" + (path === "/" ? "" : path.replace(/^\/#/, "#")) : path;
+  }
+}, true);"##;
+
+#[derive(Debug)]
+pub enum ExportError {
+    Io(PathBuf, io::Error),
+    Content(ContentError),
+    /// A page did not render as expected.
+    Status(String, StatusCode),
+    /// The output directory has files that are not from an export.
+    NotEmpty(PathBuf),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::Io(path, e) => write!(f, "{}: {e}", path.display()),
+            ExportError::Content(e) => write!(f, "{e}"),
+            ExportError::Status(path, status) => write!(f, "{path} answered {status}"),
+            ExportError::NotEmpty(path) => {
+                write!(f, "{} is not empty and does not look like an export", path.display())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+impl From<ContentError> for ExportError {
+    fn from(value: ContentError) -> Self {
+        ExportError::Content(value)
+    }
+}
+
+/// What was written.
+#[derive(Debug, Default)]
+pub struct Summary {
+    pub pages: usize,
+    /// Feeds, sitemap, preview images...
+    pub files: usize,
+    pub copied: usize,
+}
+
+/// One request to make and where its body goes.
+struct Output {
+    path: String,
+    file: PathBuf,
+    status: StatusCode,
+    /// Pages fail the export; other files are skipped with a warning.
+    page: bool,
+}
+
+impl Output {
+    /// `/blog` -> `blog/index.html`.
+    fn page(path: String) -> Self {
+        let file = Path::new(path.trim_matches('/')).join("index.html");
+        Output {
+            path,
+            file,
+            status: StatusCode::OK,
+            page: true,
+        }
+    }
+
+    fn file(path: String) -> Self {
+        Output {
+            file: PathBuf::from(path.trim_start_matches('/')),
+            path,
+            status: StatusCode::OK,
+            page: false,
+        }
+    }
+}
+
+/// Paths of the pages in `locale`, without its prefix: the parameterless
+/// routes plus one per project, post, tag and listing page.
+fn page_paths(site: &Site, locale: Locale) -> Result<BTreeSet<String>, ContentError> {
+    let mut paths = site
+        .routes
+        .iter()
+        .map(|route| route.path())
+        .filter(|path| !path.contains('{') && !path.contains('*'))
+        .map(|path| Locale::strip_prefix(path).to_string())
+        .filter(|path| path != "/admin" && !path.starts_with("/admin/"))
+        .collect::<BTreeSet<_>>();
+
+    let content = store::get_localized(locale)?;
+    let listing_pages = |count: usize| 2..=count.div_ceil(POSTS_PER_PAGE);
+    paths.extend(content.projects.iter().map(|project| project.path()));
+    paths.extend(content.published_posts().map(|post| post.path()));
+    paths.extend(
+        listing_pages(content.published_posts().count()).map(|n| format!("/blog/pagina/{n}")),
+    );
+
+    let mut tags = content
+        .published_posts()
+        .flat_map(|post| post.tags.iter().map(|tag| slugify(tag)))
+        .collect::<Vec<_>>();
+    tags.sort();
+    for tag in tags.chunk_by(|a, b| a == b) {
+        let path = tag_path(&tag[0]);
+        paths.extend(listing_pages(tag.len()).map(|n| format!("{path}/pagina/{n}")));
+        paths.insert(path);
+    }
+    Ok(paths)
+}
+
+fn write(path: &Path, contents: &[u8]) -> Result<(), ExportError> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| ExportError::Io(parent.to_path_buf(), e))?;
+    }
+    fs::write(path, contents).map_err(|e| ExportError::Io(path.to_path_buf(), e))
+}
+
+/// Empties `out`, provided it holds a previous export (or nothing at all).
+fn clear(out: &Path) -> Result<(), ExportError> {
+    let is_empty = match fs::read_dir(out) {
+        Ok(mut entries) => entries.next().is_none(),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(()),
+        Err(e) => return Err(ExportError::Io(out.to_path_buf(), e)),
+    };
+    if !is_empty && !out.join("404.html").is_file() {
+        return Err(ExportError::NotEmpty(out.to_path_buf()));
+    }
+    fs::remove_dir_all(out).map_err(|e| ExportError::Io(out.to_path_buf(), e))
+}
+
+/// Copies `from` into `to`, leaving out the top-level entries in `skip`.
+fn copy_dir(from: &Path, to: &Path, skip: &[&str]) -> Result<usize, ExportError> {
+    let entries = fs::read_dir(from).map_err(|e| ExportError::Io(from.to_path_buf(), e))?;
+    let mut copied = 0;
+    for entry in entries {
+        let entry = entry.map_err(|e| ExportError::Io(from.to_path_buf(), e))?;
+        let name = entry.file_name();
+        if skip.iter().any(|skip| name == *skip) {
+            continue;
+        }
+        let source = entry.path();
+        let target = to.join(&name);
+        if source.is_dir() {
+            copied += copy_dir(&source, &target, &[])?;
+        } else {
+            fs::create_dir_all(to).map_err(|e| ExportError::Io(to.to_path_buf(), e))?;
+            fs::copy(&source, &target).map_err(|e| ExportError::Io(source.clone(), e))?;
+            copied += 1;
+        }
+    }
+    Ok(copied)
+}
+
+/// Renders the site into `out`, replacing a previous export there.
+pub async fn export(site: &Site, out: &Path) -> Result<Summary, ExportError> {
+    let mut outputs = Vec::new();
+    for locale in Locale::ALL {
+        for path in page_paths(site, locale)? {
+            let path = locale.localize(&path);
+            // The server tells locales apart with `?lang=`; exported pages
+            // point to a file of their own instead
+            if let (Some(image), Some(file)) = (og_image_path(&path, false), og_image_path(&path, true)) {
+                outputs.push(Output {
+                    file: PathBuf::from(file.trim_start_matches('/')),
+                    ..Output::file(image)
+                });
+            }
+            outputs.push(Output::page(path));
+        }
+    }
+    for path in ["/feed.xml", "/atom.xml", "/feed.json", "/sitemap.xml", "/robots.txt"] {
+        outputs.push(Output::file(path.to_string()));
+    }
+    // Static hosts serve `404.html` for unknown paths
+    outputs.push(Output {
+        path: "/__export/404".to_string(),
+        file: PathBuf::from("404.html"),
+        status: StatusCode::NOT_FOUND,
+        page: true,
+    });
+
+    clear(out)?;
+
+    let app = test::init_service(
+        App::new()
+            .configure(|cfg| site.configure(cfg))
+            .wrap(middleware::from_fn(locale::redirect)),
+    )
+    .await;
+
+    let mut summary = Summary::default();
+    for output in outputs {
+        let res = test::call_service(&app, TestRequest::get().uri(&output.path).to_request()).await;
+        let status = res.status();
+        if status != output.status {
+            if output.page {
+                return Err(ExportError::Status(output.path, status));
+            }
+            tracing::warn!("export: skipping {}, it answered {status}", output.path);
+            continue;
+        }
+        write(&out.join(&output.file), &test::read_body(res).await)?;
+        if output.page {
+            summary.pages += 1;
+        } else {
+            summary.files += 1;
+        }
+    }
+
+    // Same layout as the server: `pkg` on its own, everything else under `/assets`
+    let site_root = Path::new(&*site.leptos_options.site_root);
+    summary.copied += copy_dir(&site_root.join("pkg"), &out.join("pkg"), &[])?;
+    summary.copied += copy_dir(site_root, &out.join("assets"), &["pkg"])?;
+    fs::copy(site_root.join("favicon.ico"), out.join("favicon.ico"))
+        .map_err(|e| ExportError::Io(site_root.join("favicon.ico"), e))?;
+    summary.copied += 1;
+    // Pages link to the fingerprinted names, which the server maps back
+    for (path, hashed) in assets::MANIFEST {
+        let source = out.join(path.trim_start_matches('/'));
+        if source.is_file() {
+            fs::copy(&source, out.join(hashed.trim_start_matches('/')))
+                .map_err(|e| ExportError::Io(source.clone(), e))?;
+            summary.copied += 1;
+        }
+    }
+
+    Ok(summary)
+}
@@ -0,0 +1,357 @@
+//! RSS 2.0 (`/feed.xml`), Atom (`/atom.xml`) and JSON Feed 1.1 (`/feed.json`)
+//! built from the same posts and projects the pages render.
+
+use std::fmt::Write;
+
+use actix_web::http::header::{self, HttpDate};
+use actix_web::{get, web, HttpResponse};
+use chrono::{DateTime, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::{xml_escape, SiteUrl};
+use crate::content::store::{self, Content};
+
+/// Whether feed entries carry the full rendered body or only the summary.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FeedMode {
+    #[default]
+    Full,
+    Summary,
+}
+
+impl FeedMode {
+    /// The accepted values, for error messages.
+    pub const EXPECTED: &str = "full or summary";
+}
+
+impl std::str::FromStr for FeedMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "full" => Ok(FeedMode::Full),
+            "summary" => Ok(FeedMode::Summary),
+            _ => Err(format!("expected {}", FeedMode::EXPECTED)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FeedConfig {
+    pub title: String,
+    pub description: String,
+    pub language: String,
+    /// Default mode; readers can override it with `?mode=summary|full`.
+    pub mode: FeedMode,
+    /// `Cache-Control: max-age` for feed responses, in seconds.
+    pub max_age: u32,
+}
+
+impl Default for FeedConfig {
+    fn default() -> Self {
+        FeedConfig {
+            title: "Nicolas Almino".to_string(),
+            description: "Projetos e artigos sobre desenvolvimento web, Rust e audiovisual."
+                .to_string(),
+            language: "pt-BR".to_string(),
+            mode: FeedMode::Full,
+            max_age: 3600,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct FeedQuery {
+    mode: Option<FeedMode>,
+}
+
+/// A post or project, flattened to what every feed format needs.
+struct FeedItem {
+    title: String,
+    url: String,
+    date: DateTime<Utc>,
+    summary: String,
+    content_html: String,
+    tags: Vec<String>,
+}
+
+fn to_datetime(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).unwrap_or_default().and_utc()
+}
+
+/// Published posts plus dated projects, newest first.
+fn collect_items(content: &Content, site: &SiteUrl) -> Vec<FeedItem> {
+    let posts = content.published_posts().map(|post| FeedItem {
+        title: post.title.clone(),
+        url: site.join(&post.path()),
+        date: to_datetime(post.date),
+        summary: post.summary.clone(),
+        content_html: site.absolutize_html(&post.body_html),
+        tags: post.tags.clone(),
+    });
+    // Projects only have a date when it was set in their front matter
+    let projects = content.projects.iter().filter_map(|project| {
+        Some(FeedItem {
+            title: project.title.clone(),
+            url: site.join(&project.path()),
+            date: to_datetime(project.date?),
+            summary: project.summary.clone(),
+            content_html: site.absolutize_html(&project.body_html),
+            tags: project.tags.clone(),
+        })
+    });
+
+    let mut items = posts.chain(projects).collect::<Vec<_>>();
+    items.sort_by(|a, b| b.date.cmp(&a.date));
+    items
+}
+
+struct Feed {
+    items: Vec<FeedItem>,
+    updated: DateTime<Utc>,
+    mode: FeedMode,
+    author: String,
+    email: String,
+}
+
+fn load_feed(site: &SiteUrl, config: &FeedConfig, query: &FeedQuery) -> actix_web::Result<Feed> {
+    let content = store::get().map_err(actix_web::error::ErrorInternalServerError)?;
+    let items = collect_items(&content, site);
+    let updated = items
+        .first()
+        .map(|item| item.date)
+        .unwrap_or_else(|| DateTime::<Utc>::UNIX_EPOCH);
+    Ok(Feed {
+        items,
+        updated,
+        mode: query.mode.unwrap_or(config.mode),
+        author: content.profile.name.clone(),
+        email: content.profile.email.clone(),
+    })
+}
+
+fn feed_response(
+    content_type: &str,
+    config: &FeedConfig,
+    feed: &Feed,
+    body: String,
+) -> HttpResponse {
+    HttpResponse::Ok()
+        .content_type(content_type)
+        .insert_header((
+            header::CACHE_CONTROL,
+            format!("public, max-age={}", config.max_age),
+        ))
+        .insert_header((
+            header::LAST_MODIFIED,
+            HttpDate::from(std::time::SystemTime::from(feed.updated)),
+        ))
+        .body(body)
+}
+
+#[get("/feed.xml")]
+pub async fn rss(
+    site: web::Data<SiteUrl>,
+    config: web::Data<FeedConfig>,
+    query: web::Query<FeedQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let feed = load_feed(&site, &config, &query)?;
+    let mut xml = String::new();
+
+    let _ = write!(
+        xml,
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<rss version="2.0" xmlns:atom="http://www.w3.org/2005/Atom" xmlns:content="http://purl.org/rss/1.0/modules/content/">
+<channel>
+<title>{title}</title>
+<link>{home}</link>
+<description>{description}</description>
+<language>{language}</language>
+<lastBuildDate>{updated}</lastBuildDate>
+<atom:link href="{self_url}" rel="self" type="application/rss+xml"/>
+"#,
+        title = xml_escape(&config.title),
+        home = xml_escape(&site.join("/")),
+        description = xml_escape(&config.description),
+        language = xml_escape(&config.language),
+        updated = feed.updated.to_rfc2822(),
+        self_url = xml_escape(&site.join("/feed.xml")),
+    );
+    for item in &feed.items {
+        let _ = write!(
+            xml,
+            "<item>\n<title>{}</title>\n<link>{url}</link>\n<guid isPermaLink=\"true\">{url}</guid>\n<pubDate>{}</pubDate>\n<description>{}</description>\n",
+            xml_escape(&item.title),
+            item.date.to_rfc2822(),
+            xml_escape(&item.summary),
+            url = xml_escape(&item.url),
+        );
+        if feed.mode == FeedMode::Full && !item.content_html.is_empty() {
+            let _ = writeln!(
+                xml,
+                "<content:encoded>{}</content:encoded>",
+                xml_escape(&item.content_html)
+            );
+        }
+        for tag in &item.tags {
+            let _ = writeln!(xml, "<category>{}</category>", xml_escape(tag));
+        }
+        xml.push_str("</item>\n");
+    }
+    xml.push_str("</channel>\n</rss>\n");
+
+    Ok(feed_response(
+        "application/rss+xml; charset=utf-8",
+        &config,
+        &feed,
+        xml,
+    ))
+}
+
+#[get("/atom.xml")]
+pub async fn atom(
+    site: web::Data<SiteUrl>,
+    config: web::Data<FeedConfig>,
+    query: web::Query<FeedQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let feed = load_feed(&site, &config, &query)?;
+    let mut xml = String::new();
+
+    let _ = write!(
+        xml,
+        r#"<?xml version="1.0" encoding="utf-8"?>
+<feed xmlns="http://www.w3.org/2005/Atom" xml:lang="{language}">
+<title>{title}</title>
+<subtitle>{description}</subtitle>
+<id>{home}</id>
+<link rel="alternate" type="text/html" href="{home}"/>
+<link rel="self" type="application/atom+xml" href="{self_url}"/>
+<updated>{updated}</updated>
+<author><name>{author}</name>{email}</author>
+"#,
+        language = xml_escape(&config.language),
+        title = xml_escape(&config.title),
+        description = xml_escape(&config.description),
+        home = xml_escape(&site.join("/")),
+        self_url = xml_escape(&site.join("/atom.xml")),
+        updated = feed.updated.to_rfc3339(),
+        author = xml_escape(&feed.author),
+        email = match feed.email.as_str() {
+            "" => String::new(),
+            email => format!("<email>{}</email>", xml_escape(email)),
+        },
+    );
+    for item in &feed.items {
+        let _ = write!(
+            xml,
+            "<entry>\n<title>{}</title>\n<id>{url}</id>\n<link rel=\"alternate\" type=\"text/html\" href=\"{url}\"/>\n<published>{date}</published>\n<updated>{date}</updated>\n<summary>{}</summary>\n",
+            xml_escape(&item.title),
+            xml_escape(&item.summary),
+            url = xml_escape(&item.url),
+            date = item.date.to_rfc3339(),
+        );
+        if feed.mode == FeedMode::Full && !item.content_html.is_empty() {
+            let _ = writeln!(
+                xml,
+                "<content type=\"html\">{}</content>",
+                xml_escape(&item.content_html)
+            );
+        }
+        for tag in &item.tags {
+            let _ = writeln!(xml, "<category term=\"{}\"/>", xml_escape(tag));
+        }
+        xml.push_str("</entry>\n");
+    }
+    xml.push_str("</feed>\n");
+
+    Ok(feed_response(
+        "application/atom+xml; charset=utf-8",
+        &config,
+        &feed,
+        xml,
+    ))
+}
+
+// --- JSON Feed 1.1 (https://www.jsonfeed.org/version/1.1/) ---
+
+#[derive(Serialize)]
+struct JsonFeed<'a> {
+    version: &'static str,
+    title: &'a str,
+    home_page_url: String,
+    feed_url: String,
+    description: &'a str,
+    language: &'a str,
+    authors: Vec<JsonFeedAuthor<'a>>,
+    items: Vec<JsonFeedItem<'a>>,
+}
+
+#[derive(Serialize)]
+struct JsonFeedAuthor<'a> {
+    name: &'a str,
+    url: String,
+}
+
+#[derive(Serialize)]
+struct JsonFeedItem<'a> {
+    id: &'a str,
+    url: &'a str,
+    title: &'a str,
+    summary: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_html: Option<&'a str>,
+    /// Items must carry some content, so summary mode falls back to `content_text`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    content_text: Option<&'a str>,
+    date_published: String,
+    tags: &'a [String],
+}
+
+#[get("/feed.json")]
+pub async fn json(
+    site: web::Data<SiteUrl>,
+    config: web::Data<FeedConfig>,
+    query: web::Query<FeedQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let feed = load_feed(&site, &config, &query)?;
+
+    let items = feed
+        .items
+        .iter()
+        .map(|item| {
+            let full = feed.mode == FeedMode::Full && !item.content_html.is_empty();
+            JsonFeedItem {
+                id: &item.url,
+                url: &item.url,
+                title: &item.title,
+                summary: &item.summary,
+                content_html: full.then_some(item.content_html.as_str()),
+                content_text: (!full).then_some(item.summary.as_str()),
+                date_published: item.date.to_rfc3339(),
+                tags: &item.tags,
+            }
+        })
+        .collect();
+    let body = serde_json::to_string(&JsonFeed {
+        version: "https://jsonfeed.org/version/1.1",
+        title: &config.title,
+        home_page_url: site.join("/"),
+        feed_url: site.join("/feed.json"),
+        description: &config.description,
+        language: &config.language,
+        authors: vec![JsonFeedAuthor {
+            name: &feed.author,
+            url: site.join("/"),
+        }],
+        items,
+    })
+    .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(feed_response(
+        "application/feed+json; charset=utf-8",
+        &config,
+        &feed,
+        body,
+    ))
+}
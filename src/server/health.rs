@@ -0,0 +1,147 @@
+//! Probes for deploys and monitoring: `/healthz` answers while the process
+//! runs, `/readyz` once it can serve the site, and `/__version` tells which
+//! build is running, so a deploy or a rollback can be checked from outside.
+
+use std::fs;
+use std::path::Path;
+
+use actix_web::http::header;
+use actix_web::{get, web, HttpResponse};
+use chrono::{DateTime, Utc};
+use leptos::config::LeptosOptions;
+use serde::Serialize;
+
+use super::contact::ContactService;
+use crate::assets::MANIFEST;
+use crate::content::store;
+
+/// Metadata embedded by `build.rs`.
+#[derive(Serialize)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    pub git_sha: &'static str,
+    pub built_at: Option<DateTime<Utc>>,
+    pub features: Vec<&'static str>,
+}
+
+impl BuildInfo {
+    pub fn current() -> Self {
+        BuildInfo {
+            version: env!("CARGO_PKG_VERSION"),
+            git_sha: env!("BUILD_GIT_SHA"),
+            built_at: env!("BUILD_TIMESTAMP")
+                .parse()
+                .ok()
+                .and_then(|secs| DateTime::from_timestamp(secs, 0)),
+            features: env!("BUILD_FEATURES")
+                .split(',')
+                .filter(|feature| !feature.is_empty())
+                .collect(),
+        }
+    }
+}
+
+/// Outcome of one readiness check. The probe is public, so what went wrong
+/// (paths, OS errors) goes to the log and not into the response.
+#[derive(Serialize)]
+struct Check {
+    name: &'static str,
+    ok: bool,
+}
+
+impl Check {
+    fn new(name: &'static str, result: Result<(), String>) -> Self {
+        if let Err(e) = &result {
+            tracing::warn!(check = name, "readyz: not ready: {e}");
+        }
+        Check {
+            name,
+            ok: result.is_ok(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+struct Readiness {
+    ready: bool,
+    checks: Vec<Check>,
+}
+
+/// The content store holds a snapshot.
+fn content_loaded() -> Result<(), String> {
+    store::get().map(|_| ()).map_err(|e| e.to_string())
+}
+
+/// The hydration bundle and every fingerprinted asset are on disk.
+fn assets_present(site_root: &Path, options: &LeptosOptions) -> Result<(), String> {
+    let pkg = site_root.join(&*options.site_pkg_dir);
+    let bundle = fs::read_dir(&pkg)
+        .map_err(|e| format!("{}: {e}", pkg.display()))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            path.extension()?.to_str().map(str::to_string)
+        })
+        .collect::<Vec<_>>();
+    for extension in ["js", "wasm", "css"] {
+        if !bundle.iter().any(|found| found == extension) {
+            return Err(format!("no .{extension} file in {}", pkg.display()));
+        }
+    }
+
+    let missing = MANIFEST
+        .iter()
+        .map(|(path, _)| site_root.join(path.trim_start_matches("/assets/")))
+        .filter(|path| !path.is_file())
+        .collect::<Vec<_>>();
+    match missing.first() {
+        None => Ok(()),
+        Some(first) => Err(format!(
+            "{} assets missing, such as {}",
+            missing.len(),
+            first.display()
+        )),
+    }
+}
+
+fn no_store(mut response: actix_web::HttpResponseBuilder) -> actix_web::HttpResponseBuilder {
+    response.insert_header((header::CACHE_CONTROL, "no-store"));
+    response
+}
+
+/// Liveness: the process answers requests.
+#[get("/healthz")]
+pub async fn healthz() -> HttpResponse {
+    no_store(HttpResponse::Ok()).body("ok")
+}
+
+/// Readiness: content loaded, contact storage writable and assets present.
+/// 503 until then, with the name and outcome of each check.
+#[get("/readyz")]
+pub async fn readyz(
+    options: web::Data<LeptosOptions>,
+    contact: web::Data<ContactService>,
+) -> actix_web::Result<HttpResponse> {
+    let checks = web::block(move || {
+        let site_root = Path::new(&*options.site_root);
+        vec![
+            Check::new("content", content_loaded()),
+            Check::new("storage", contact.check_storage().map_err(|e| e.to_string())),
+            Check::new("assets", assets_present(site_root, &options)),
+        ]
+    })
+    .await?;
+    let ready = checks.iter().all(|check| check.ok);
+    let response = if ready {
+        HttpResponse::Ok()
+    } else {
+        HttpResponse::ServiceUnavailable()
+    };
+    Ok(no_store(response).json(Readiness { ready, checks }))
+}
+
+/// The running build, as JSON.
+#[get("/__version")]
+pub async fn version() -> HttpResponse {
+    no_store(HttpResponse::Ok()).json(BuildInfo::current())
+}
@@ -0,0 +1,99 @@
+//! Development live reload for content: a file watcher reloads the content
+//! store when something under `content/` changes, then tells open pages to
+//! reload through a server-sent event stream. No Rust rebuild involved.
+
+use std::path::{Component, Path};
+use std::sync::mpsc;
+use std::time::Duration;
+
+use actix_web::http::header;
+use actix_web::{get, web, HttpResponse};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::broadcast::{self, error::RecvError};
+
+use crate::content::store;
+
+/// Editors often write a file in several steps; wait for them to settle.
+const DEBOUNCE: Duration = Duration::from_millis(100);
+/// Comment sent on idle streams so proxies do not close them.
+const KEEP_ALIVE: Duration = Duration::from_secs(15);
+
+/// Inline script for the page shell, listening to [`events`]. Admin pages
+/// are left alone so an open editor keeps its form.
+pub const CLIENT_SCRIPT: &str = r#"if (!location.pathname.startsWith("/admin")) new EventSource("/__content-reload").onmessage = () => location.reload();"#;
+
+/// Content files only: editor drafts and temporary files do not change the site.
+fn is_relevant(path: &Path) -> bool {
+    let is_draft = path
+        .components()
+        .any(|c| c == Component::Normal(".drafts".as_ref()));
+    let is_content = matches!(path.extension().and_then(|e| e.to_str()), Some("md" | "toml"));
+    is_content && !is_draft
+}
+
+/// Shared by the workers through `web::Data`.
+pub struct LiveReload {
+    sender: broadcast::Sender<()>,
+    // Stops watching when dropped
+    _watcher: RecommendedWatcher,
+}
+
+impl LiveReload {
+    /// Starts watching `dir` on a background thread.
+    pub fn start(dir: &Path) -> notify::Result<Self> {
+        let (sender, _) = broadcast::channel(16);
+        let (events, changes) = mpsc::channel();
+        let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+            if let Ok(event) = event {
+                let changed = matches!(
+                    event.kind,
+                    EventKind::Create(_) | EventKind::Modify(_) | EventKind::Remove(_)
+                );
+                if changed && event.paths.iter().any(|p| is_relevant(p)) {
+                    let _ = events.send(());
+                }
+            }
+        })?;
+        watcher.watch(dir, RecursiveMode::Recursive)?;
+
+        let notify_pages = sender.clone();
+        std::thread::spawn(move || {
+            while changes.recv().is_ok() {
+                while changes.recv_timeout(DEBOUNCE).is_ok() {}
+                match store::reload() {
+                    Ok(()) => {
+                        tracing::info!("content changed, reloaded");
+                        // Nobody listening is fine
+                        let _ = notify_pages.send(());
+                    }
+                    // Keep the previous content until the file is fixed
+                    Err(e) => tracing::error!("content changed but failed to load: {e}"),
+                }
+            }
+        });
+
+        Ok(LiveReload {
+            sender,
+            _watcher: watcher,
+        })
+    }
+}
+
+/// Server-sent events: one `reload` message per content change.
+#[get("/__content-reload")]
+pub async fn events(reload: web::Data<LiveReload>) -> HttpResponse {
+    let stream = futures_util::stream::unfold(reload.sender.subscribe(), |mut changes| async move {
+        let chunk: &'static str = match tokio::time::timeout(KEEP_ALIVE, changes.recv()).await {
+            Ok(Ok(())) | Ok(Err(RecvError::Lagged(_))) => "data: reload\n\n",
+            Ok(Err(RecvError::Closed)) => return None,
+            Err(_) => ": keep-alive\n\n",
+        };
+        Some((Ok::<_, actix_web::Error>(web::Bytes::from_static(chunk.as_bytes())), changes))
+    });
+    HttpResponse::Ok()
+        .content_type("text/event-stream")
+        .insert_header((header::CACHE_CONTROL, "no-cache"))
+        // Keeps `Compress` from holding events back in its buffer
+        .insert_header((header::CONTENT_ENCODING, "identity"))
+        .streaming(stream)
+}
@@ -0,0 +1,111 @@
+//! Language negotiation for the page routes.
+//!
+//! The locale of a page is part of its URL (`/en/...`), so this only decides
+//! where a visitor lands: an explicit choice stored in the `lang` cookie wins,
+//! otherwise `Accept-Language` picks the language on the first visit.
+
+use actix_web::body::{EitherBody, MessageBody};
+use actix_web::cookie::{time::Duration, Cookie, SameSite};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{self, HeaderValue};
+use actix_web::http::Method;
+use actix_web::middleware::Next;
+use actix_web::{get, web, HttpRequest, HttpResponse};
+use serde::Deserialize;
+
+use crate::i18n::{self, Locale};
+
+/// Paths served outside the Leptos router, which have no localized version.
+/// `/__` is for internal endpoints such as the content reload stream.
+const SKIP_PREFIXES: [&str; 10] = [
+    "/api/", "/pkg/", "/assets/", "/og/", "/lang/", "/admin/", "/__", "/healthz", "/readyz",
+    "/metrics",
+];
+
+/// The locale a visitor asked for: the cookie first, then `Accept-Language`.
+fn preferred(req: &HttpRequest) -> Option<Locale> {
+    req.cookie(i18n::COOKIE)
+        .and_then(|cookie| Locale::from_tag(cookie.value()))
+        .or_else(|| {
+            req.headers()
+                .get(header::ACCEPT_LANGUAGE)
+                .and_then(|value| value.to_str().ok())
+                .and_then(i18n::negotiate)
+        })
+}
+
+fn is_page(path: &str) -> bool {
+    // Anything with an extension is a file (favicon.ico, sitemap.xml, feed.json...)
+    let is_file = path.rsplit('/').next().is_some_and(|last| last.contains('.'));
+    !is_file
+        && path != "/admin"
+        && !SKIP_PREFIXES.iter().any(|prefix| path.starts_with(prefix))
+}
+
+/// Redirects pages in the default locale to the visitor's preferred one.
+/// Register with `middleware::from_fn(locale::redirect)`.
+pub async fn redirect(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody>>, actix_web::Error> {
+    let path = req.path().to_string();
+    let negotiable = matches!(*req.method(), Method::GET | Method::HEAD)
+        && is_page(&path)
+        && Locale::from_path(&path) == Locale::default();
+    if !negotiable {
+        return Ok(next.call(req).await?.map_into_left_body());
+    }
+
+    let vary = HeaderValue::from_static("Accept-Language, Cookie");
+    let locale = preferred(req.request()).unwrap_or_default();
+    if locale != Locale::default() {
+        let mut location = locale.localize(&path);
+        if !req.query_string().is_empty() {
+            location = format!("{location}?{}", req.query_string());
+        }
+        let response = HttpResponse::Found()
+            .insert_header((header::LOCATION, location))
+            .insert_header((header::VARY, vary))
+            .finish();
+        return Ok(req.into_response(response).map_into_right_body());
+    }
+
+    let mut res = next.call(req).await?;
+    res.headers_mut().insert(header::VARY, vary);
+    Ok(res.map_into_left_body())
+}
+
+#[derive(Deserialize)]
+struct SwitchQuery {
+    next: Option<String>,
+}
+
+/// `/lang/{locale}?next=/path`: remembers the choice for a year and goes back
+/// to `next` (a site path; anything else falls back to the home page).
+#[get("/lang/{locale}")]
+pub async fn switch(
+    locale: web::Path<String>,
+    query: web::Query<SwitchQuery>,
+) -> HttpResponse {
+    let Some(locale) = Locale::from_tag(&locale) else {
+        return HttpResponse::NotFound().finish();
+    };
+    let next = query
+        .next
+        .as_deref()
+        .filter(|next| next.starts_with('/') && !next.starts_with("//") && !next.contains('\\'))
+        .map(|next| locale.localize(next))
+        .unwrap_or_else(|| locale.localize("/"));
+
+    let cookie = Cookie::build(i18n::COOKIE, locale.tag())
+        .path("/")
+        .max_age(Duration::days(365))
+        .same_site(SameSite::Lax)
+        .http_only(true)
+        .finish();
+    HttpResponse::SeeOther()
+        .cookie(cookie)
+        .insert_header((header::LOCATION, next))
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .finish()
+}
@@ -0,0 +1,571 @@
+//! Prometheus metrics at `/metrics`: requests and latency per route pattern,
+//! page rendering times, response sizes, static file hits, 404s, contact form
+//! submissions and process stats.
+//!
+//! Everything is counted in memory and rendered in the text exposition format
+//! on each scrape, so the figures start over when the process restarts. The
+//! endpoint is never public: it is served on its own address
+//! (`METRICS_ADDR`), meant to be reachable from the monitoring network only,
+//! or on the site's address behind a bearer token (`METRICS_TOKEN`).
+
+use std::collections::BTreeMap;
+use std::fmt;
+use std::io;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use actix_web::body::{BodySize, MessageBody};
+use actix_web::dev::{Server, ServiceRequest, ServiceResponse};
+use actix_web::http::{header, StatusCode};
+use actix_web::middleware::Next;
+use actix_web::{get, web, App, HttpRequest, HttpResponse, HttpServer};
+
+use super::auth::constant_time_eq;
+use super::health::BuildInfo;
+
+/// Upper bounds of the latency buckets, in seconds.
+const DURATION_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+/// Upper bounds of the response size buckets, in bytes.
+const SIZE_BUCKETS: [f64; 8] = [
+    256.0, 1024.0, 4096.0, 16384.0, 65536.0, 262144.0, 1048576.0, 4194304.0,
+];
+/// Distinct paths counted as 404s; later ones are counted together, so a
+/// scanner cannot grow the output without bound.
+const MAX_NOT_FOUND_PATHS: usize = 100;
+/// Longest 404 path kept, in characters.
+const MAX_PATH_LABEL: usize = 120;
+/// Route label of requests no service matched.
+const UNMATCHED: &str = "(unmatched)";
+/// Path label of the 404s past [`MAX_NOT_FOUND_PATHS`].
+const OTHER_PATHS: &str = "(other)";
+/// `USER_HZ`, the unit of the CPU times in `/proc`: 100 on the usual Linux
+/// platforms.
+#[cfg(target_os = "linux")]
+const CLOCK_TICKS: f64 = 100.0;
+const CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+#[derive(Clone, Debug, Default)]
+pub struct MetricsConfig {
+    /// Separate address serving `/metrics` and nothing else.
+    pub addr: Option<SocketAddr>,
+    /// Bearer token a scrape must send.
+    pub token: Option<String>,
+}
+
+impl MetricsConfig {
+    /// Metrics are off unless an address or a token is set.
+    pub fn is_enabled(&self) -> bool {
+        self.addr.is_some() || self.token.is_some()
+    }
+}
+
+/// Counts per bucket (not cumulative), plus the sum and count of the
+/// observed values.
+struct Histogram {
+    bounds: &'static [f64],
+    counts: Vec<u64>,
+    sum: f64,
+    count: u64,
+}
+
+impl Histogram {
+    fn new(bounds: &'static [f64]) -> Self {
+        Histogram {
+            bounds,
+            counts: vec![0; bounds.len()],
+            sum: 0.0,
+            count: 0,
+        }
+    }
+
+    fn observe(&mut self, value: f64) {
+        if let Some(i) = self.bounds.iter().position(|bound| value <= *bound) {
+            self.counts[i] += 1;
+        }
+        self.sum += value;
+        self.count += 1;
+    }
+}
+
+#[derive(Default)]
+struct Counts {
+    /// By route pattern, method and status.
+    requests: BTreeMap<(String, String, u16), u64>,
+    durations: BTreeMap<String, Histogram>,
+    sizes: BTreeMap<String, Histogram>,
+    renders: BTreeMap<String, Histogram>,
+    /// By directory (`pkg`/`assets`) and `Content-Encoding`.
+    static_hits: BTreeMap<(&'static str, String), u64>,
+    not_found: BTreeMap<String, u64>,
+    contact: BTreeMap<&'static str, u64>,
+}
+
+fn observe(series: &mut BTreeMap<String, Histogram>, key: &str, bounds: &'static [f64], value: f64) {
+    match series.get_mut(key) {
+        Some(histogram) => histogram.observe(value),
+        None => {
+            let mut histogram = Histogram::new(bounds);
+            histogram.observe(value);
+            series.insert(key.to_string(), histogram);
+        }
+    }
+}
+
+/// The metrics of the running server, shared by every worker through
+/// `web::Data`.
+pub struct Metrics {
+    config: MetricsConfig,
+    workers: usize,
+    started: SystemTime,
+    in_flight: AtomicI64,
+    counts: Mutex<Counts>,
+}
+
+impl Metrics {
+    pub fn new(config: MetricsConfig, workers: usize) -> Self {
+        Metrics {
+            config,
+            workers,
+            started: SystemTime::now(),
+            in_flight: AtomicI64::new(0),
+            counts: Mutex::default(),
+        }
+    }
+
+    pub fn config(&self) -> &MetricsConfig {
+        &self.config
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Counts> {
+        // Counters stay meaningful even if a panic interrupted an update
+        self.counts.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn observe(&self, res: &ServiceResponse<impl MessageBody>, elapsed: Duration) {
+        let req = res.request();
+        let route = route(req);
+        let status = res.status();
+        let size = match res.response().body().size() {
+            BodySize::Sized(bytes) => Some(bytes),
+            BodySize::None | BodySize::Stream => None,
+        };
+        let mut counts = self.lock();
+        *counts
+            .requests
+            .entry((route.clone(), req.method().to_string(), status.as_u16()))
+            .or_default() += 1;
+        observe(&mut counts.durations, &route, &DURATION_BUCKETS, elapsed.as_secs_f64());
+        if let Some(size) = size {
+            observe(&mut counts.sizes, &route, &SIZE_BUCKETS, size as f64);
+        }
+
+        let served = status.is_success() || status == StatusCode::NOT_MODIFIED;
+        if let (Some(dir), true) = (static_dir(req.path()), served) {
+            // Precompressed copies are sent by `compression::serve`, inside
+            // this middleware, so the encoding shows whether they were used
+            let encoding = res
+                .headers()
+                .get(header::CONTENT_ENCODING)
+                .and_then(|value| value.to_str().ok())
+                .unwrap_or("identity");
+            *counts.static_hits.entry((dir, encoding.to_string())).or_default() += 1;
+        }
+        if status == StatusCode::NOT_FOUND {
+            let path = req.path().chars().take(MAX_PATH_LABEL).collect::<String>();
+            let full = counts.not_found.len() >= MAX_NOT_FOUND_PATHS;
+            let key = if full && !counts.not_found.contains_key(&path) {
+                OTHER_PATHS.to_string()
+            } else {
+                path
+            };
+            *counts.not_found.entry(key).or_default() += 1;
+        }
+    }
+
+    /// Only a scrape with the token gets through, when there is one.
+    fn authorized(&self, req: &HttpRequest) -> bool {
+        let Some(token) = &self.config.token else {
+            return true;
+        };
+        req.headers()
+            .get(header::AUTHORIZATION)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.strip_prefix("Bearer "))
+            .is_some_and(|given| constant_time_eq(given.trim(), token))
+    }
+
+    /// The text exposition format.
+    fn render(&self) -> String {
+        let mut out = String::new();
+        let build = BuildInfo::current();
+        family(&mut out, "site_build_info", "gauge", "Version and commit of the running build.");
+        sample(
+            &mut out,
+            "site_build_info",
+            &[("version", build.version), ("git_sha", build.git_sha)],
+            1,
+        );
+
+        {
+            let counts = self.lock();
+            family(
+                &mut out,
+                "http_requests_total",
+                "counter",
+                "Requests answered, by route pattern, method and status.",
+            );
+            for ((route, method, status), count) in &counts.requests {
+                let status = status.to_string();
+                let labels = [("route", route.as_str()), ("method", method.as_str()), ("status", status.as_str())];
+                sample(&mut out, "http_requests_total", &labels, count);
+            }
+            histogram(
+                &mut out,
+                "http_request_duration_seconds",
+                "Time to answer a request, by route pattern.",
+                "route",
+                &counts.durations,
+            );
+            histogram(
+                &mut out,
+                "http_response_size_bytes",
+                "Size of the response bodies whose size is known, by route pattern.",
+                "route",
+                &counts.sizes,
+            );
+            histogram(
+                &mut out,
+                "ssr_render_duration_seconds",
+                "Time to render a page on the server, suspended parts included, by route pattern.",
+                "route",
+                &counts.renders,
+            );
+            family(
+                &mut out,
+                "static_file_hits_total",
+                "counter",
+                "Files served from pkg and assets, by directory and encoding.",
+            );
+            for ((dir, encoding), count) in &counts.static_hits {
+                let labels = [("dir", *dir), ("encoding", encoding.as_str())];
+                sample(&mut out, "static_file_hits_total", &labels, count);
+            }
+            family(&mut out, "http_not_found_total", "counter", "404 responses, by path.");
+            for (path, count) in &counts.not_found {
+                sample(&mut out, "http_not_found_total", &[("path", path.as_str())], count);
+            }
+            family(
+                &mut out,
+                "contact_submissions_total",
+                "counter",
+                "Contact form submissions, by outcome.",
+            );
+            for (outcome, count) in &counts.contact {
+                sample(&mut out, "contact_submissions_total", &[("outcome", *outcome)], count);
+            }
+        }
+
+        family(&mut out, "http_requests_in_flight", "gauge", "Requests being handled.");
+        sample(&mut out, "http_requests_in_flight", &[], self.in_flight.load(Ordering::Relaxed));
+        family(&mut out, "http_server_workers", "gauge", "Worker threads serving the site.");
+        sample(&mut out, "http_server_workers", &[], self.workers);
+        let started = self.started.duration_since(UNIX_EPOCH).unwrap_or_default();
+        family(
+            &mut out,
+            "process_start_time_seconds",
+            "gauge",
+            "Start time of the process since the Unix epoch.",
+        );
+        sample(&mut out, "process_start_time_seconds", &[], started.as_secs());
+
+        let process = process();
+        if let Some(bytes) = process.resident_bytes {
+            family(&mut out, "process_resident_memory_bytes", "gauge", "Resident memory size.");
+            sample(&mut out, "process_resident_memory_bytes", &[], bytes);
+        }
+        if let Some(seconds) = process.cpu_seconds {
+            family(&mut out, "process_cpu_seconds_total", "counter", "User and system CPU time.");
+            sample(&mut out, "process_cpu_seconds_total", &[], seconds);
+        }
+        if let Some(threads) = process.threads {
+            family(&mut out, "process_threads", "gauge", "Threads of the process.");
+            sample(&mut out, "process_threads", &[], threads);
+        }
+        if let Some(fds) = process.open_fds {
+            family(&mut out, "process_open_fds", "gauge", "Open file descriptors.");
+            sample(&mut out, "process_open_fds", &[], fds);
+        }
+        out
+    }
+}
+
+/// The route pattern the request matched, such as `/blog/{slug}`, so the
+/// labels stay few whatever the paths.
+fn route(req: &HttpRequest) -> String {
+    req.match_pattern().unwrap_or_else(|| UNMATCHED.to_string())
+}
+
+fn static_dir(path: &str) -> Option<&'static str> {
+    if path.starts_with("/pkg/") {
+        Some("pkg")
+    } else if path.starts_with("/assets/") {
+        Some("assets")
+    } else {
+        None
+    }
+}
+
+/// Counts a request as in flight while it lives.
+struct InFlight<'a>(&'a AtomicI64);
+
+impl<'a> InFlight<'a> {
+    fn enter(gauge: &'a AtomicI64) -> Self {
+        gauge.fetch_add(1, Ordering::Relaxed);
+        InFlight(gauge)
+    }
+}
+
+impl Drop for InFlight<'_> {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Counts every request, its time and response size under the route pattern
+/// it matched, plus static file hits and 404s. Register with
+/// `middleware::from_fn(metrics::record)`; does nothing when metrics are off.
+pub async fn record(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    let Some(metrics) = req.app_data::<web::Data<Metrics>>().cloned() else {
+        return next.call(req).await;
+    };
+    let start = Instant::now();
+    let _in_flight = InFlight::enter(&metrics.in_flight);
+    let res = next.call(req).await?;
+    metrics.observe(&res, start.elapsed());
+    Ok(res)
+}
+
+/// Adds the time taken to render a page, shell and suspended parts.
+pub fn record_render(req: &HttpRequest, duration: Duration) {
+    if let Some(metrics) = req.app_data::<web::Data<Metrics>>() {
+        let route = route(req);
+        observe(&mut metrics.lock().renders, &route, &DURATION_BUCKETS, duration.as_secs_f64());
+    }
+}
+
+/// Counts a contact form submission; `outcome` is a fixed word such as
+/// `stored` or `rejected`.
+pub fn count_contact(req: &HttpRequest, outcome: &'static str) {
+    if let Some(metrics) = req.app_data::<web::Data<Metrics>>() {
+        *metrics.lock().contact.entry(outcome).or_default() += 1;
+    }
+}
+
+/// The metrics, for Prometheus. 401 without the token when one is set.
+#[get("/metrics")]
+pub async fn scrape(req: HttpRequest, metrics: web::Data<Metrics>) -> HttpResponse {
+    if !metrics.authorized(&req) {
+        return HttpResponse::Unauthorized()
+            .insert_header((header::WWW_AUTHENTICATE, "Bearer"))
+            .finish();
+    }
+    HttpResponse::Ok()
+        .insert_header((header::CONTENT_TYPE, CONTENT_TYPE))
+        .insert_header((header::CACHE_CONTROL, "no-store"))
+        .body(metrics.render())
+}
+
+/// The server for `METRICS_ADDR`: one worker answering `/metrics` only.
+/// Signals are left to `systemd::stop_on_signal`, as for the site.
+pub fn server(metrics: web::Data<Metrics>, addr: SocketAddr) -> io::Result<Server> {
+    Ok(HttpServer::new(move || App::new().app_data(metrics.clone()).service(scrape))
+        .workers(1)
+        .disable_signals()
+        .bind(addr)?
+        .run())
+}
+
+fn family(out: &mut String, name: &str, kind: &str, help: &str) {
+    out.push_str(&format!("# HELP {name} {help}\n# TYPE {name} {kind}\n"));
+}
+
+fn sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: impl fmt::Display) {
+    let labels = labels
+        .iter()
+        .map(|(label, value)| format!("{label}=\"{}\"", escape(value)))
+        .collect::<Vec<_>>();
+    if labels.is_empty() {
+        out.push_str(&format!("{name} {value}\n"));
+    } else {
+        out.push_str(&format!("{name}{{{}}} {value}\n", labels.join(",")));
+    }
+}
+
+fn histogram(out: &mut String, name: &str, help: &str, label: &str, series: &BTreeMap<String, Histogram>) {
+    family(out, name, "histogram", help);
+    for (key, histogram) in series {
+        let bucket = format!("{name}_bucket");
+        let mut cumulative = 0;
+        for (bound, count) in histogram.bounds.iter().zip(&histogram.counts) {
+            cumulative += count;
+            let le = bound.to_string();
+            sample(out, &bucket, &[(label, key.as_str()), ("le", le.as_str())], cumulative);
+        }
+        sample(out, &bucket, &[(label, key.as_str()), ("le", "+Inf")], histogram.count);
+        sample(out, &format!("{name}_sum"), &[(label, key.as_str())], histogram.sum);
+        sample(out, &format!("{name}_count"), &[(label, key.as_str())], histogram.count);
+    }
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+/// Figures about this process, when the platform tells.
+#[derive(Default)]
+struct Process {
+    resident_bytes: Option<u64>,
+    cpu_seconds: Option<f64>,
+    threads: Option<u64>,
+    open_fds: Option<u64>,
+}
+
+#[cfg(target_os = "linux")]
+fn process() -> Process {
+    use std::fs;
+
+    let status = fs::read_to_string("/proc/self/status").unwrap_or_default();
+    let field = |name: &str| {
+        status
+            .lines()
+            .find_map(|line| line.strip_prefix(name))
+            .and_then(|rest| rest.split_whitespace().next()?.parse::<u64>().ok())
+    };
+    // The fields after the command name, which is in parentheses and may
+    // hold spaces; utime and stime are the 14th and 15th of stat(5)
+    let stat = fs::read_to_string("/proc/self/stat").unwrap_or_default();
+    let cpu_seconds = stat.rsplit_once(')').and_then(|(_, rest)| {
+        let fields = rest.split_whitespace().collect::<Vec<_>>();
+        let ticks = fields.get(11)?.parse::<u64>().ok()? + fields.get(12)?.parse::<u64>().ok()?;
+        Some(ticks as f64 / CLOCK_TICKS)
+    });
+    Process {
+        resident_bytes: field("VmRSS:").map(|kib| kib * 1024),
+        cpu_seconds,
+        threads: field("Threads:"),
+        open_fds: fs::read_dir("/proc/self/fd").ok().map(|dir| dir.count() as u64),
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn process() -> Process {
+    Process::default()
+}
+
+#[cfg(test)]
+mod tests {
+    use actix_web::test::TestRequest;
+
+    use super::*;
+
+    fn metrics(token: Option<&str>) -> Metrics {
+        Metrics::new(
+            MetricsConfig {
+                addr: None,
+                token: token.map(str::to_string),
+            },
+            1,
+        )
+    }
+
+    fn not_found(metrics: &Metrics, path: &str) {
+        let res = TestRequest::with_uri(path)
+            .to_srv_request()
+            .into_response(HttpResponse::NotFound().finish());
+        metrics.observe(&res, Duration::from_millis(1));
+    }
+
+    #[test]
+    fn histogram_buckets_are_cumulative() {
+        let mut series = BTreeMap::new();
+        for value in [0.5, 1.5, 1.5, 10.0] {
+            observe(&mut series, "/blog", &[1.0, 2.0, 5.0], value);
+        }
+        let mut out = String::new();
+        histogram(&mut out, "latency", "Help.", "route", &series);
+        assert_eq!(
+            out,
+            "# HELP latency Help.\n# TYPE latency histogram\n\
+             latency_bucket{route=\"/blog\",le=\"1\"} 1\n\
+             latency_bucket{route=\"/blog\",le=\"2\"} 3\n\
+             latency_bucket{route=\"/blog\",le=\"5\"} 3\n\
+             latency_bucket{route=\"/blog\",le=\"+Inf\"} 4\n\
+             latency_sum{route=\"/blog\"} 13.5\n\
+             latency_count{route=\"/blog\"} 4\n"
+        );
+    }
+
+    #[test]
+    fn label_values_are_escaped() {
+        assert_eq!(escape("plain"), "plain");
+        assert_eq!(escape(r#"a\b"c"#), r#"a\\b\"c"#);
+        assert_eq!(escape("line\nbreak"), "line\\nbreak");
+        let mut out = String::new();
+        sample(&mut out, "hits", &[("path", "/\"x\"\n")], 1);
+        assert_eq!(out, "hits{path=\"/\\\"x\\\"\\n\"} 1\n");
+    }
+
+    #[test]
+    fn not_found_paths_are_capped() {
+        let metrics = metrics(None);
+        for i in 0..MAX_NOT_FOUND_PATHS {
+            not_found(&metrics, &format!("/missing-{i}"));
+        }
+        not_found(&metrics, "/one-too-many");
+        not_found(&metrics, "/another");
+        // Paths already listed keep their own count
+        not_found(&metrics, "/missing-0");
+
+        let counts = metrics.lock();
+        assert_eq!(counts.not_found.len(), MAX_NOT_FOUND_PATHS + 1);
+        assert_eq!(counts.not_found.get(OTHER_PATHS), Some(&2));
+        assert_eq!(counts.not_found.get("/missing-0"), Some(&2));
+        assert!(!counts.not_found.contains_key("/one-too-many"));
+    }
+
+    #[test]
+    fn long_not_found_paths_are_cut() {
+        let metrics = metrics(None);
+        not_found(&metrics, &format!("/{}", "a".repeat(500)));
+        let counts = metrics.lock();
+        assert!(counts.not_found.keys().all(|path| path.chars().count() == MAX_PATH_LABEL));
+    }
+
+    #[test]
+    fn only_the_bearer_token_is_authorized() {
+        let with = |value: &str| {
+            TestRequest::default()
+                .insert_header((header::AUTHORIZATION, value))
+                .to_http_request()
+        };
+        let metrics = metrics(Some("secret"));
+        assert!(metrics.authorized(&with("Bearer secret")));
+        assert!(!metrics.authorized(&with("Bearer other")));
+        assert!(!metrics.authorized(&with("Bearer ")));
+        assert!(!metrics.authorized(&with("secret")));
+        assert!(!metrics.authorized(&with("Basic secret")));
+        assert!(!metrics.authorized(&with("bearer secret")));
+        assert!(!metrics.authorized(&TestRequest::default().to_http_request()));
+
+        // Without a token the separate address is the only protection
+        assert!(self::metrics(None).authorized(&TestRequest::default().to_http_request()));
+    }
+}
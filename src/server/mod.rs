@@ -0,0 +1,128 @@
+//! Server-only HTTP endpoints that live outside the Leptos router.
+
+pub mod antispam;
+pub mod auth;
+pub mod caching;
+pub mod compression;
+pub mod config;
+pub mod contact;
+pub mod editor;
+pub mod export;
+pub mod feeds;
+pub mod health;
+pub mod live_reload;
+pub mod locale;
+pub mod metrics;
+pub mod og_image;
+pub mod security;
+pub mod site;
+pub mod sitemap;
+pub mod systemd;
+pub mod telemetry;
+
+use std::net::{IpAddr, SocketAddr};
+
+/// Public base URL of the site, without a trailing slash. Used wherever an
+/// absolute link is required (feeds, sitemap...).
+#[derive(Clone, Debug)]
+pub struct SiteUrl(String);
+
+impl SiteUrl {
+    pub fn new(url: impl Into<String>) -> Self {
+        SiteUrl(url.into().trim_end_matches('/').to_string())
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Absolute URL for a site path such as `/blog/post`.
+    pub fn join(&self, path: &str) -> String {
+        format!("{}/{}", self.0, path.trim_start_matches('/'))
+    }
+
+    /// Rewrites root-relative `href`/`src` attributes in rendered HTML so it
+    /// can be read outside the site (feed readers, email...).
+    pub fn absolutize_html(&self, html: &str) -> String {
+        ["href", "src"]
+            .into_iter()
+            .fold(html.to_string(), |html, attribute| self.absolutize_attribute(&html, attribute))
+    }
+
+    fn absolutize_attribute(&self, html: &str, attribute: &str) -> String {
+        let marker = format!("{attribute}=\"");
+        let mut out = String::with_capacity(html.len());
+        let mut rest = html;
+        while let Some(start) = rest.find(&marker) {
+            let (head, tail) = rest.split_at(start + marker.len());
+            out.push_str(head);
+            // `//host/path` is protocol-relative, so already absolute
+            if tail.starts_with('/') && !tail.starts_with("//") {
+                out.push_str(&self.0);
+            }
+            rest = tail;
+        }
+        out.push_str(rest);
+        out
+    }
+}
+
+/// Escapes text for use in XML content and attribute values.
+pub fn xml_escape(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Address of the client. With `trust_proxy` it comes from `Forwarded` /
+/// `X-Forwarded-For`, which only a reverse proxy in front should set.
+pub fn client_ip(req: &actix_web::HttpRequest, trust_proxy: bool) -> Option<IpAddr> {
+    if !trust_proxy {
+        return req.peer_addr().map(|addr| addr.ip());
+    }
+    let info = req.connection_info();
+    let addr = info.realip_remote_addr()?;
+    // May carry a port, and IPv6 may be bracketed
+    addr.parse()
+        .ok()
+        .or_else(|| addr.parse::<SocketAddr>().ok().map(|a| a.ip()))
+        .or_else(|| addr.trim_matches(['[', ']']).parse().ok())
+}
+
+/// 32 bytes from the OS random number generator.
+pub fn random_bytes() -> Vec<u8> {
+    let mut bytes = vec![0; 32];
+    getrandom::getrandom(&mut bytes).expect("OS random number generator");
+    bytes
+}
+
+/// Lowercase hex encoding.
+pub fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{b:02x}")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn absolutizes_root_relative_links_only() {
+        let site = SiteUrl::new("https://example.com/");
+        assert_eq!(
+            site.absolutize_html(r#"<a href="/blog">Blog</a><img src="/assets/a.png">"#),
+            r#"<a href="https://example.com/blog">Blog</a><img src="https://example.com/assets/a.png">"#
+        );
+        // Protocol-relative, absolute and relative URLs are left alone
+        let untouched = r#"<img src="//cdn.example/x.png"><a href="https://other.example/">o</a><a href="post">p</a>"#;
+        assert_eq!(site.absolutize_html(untouched), untouched);
+    }
+}
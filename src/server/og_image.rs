@@ -0,0 +1,276 @@
+//! Open Graph preview images at `/og/{kind}/{slug}.png`.
+//!
+//! Cards are drawn in pure Rust (no headless browser) with the site's dark
+//! palette, the "NA." wordmark and the page title in Science Gothic, the
+//! site's typeface, then cached on disk. The font is built into the binary,
+//! so cards render offline and in exports.
+
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use ab_glyph::{FontVec, PxScale, VariableFont};
+use actix_web::http::header;
+use actix_web::{get, web, HttpResponse};
+use image::{Rgba, RgbaImage};
+use imageproc::drawing::{draw_text_mut, text_size};
+use serde::Deserialize;
+use sha2::{Digest, Sha256};
+
+use super::hex;
+use crate::content::slugify;
+use crate::content::store;
+use crate::i18n::Locale;
+
+pub const WIDTH: u32 = 1200;
+pub const HEIGHT: u32 = 630;
+
+const BACKGROUND: [u8; 3] = [0x0a, 0x0a, 0x0a];
+const GLOW: [u8; 3] = [0x3b, 0x82, 0xf6];
+const WHITE: Rgba<u8> = Rgba([0xff, 0xff, 0xff, 0xff]);
+const BLUE: Rgba<u8> = Rgba([0x60, 0xa5, 0xfa, 0xff]);
+const GRAY: Rgba<u8> = Rgba([0x6b, 0x72, 0x80, 0xff]);
+
+const MARGIN: i32 = 80;
+const TITLE_SIZE: f32 = 76.0;
+const TITLE_LINE_HEIGHT: i32 = 88;
+const TITLE_MAX_LINES: usize = 3;
+
+/// The card font, `fonts/ScienceGothic.ttf` (SIL Open Font License, see
+/// `fonts/OFL.txt`). Kept out of `assets/` so it is not served.
+pub const FONT: &[u8] = include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/fonts/ScienceGothic.ttf"));
+
+/// Part of every cache key. Bump it when the font or the layout changes, so
+/// cached cards are drawn again.
+const CARD_VERSION: &str = "science-gothic-1";
+
+/// Renderer state shared by the workers.
+#[derive(Clone)]
+pub struct OgImages {
+    font: Arc<FontVec>,
+    cache_dir: PathBuf,
+    /// Shown in the card footer, e.g. `nicolasalmino.com`.
+    footer: String,
+}
+
+impl OgImages {
+    /// Draws with `font`, a TrueType/OpenType file such as [`FONT`]; variable
+    /// fonts are set to weight 700.
+    pub fn new(
+        font: &[u8],
+        cache_dir: impl Into<PathBuf>,
+        footer: impl Into<String>,
+    ) -> Result<Self, String> {
+        let mut font = FontVec::try_from_vec(font.to_vec()).map_err(|e| format!("invalid font: {e}"))?;
+        font.set_variation(b"wght", 700.0);
+        Ok(OgImages {
+            font: Arc::new(font),
+            cache_dir: cache_dir.into(),
+            footer: footer.into(),
+        })
+    }
+
+    /// Cache file of a card. The name includes a hash of the text, so edits
+    /// produce a new card, and [`CARD_VERSION`]; SHA-256 keeps it the same
+    /// across Rust versions.
+    fn cache_file(&self, kind: &str, slug: &str, label: &str, title: &str) -> PathBuf {
+        let mut hasher = Sha256::new();
+        for part in [CARD_VERSION, label, title, &self.footer] {
+            hasher.update(part.as_bytes());
+            hasher.update([0]);
+        }
+        let hash = hex(&hasher.finalize()[..8]);
+        self.cache_dir
+            .join(format!("{}-{}-{hash}.png", slugify(kind), slugify(slug)))
+    }
+}
+
+/// Label and title drawn on the card for a `kind`/`slug` pair, or `None` when
+/// there is no such page.
+fn card_text(kind: &str, slug: &str, locale: Locale) -> Option<(String, String)> {
+    let content = store::get_localized(locale).ok()?;
+    let t = locale.t();
+    match kind {
+        "pagina" => match slug {
+            "home" => Some((content.profile.title.clone(), content.profile.name.clone())),
+            "blog" => Some(("BLOG".to_string(), t.og_blog.to_string())),
+            _ => None,
+        },
+        "projeto" => content
+            .projects
+            .iter()
+            .find(|p| p.slug == slug)
+            .map(|p| (t.og_project.to_string(), p.title.clone())),
+        "blog" => content
+            .published_posts()
+            .find(|p| p.slug == slug)
+            .map(|p| ("BLOG".to_string(), p.title.clone())),
+        "tag" => content
+            .published_posts()
+            .flat_map(|p| p.tags.iter())
+            .find(|t| slugify(t) == slug)
+            .map(|t| ("BLOG".to_string(), format!("#{t}"))),
+        _ => None,
+    }
+}
+
+/// Greedy word wrap; the last line gets an ellipsis when the text does not fit.
+fn wrap(
+    font: &FontVec,
+    scale: PxScale,
+    text: &str,
+    max_width: u32,
+    max_lines: usize,
+) -> Vec<String> {
+    let fits = |line: &str| text_size(scale, font, line).0 <= max_width;
+    let mut lines: Vec<String> = Vec::new();
+    let mut current = String::new();
+
+    for word in text.split_whitespace() {
+        let candidate = if current.is_empty() {
+            word.to_string()
+        } else {
+            format!("{current} {word}")
+        };
+        if fits(&candidate) || current.is_empty() {
+            current = candidate;
+        } else {
+            lines.push(std::mem::replace(&mut current, word.to_string()));
+        }
+    }
+    if !current.is_empty() {
+        lines.push(current);
+    }
+
+    if lines.len() > max_lines {
+        lines.truncate(max_lines);
+        let last = lines.last_mut().expect("max_lines > 0");
+        while !last.is_empty() && !fits(&format!("{last}…")) {
+            last.pop();
+        }
+        last.push('…');
+    }
+    lines
+}
+
+fn render(font: &FontVec, label: &str, title: &str, footer: &str) -> RgbaImage {
+    let mut img = RgbaImage::new(WIDTH, HEIGHT);
+
+    // Background: grid pattern plus a blue glow in the top right, as in the hero
+    let (cx, cy, radius) = (WIDTH as f32 * 0.8, HEIGHT as f32 * 0.1, 750.0_f32);
+    for (x, y, pixel) in img.enumerate_pixels_mut() {
+        let dist = ((x as f32 - cx).powi(2) + (y as f32 - cy).powi(2)).sqrt();
+        let glow = (1.0 - dist / radius).max(0.0) * 0.22;
+        let grid = if x % 50 == 0 || y % 50 == 0 {
+            0.04
+        } else {
+            0.0
+        };
+        let channel = |base: u8, tint: u8| {
+            let value = base as f32 * (1.0 - glow) + tint as f32 * glow + 255.0 * grid;
+            value.min(255.0) as u8
+        };
+        *pixel = Rgba([
+            channel(BACKGROUND[0], GLOW[0]),
+            channel(BACKGROUND[1], GLOW[1]),
+            channel(BACKGROUND[2], GLOW[2]),
+            0xff,
+        ]);
+    }
+
+    draw_text_mut(
+        &mut img,
+        WHITE,
+        MARGIN,
+        64,
+        PxScale::from(56.0),
+        font,
+        "NA.",
+    );
+
+    let title_scale = PxScale::from(TITLE_SIZE);
+    let lines = wrap(
+        font,
+        title_scale,
+        title,
+        WIDTH - 2 * MARGIN as u32,
+        TITLE_MAX_LINES,
+    );
+    // Title block sits above the footer, growing upwards
+    let mut y = HEIGHT as i32 - 130 - lines.len() as i32 * TITLE_LINE_HEIGHT;
+    draw_text_mut(
+        &mut img,
+        BLUE,
+        MARGIN,
+        y - 56,
+        PxScale::from(30.0),
+        font,
+        &label.to_uppercase(),
+    );
+    for line in &lines {
+        draw_text_mut(&mut img, WHITE, MARGIN, y, title_scale, font, line);
+        y += TITLE_LINE_HEIGHT;
+    }
+
+    draw_text_mut(
+        &mut img,
+        GRAY,
+        MARGIN,
+        HEIGHT as i32 - 80,
+        PxScale::from(28.0),
+        font,
+        footer,
+    );
+    img
+}
+
+fn encode_png(img: &RgbaImage) -> Result<Vec<u8>, image::ImageError> {
+    let mut png = Vec::new();
+    img.write_to(&mut std::io::Cursor::new(&mut png), image::ImageFormat::Png)?;
+    Ok(png)
+}
+
+#[derive(Deserialize)]
+struct OgQuery {
+    lang: Option<String>,
+}
+
+#[get("/og/{kind}/{slug}.png")]
+pub async fn og_image(
+    og: web::Data<OgImages>,
+    path: web::Path<(String, String)>,
+    query: web::Query<OgQuery>,
+) -> actix_web::Result<HttpResponse> {
+    let (kind, slug) = path.into_inner();
+    let locale = query
+        .lang
+        .as_deref()
+        .and_then(Locale::from_tag)
+        .unwrap_or_default();
+    let Some((label, title)) = card_text(&kind, &slug, locale) else {
+        return Ok(HttpResponse::NotFound().finish());
+    };
+    let file = og.cache_file(&kind, &slug, &label, &title);
+
+    let og = og.into_inner();
+    let font = og.font.clone();
+    let png = web::block(move || -> std::io::Result<Vec<u8>> {
+        if let Ok(png) = std::fs::read(&file) {
+            return Ok(png);
+        }
+        let png = encode_png(&render(&font, &label, &title, &og.footer))
+            .map_err(std::io::Error::other)?;
+        // Write to a temporary file first so concurrent requests never read a partial image
+        std::fs::create_dir_all(&og.cache_dir)?;
+        let tmp = file.with_extension("png.tmp");
+        std::fs::write(&tmp, &png)?;
+        std::fs::rename(&tmp, &file)?;
+        Ok(png)
+    })
+    .await?
+    .map_err(actix_web::error::ErrorInternalServerError)?;
+
+    Ok(HttpResponse::Ok()
+        .content_type("image/png")
+        .insert_header((header::CACHE_CONTROL, "public, max-age=86400"))
+        .body(png))
+}
@@ -0,0 +1,179 @@
+//! Security headers. Pages get a Content-Security-Policy that only runs
+//! scripts carrying the per-request nonce (see [`set_policy`]); every
+//! response gets HSTS, `nosniff`, `Referrer-Policy` and `Permissions-Policy`.
+
+use actix_web::body::MessageBody;
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{self, HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{post, web, HttpRequest, HttpResponse};
+use leptos::prelude::use_context;
+use leptos_actix::ResponseOptions;
+use std::time::Duration;
+
+use super::antispam::RateLimit;
+use super::client_ip;
+
+/// Longest report logged, so a noisy client cannot flood the log.
+const MAX_REPORT_LOG: usize = 2048;
+/// Reports logged per client address and window; the rest are dropped.
+const MAX_REPORTS: usize = 10;
+const REPORT_WINDOW: Duration = Duration::from_secs(60);
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CspMode {
+    #[default]
+    Enforce,
+    /// Violations are only reported to `/csp-report`, for trying out changes.
+    ReportOnly,
+    Off,
+}
+
+impl CspMode {
+    /// The accepted values, for error messages.
+    pub const EXPECTED: &str = "enforce, report-only or off";
+}
+
+impl std::str::FromStr for CspMode {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "enforce" => Ok(CspMode::Enforce),
+            "report-only" => Ok(CspMode::ReportOnly),
+            "off" => Ok(CspMode::Off),
+            _ => Err(format!("expected {}", CspMode::EXPECTED)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct SecurityConfig {
+    pub csp: CspMode,
+    /// Let the page open a WebSocket to any host, for `cargo leptos watch`.
+    pub dev: bool,
+    /// `Strict-Transport-Security` value; empty leaves it out.
+    pub hsts: String,
+    pub referrer_policy: String,
+    pub permissions_policy: String,
+    /// Limit `/csp-report` by the address from `Forwarded` /
+    /// `X-Forwarded-For`. Only enable behind a reverse proxy that sets them.
+    pub trust_proxy: bool,
+}
+
+impl Default for SecurityConfig {
+    fn default() -> Self {
+        SecurityConfig {
+            csp: CspMode::Enforce,
+            dev: false,
+            hsts: "max-age=31536000".to_string(),
+            referrer_policy: "strict-origin-when-cross-origin".to_string(),
+            permissions_policy: "camera=(), microphone=(), geolocation=(), payment=(), usb=()"
+                .to_string(),
+            trust_proxy: false,
+        }
+    }
+}
+
+impl SecurityConfig {
+    /// The policy for a page whose inline scripts carry `nonce`. WASM needs
+    /// `'wasm-unsafe-eval'`, and components set `style` attributes.
+    pub fn policy(&self, nonce: &str) -> String {
+        let connect = if self.dev { "'self' ws: wss:" } else { "'self'" };
+        [
+            "default-src 'self'".to_string(),
+            format!("script-src 'self' 'nonce-{nonce}' 'wasm-unsafe-eval'"),
+            format!("style-src 'self' 'nonce-{nonce}' https://fonts.googleapis.com"),
+            "style-src-attr 'unsafe-inline'".to_string(),
+            "font-src 'self' https://fonts.gstatic.com".to_string(),
+            "img-src 'self' data: https:".to_string(),
+            "media-src 'self' https:".to_string(),
+            format!("connect-src {connect}"),
+            "object-src 'none'".to_string(),
+            "base-uri 'self'".to_string(),
+            "form-action 'self'".to_string(),
+            "frame-ancestors 'none'".to_string(),
+            "report-uri /csp-report".to_string(),
+        ]
+        .join("; ")
+    }
+
+    fn policy_header(&self) -> Option<HeaderName> {
+        match self.csp {
+            CspMode::Enforce => Some(header::CONTENT_SECURITY_POLICY),
+            CspMode::ReportOnly => Some(header::CONTENT_SECURITY_POLICY_REPORT_ONLY),
+            CspMode::Off => None,
+        }
+    }
+}
+
+/// Sends the page's policy through Leptos' `ResponseOptions`. Called by the
+/// page shell, which provides the nonce.
+pub fn set_policy(config: &SecurityConfig, nonce: &str) {
+    let (Some(name), Some(response)) = (config.policy_header(), use_context::<ResponseOptions>())
+    else {
+        return;
+    };
+    if let Ok(value) = HeaderValue::from_str(&config.policy(nonce)) {
+        response.insert_header(name, value);
+    }
+}
+
+/// Adds the headers every response gets. Register with
+/// `middleware::from_fn(security::headers)`.
+pub async fn headers(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    let config = req.app_data::<web::Data<SecurityConfig>>().cloned();
+    let mut res = next.call(req).await?;
+    let Some(config) = config else {
+        return Ok(res);
+    };
+
+    let headers = res.headers_mut();
+    headers.insert(header::X_CONTENT_TYPE_OPTIONS, HeaderValue::from_static("nosniff"));
+    for (name, value) in [
+        (header::STRICT_TRANSPORT_SECURITY, &config.hsts),
+        (header::REFERRER_POLICY, &config.referrer_policy),
+        (HeaderName::from_static("permissions-policy"), &config.permissions_policy),
+    ] {
+        if let Ok(value) = HeaderValue::from_str(value) {
+            if !value.is_empty() {
+                headers.insert(name, value);
+            }
+        }
+    }
+    Ok(res)
+}
+
+/// Counts CSP reports per client, shared by the workers through `web::Data`.
+pub struct CspReports {
+    limit: RateLimit,
+    trust_proxy: bool,
+}
+
+impl CspReports {
+    pub fn new(config: &SecurityConfig) -> Self {
+        CspReports {
+            limit: RateLimit::new(MAX_REPORTS, REPORT_WINDOW),
+            trust_proxy: config.trust_proxy,
+        }
+    }
+}
+
+/// Collects the violation reports browsers send to `report-uri`, into the
+/// server log. Past [`MAX_REPORTS`] a minute, a client's reports are dropped.
+#[post("/csp-report")]
+pub async fn report(req: HttpRequest, reports: web::Data<CspReports>, body: web::Bytes) -> HttpResponse {
+    if !reports.limit.check(client_ip(&req, reports.trust_proxy)) {
+        return HttpResponse::TooManyRequests().finish();
+    }
+    let report = String::from_utf8_lossy(&body);
+    let report = match report.char_indices().nth(MAX_REPORT_LOG) {
+        Some((end, _)) => &report[..end],
+        None => &report,
+    };
+    tracing::warn!(target: "csp", "violation: {}", report.trim());
+    HttpResponse::NoContent().finish()
+}
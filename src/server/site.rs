@@ -0,0 +1,162 @@
+//! Everything the site serves, registered in one place so the HTTP server and
+//! the static export run the exact same pipeline.
+
+use actix_files::Files;
+use actix_web::web;
+use leptos::config::LeptosOptions;
+use leptos::nonce::{provide_nonce, use_nonce};
+use leptos::prelude::*;
+use leptos_actix::{ActixRouteListing, LeptosRoutes};
+use leptos_meta::{HashedStylesheet, MetaTags};
+
+use super::{
+    antispam, auth, contact, editor, export, feeds, health, live_reload, locale, metrics, og_image, security,
+    sitemap, telemetry, SiteUrl,
+};
+use crate::app::App;
+use crate::components::page_meta::SiteMeta;
+
+/// Shared state built once at startup; cloned into every worker.
+#[derive(Clone)]
+pub struct Site {
+    pub leptos_options: LeptosOptions,
+    /// Routes of the Leptos app, from `generate_route_list`.
+    pub routes: Vec<ActixRouteListing>,
+    pub site_url: SiteUrl,
+    pub feed_config: feeds::FeedConfig,
+    pub robots_config: sitemap::RobotsConfig,
+    pub og_images: og_image::OgImages,
+    pub security: security::SecurityConfig,
+    pub log: telemetry::LogConfig,
+    pub log_level: web::Data<telemetry::LogLevel>,
+    pub contact: web::Data<contact::ContactService>,
+    pub antispam: web::Data<antispam::Antispam>,
+    pub csp_reports: web::Data<security::CspReports>,
+    pub auth: web::Data<auth::Auth>,
+    pub editor: web::Data<editor::Editor>,
+    pub live_reload: Option<web::Data<live_reload::LiveReload>>,
+    /// Off unless `METRICS_ADDR` or `METRICS_TOKEN` is set.
+    pub metrics: Option<web::Data<metrics::Metrics>>,
+    /// Pages are being written to files rather than served.
+    pub static_export: bool,
+}
+
+impl Site {
+    /// Registers the services and shared state. `ServiceConfig` cannot take
+    /// middleware, so callers wrap the app with [`locale::redirect`].
+    pub fn configure(&self, cfg: &mut web::ServiceConfig) {
+        let site_root = self.leptos_options.site_root.to_string();
+
+        cfg.app_data(web::Data::new(self.leptos_options.clone()))
+            .app_data(web::Data::new(self.site_url.clone()))
+            .app_data(web::Data::new(self.feed_config.clone()))
+            .app_data(web::Data::new(sitemap::SitemapRoutes::new(
+                self.routes.iter().map(|route| route.path()),
+            )))
+            .app_data(web::Data::new(self.robots_config.clone()))
+            .app_data(web::Data::new(self.og_images.clone()))
+            .app_data(web::Data::new(self.security.clone()))
+            .app_data(web::Data::new(self.log.clone()))
+            .app_data(self.log_level.clone())
+            .app_data(self.contact.clone())
+            .app_data(self.antispam.clone())
+            .app_data(self.csp_reports.clone())
+            .app_data(self.auth.clone())
+            .app_data(self.editor.clone());
+
+        // serve JS/WASM/CSS from `pkg`
+        cfg.service(Files::new("/pkg", format!("{site_root}/pkg")))
+            // serve other assets from the `assets` directory
+            .service(Files::new("/assets", &site_root))
+            // serve the favicon from /favicon.ico
+            .service(favicon)
+            // liveness, readiness and build info
+            .service(health::healthz)
+            .service(health::readyz)
+            .service(health::version)
+            // server functions (content API)
+            .route("/api/{tail:.*}", leptos_actix::handle_server_fns())
+            // RSS, Atom and JSON feeds
+            .service(feeds::rss)
+            .service(feeds::atom)
+            .service(feeds::json)
+            // sitemap.xml and robots.txt
+            .service(sitemap::sitemap)
+            .service(sitemap::robots)
+            // generated Open Graph images
+            .service(og_image::og_image)
+            // language switcher
+            .service(locale::switch)
+            // Content-Security-Policy violation reports
+            .service(security::report);
+        // content live reload stream
+        if let Some(reload) = &self.live_reload {
+            cfg.app_data(reload.clone()).service(live_reload::events);
+        }
+        // Prometheus metrics; with METRICS_ADDR they are served there instead
+        if let Some(metrics) = &self.metrics {
+            cfg.app_data(metrics.clone());
+            if metrics.config().addr.is_none() {
+                cfg.service(metrics::scrape);
+            }
+        }
+
+        // Last: the app's fallback route matches any path
+        cfg.leptos_routes(self.routes.clone(), self.shell());
+    }
+
+    /// The HTML document around [`App`].
+    fn shell(&self) -> impl Fn() -> AnyView + Clone + Send + 'static {
+        let leptos_options = self.leptos_options.clone();
+        let watch_content = self.live_reload.is_some();
+        let static_export = self.static_export;
+        let security = self.security.clone();
+        let site_meta = SiteMeta {
+            base_url: self.site_url.as_str().to_string(),
+            static_export,
+            ..SiteMeta::default()
+        };
+        move || {
+            provide_context(site_meta.clone());
+            // Inline scripts run only with this request's nonce; Leptos adds
+            // it to its own
+            provide_nonce();
+            let nonce = use_nonce();
+            if let (Some(nonce), false) = (&nonce, static_export) {
+                security::set_policy(&security, nonce);
+            }
+            view! {
+                <!DOCTYPE html>
+                // `lang` is set by the app from the page locale
+                <html>
+                    <head>
+                        <meta charset="utf-8"/>
+                        <meta name="viewport" content="width=device-width, initial-scale=1"/>
+                        <link rel="icon" href=asset!("/assets/favicon.ico")/>
+                        <AutoReload options=leptos_options.clone() />
+                        <HydrationScripts options=leptos_options.clone()/>
+                        <HashedStylesheet options=leptos_options.clone() id="leptos"/>
+                        <MetaTags/>
+                        {watch_content.then(|| view! { <script nonce=nonce.clone() inner_html=live_reload::CLIENT_SCRIPT></script> })}
+                        {static_export.then(|| view! { <script nonce=nonce.clone() inner_html=export::CLIENT_SCRIPT></script> })}
+                    </head>
+                    <body>
+                        <App/>
+                    </body>
+                </html>
+            }
+            .into_any()
+        }
+    }
+}
+
+#[actix_web::get("favicon.ico")]
+async fn favicon(
+    leptos_options: web::Data<LeptosOptions>,
+) -> actix_web::Result<actix_files::NamedFile> {
+    let leptos_options = leptos_options.into_inner();
+    let site_root = &leptos_options.site_root;
+    Ok(actix_files::NamedFile::open(format!(
+        "{site_root}/favicon.ico"
+    ))?)
+}
@@ -0,0 +1,258 @@
+//! `/sitemap.xml` and `/robots.txt`.
+//!
+//! Static pages come from the Leptos route list (`generate_route_list`), dynamic
+//! pages from the content store (projects, posts and tags). Every page is
+//! listed once per locale, with `hreflang` links to the other versions.
+
+use std::fmt::Write;
+
+use actix_web::http::header;
+use actix_web::{get, web, HttpResponse};
+use chrono::NaiveDate;
+
+use super::{xml_escape, SiteUrl};
+use crate::content::store::{self, Content};
+use crate::content::{slugify, tag_path, Project};
+use crate::i18n::Locale;
+
+/// Static paths of the app, extracted once from the route list.
+#[derive(Clone, Debug, Default)]
+pub struct SitemapRoutes(Vec<String>);
+
+impl SitemapRoutes {
+    /// Keeps the routes without parameters or wildcards (`/projetos/{slug}`,
+    /// `/{any:.*}`); their concrete URLs come from the content instead. Locale
+    /// prefixes are stripped, the sitemap adds them back per locale. The admin
+    /// pages are left out.
+    pub fn new<'a>(paths: impl IntoIterator<Item = &'a str>) -> Self {
+        let mut paths = paths
+            .into_iter()
+            .filter(|path| !path.contains('{') && !path.contains('*'))
+            .filter(|path| *path != "/admin" && !path.starts_with("/admin/"))
+            .map(|path| {
+                if path.is_empty() {
+                    "/".to_string()
+                } else {
+                    Locale::strip_prefix(path).to_string()
+                }
+            })
+            .collect::<Vec<_>>();
+        paths.sort();
+        paths.dedup();
+        SitemapRoutes(paths)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct RobotsConfig {
+    /// When false every crawler is asked to stay away (staging mirrors).
+    pub allow_indexing: bool,
+    pub disallow: Vec<String>,
+}
+
+impl Default for RobotsConfig {
+    fn default() -> Self {
+        RobotsConfig {
+            allow_indexing: true,
+            disallow: vec!["/api/".to_string(), "/admin".to_string()],
+        }
+    }
+}
+
+#[derive(Default)]
+struct Media {
+    images: Vec<String>,
+    videos: Vec<Video>,
+}
+
+struct Video {
+    title: String,
+    description: String,
+    thumbnail: String,
+    content: String,
+}
+
+struct Entry {
+    path: String,
+    lastmod: Option<NaiveDate>,
+    media: Media,
+}
+
+/// Alternate language versions of a page as `(hreflang, path)` pairs.
+fn alternates(path: &str) -> Vec<(&'static str, String)> {
+    Locale::ALL
+        .into_iter()
+        .map(|locale| (locale.tag(), locale.localize(path)))
+        .chain([("x-default", Locale::default().localize(path))])
+        .collect()
+}
+
+fn project_media(project: &Project) -> Media {
+    let mut images = project
+        .gallery
+        .iter()
+        .map(|item| item.src.clone())
+        .collect::<Vec<_>>();
+    // Video entries need a thumbnail, so the poster doubles as one
+    let videos = match (&project.poster, project.videos.first()) {
+        (Some(poster), Some(video)) => vec![Video {
+            title: project.title.clone(),
+            description: project.summary.clone(),
+            thumbnail: poster.clone(),
+            content: video.src.clone(),
+        }],
+        (Some(poster), None) => {
+            images.insert(0, poster.clone());
+            Vec::new()
+        }
+        _ => Vec::new(),
+    };
+    Media { images, videos }
+}
+
+fn entries(content: &Content, routes: &SitemapRoutes) -> Vec<Entry> {
+    let newest_post = content.published_posts().map(|p| p.date).max();
+    let newest_project = content.projects.iter().filter_map(|p| p.date).max();
+
+    let mut entries = routes
+        .0
+        .iter()
+        .map(|path| {
+            let (lastmod, media) = match path.as_str() {
+                // The home page carries the project showcase
+                "/" => {
+                    let media = content.projects.iter().map(project_media).fold(
+                        Media::default(),
+                        |mut acc, media| {
+                            acc.images.extend(media.images);
+                            acc.videos.extend(media.videos);
+                            acc
+                        },
+                    );
+                    (newest_post.max(newest_project), media)
+                }
+                "/blog" => (newest_post, Media::default()),
+                _ => (None, Media::default()),
+            };
+            Entry {
+                path: path.clone(),
+                lastmod,
+                media,
+            }
+        })
+        .collect::<Vec<_>>();
+
+    entries.extend(content.projects.iter().map(|project| Entry {
+        path: project.path(),
+        lastmod: project.date,
+        media: project_media(project),
+    }));
+    entries.extend(content.published_posts().map(|post| Entry {
+        path: post.path(),
+        lastmod: Some(post.date),
+        media: Media::default(),
+    }));
+
+    let mut tags = content
+        .published_posts()
+        .flat_map(|post| {
+            post.tags
+                .iter()
+                .map(move |tag| (slugify(tag), tag, post.date))
+        })
+        .collect::<Vec<_>>();
+    // Newest date first within each tag, so dedup keeps it
+    tags.sort_by(|a, b| a.0.cmp(&b.0).then(b.2.cmp(&a.2)));
+    tags.dedup_by(|a, b| a.0 == b.0);
+    entries.extend(tags.into_iter().map(|(_, tag, date)| Entry {
+        path: tag_path(tag),
+        lastmod: Some(date),
+        media: Media::default(),
+    }));
+
+    entries
+}
+
+#[get("/sitemap.xml")]
+pub async fn sitemap(
+    site: web::Data<SiteUrl>,
+    routes: web::Data<SitemapRoutes>,
+) -> actix_web::Result<HttpResponse> {
+    let mut xml = String::from(concat!(
+        r#"<?xml version="1.0" encoding="utf-8"?>"#,
+        "\n",
+        r#"<urlset xmlns="http://www.sitemaps.org/schemas/sitemap/0.9" xmlns:xhtml="http://www.w3.org/1999/xhtml" xmlns:image="http://www.google.com/schemas/sitemap-image/1.1" xmlns:video="http://www.google.com/schemas/sitemap-video/1.1">"#,
+        "\n",
+    ));
+
+    for locale in Locale::ALL {
+        let content =
+            store::get_localized(locale).map_err(actix_web::error::ErrorInternalServerError)?;
+        for entry in entries(&content, &routes) {
+            write_entry(&mut xml, &site, locale, &entry);
+        }
+    }
+    xml.push_str("</urlset>\n");
+
+    Ok(HttpResponse::Ok()
+        .content_type("application/xml; charset=utf-8")
+        .insert_header((header::CACHE_CONTROL, "public, max-age=3600"))
+        .body(xml))
+}
+
+fn write_entry(xml: &mut String, site: &SiteUrl, locale: Locale, entry: &Entry) {
+    let _ = writeln!(
+        xml,
+        "<url>\n<loc>{}</loc>",
+        xml_escape(&site.join(&locale.localize(&entry.path)))
+    );
+    if let Some(lastmod) = entry.lastmod {
+        let _ = writeln!(xml, "<lastmod>{}</lastmod>", lastmod.format("%Y-%m-%d"));
+    }
+    for (lang, path) in alternates(&entry.path) {
+        let _ = writeln!(
+            xml,
+            r#"<xhtml:link rel="alternate" hreflang="{lang}" href="{}"/>"#,
+            xml_escape(&site.join(&path))
+        );
+    }
+    for image in &entry.media.images {
+        let _ = writeln!(
+            xml,
+            "<image:image><image:loc>{}</image:loc></image:image>",
+            xml_escape(&site.join(image))
+        );
+    }
+    for video in &entry.media.videos {
+        let _ = writeln!(
+            xml,
+            "<video:video>\n<video:thumbnail_loc>{}</video:thumbnail_loc>\n<video:title>{}</video:title>\n<video:description>{}</video:description>\n<video:content_loc>{}</video:content_loc>\n</video:video>",
+            xml_escape(&site.join(&video.thumbnail)),
+            xml_escape(&video.title),
+            xml_escape(&video.description),
+            xml_escape(&site.join(&video.content)),
+        );
+    }
+    xml.push_str("</url>\n");
+}
+
+#[get("/robots.txt")]
+pub async fn robots(site: web::Data<SiteUrl>, config: web::Data<RobotsConfig>) -> HttpResponse {
+    let mut body = String::from("User-agent: *\n");
+    if config.allow_indexing {
+        for path in &config.disallow {
+            let _ = writeln!(body, "Disallow: {path}");
+        }
+        if config.disallow.is_empty() {
+            body.push_str("Disallow:\n");
+        }
+    } else {
+        body.push_str("Disallow: /\n");
+    }
+    let _ = writeln!(body, "\nSitemap: {}", site.join("/sitemap.xml"));
+
+    HttpResponse::Ok()
+        .content_type("text/plain; charset=utf-8")
+        .insert_header((header::CACHE_CONTROL, "public, max-age=86400"))
+        .body(body)
+}
@@ -0,0 +1,313 @@
+//! Running under systemd: listening sockets passed by socket activation
+//! (`LISTEN_FDS`), readiness, watchdog and shutdown notifications over
+//! `NOTIFY_SOCKET`, and a graceful stop on SIGTERM.
+//!
+//! Everything is driven by the environment systemd sets, so it can be tried
+//! without systemd: `systemd-socket-activate` passes a socket, and any Unix
+//! datagram socket can stand in for `NOTIFY_SOCKET` (see the README). Outside
+//! systemd, and on other platforms, all of it does nothing.
+
+use std::ffi::OsString;
+use std::io;
+use std::net::TcpListener;
+use std::time::Duration;
+
+use actix_web::dev::ServerHandle;
+
+/// First descriptor passed by systemd; the others follow.
+#[cfg(unix)]
+const LISTEN_FDS_START: i32 = 3;
+
+/// Sockets passed by systemd socket activation, empty when there are none.
+/// systemd keeps them open across restarts, so connections arriving while
+/// the service restarts wait in the backlog instead of being refused.
+pub fn listeners() -> io::Result<Vec<TcpListener>> {
+    listeners_with(|name| std::env::var(name).ok())
+}
+
+/// [`listeners`], reading the variables through `var`.
+fn listeners_with(var: impl Fn(&str) -> Option<String>) -> io::Result<Vec<TcpListener>> {
+    let number = |name| var(name).and_then(|v| v.trim().parse::<u32>().ok());
+    // Meant for this process only, not for one it was started from
+    if number("LISTEN_PID") != Some(std::process::id()) {
+        return Ok(Vec::new());
+    }
+    let count = number("LISTEN_FDS").unwrap_or(0);
+    listen_fds(count)
+}
+
+#[cfg(unix)]
+fn listen_fds(count: u32) -> io::Result<Vec<TcpListener>> {
+    use std::os::fd::FromRawFd;
+
+    (0..count as i32)
+        .map(|i| {
+            // SAFETY: systemd hands descriptors 3.. to this process, and
+            // nothing else in it takes ownership of them
+            let listener = unsafe { TcpListener::from_raw_fd(LISTEN_FDS_START + i) };
+            // Fails unless the descriptor is a TCP socket
+            listener.local_addr().map_err(|e| {
+                io::Error::new(e.kind(), format!("LISTEN_FDS socket {i} is not TCP: {e}"))
+            })?;
+            listener.set_nonblocking(true)?;
+            Ok(listener)
+        })
+        .collect()
+}
+
+#[cfg(not(unix))]
+fn listen_fds(_count: u32) -> io::Result<Vec<TcpListener>> {
+    Ok(Vec::new())
+}
+
+/// Half of the watchdog timeout systemd expects pings within, if it set one
+/// for this process (`WatchdogSec=` in the unit).
+pub fn watchdog_interval() -> Option<Duration> {
+    watchdog_interval_with(|name| std::env::var(name).ok())
+}
+
+/// [`watchdog_interval`], reading the variables through `var`.
+fn watchdog_interval_with(var: impl Fn(&str) -> Option<String>) -> Option<Duration> {
+    let number = |name| var(name).and_then(|v| v.trim().parse::<u64>().ok());
+    if number("WATCHDOG_PID").is_some_and(|pid| pid != u64::from(std::process::id())) {
+        return None;
+    }
+    number("WATCHDOG_USEC")
+        .filter(|usec| *usec > 0)
+        .map(|usec| Duration::from_micros(usec / 2))
+}
+
+/// Sends state changes to the service manager.
+#[derive(Clone, Debug, Default)]
+pub struct Notifier {
+    /// Path of the datagram socket, or `@name` for an abstract one (Linux).
+    socket: Option<OsString>,
+}
+
+impl Notifier {
+    pub fn new(socket: Option<OsString>) -> Self {
+        Notifier {
+            socket: socket.filter(|path| !path.is_empty()),
+        }
+    }
+
+    /// Notifies `NOTIFY_SOCKET`, if systemd set it.
+    pub fn from_env() -> Self {
+        Notifier::new(std::env::var_os("NOTIFY_SOCKET"))
+    }
+
+    /// Sends `state`, newline-separated `KEY=value` pairs such as `READY=1`.
+    #[cfg(unix)]
+    pub fn notify(&self, state: &str) -> io::Result<()> {
+        use std::os::unix::net::UnixDatagram;
+
+        let Some(path) = &self.socket else {
+            return Ok(());
+        };
+        let socket = UnixDatagram::unbound()?;
+        #[cfg(target_os = "linux")]
+        if let Some(name) = path.to_str().and_then(|path| path.strip_prefix('@')) {
+            use std::os::linux::net::SocketAddrExt;
+            use std::os::unix::net::SocketAddr;
+
+            let addr = SocketAddr::from_abstract_name(name)?;
+            return socket.send_to_addr(state.as_bytes(), &addr).map(|_| ());
+        }
+        socket.send_to(state.as_bytes(), path).map(|_| ())
+    }
+
+    #[cfg(not(unix))]
+    pub fn notify(&self, _state: &str) -> io::Result<()> {
+        Ok(())
+    }
+
+    /// [`notify`](Self::notify), logging failures: systemd not hearing from
+    /// the server should not stop it.
+    pub fn send(&self, state: &str) {
+        if let Err(e) = self.notify(state) {
+            tracing::warn!("sd_notify {:?}: {e}", state.lines().next().unwrap_or_default());
+        }
+    }
+
+    /// Pings the watchdog every `interval`, for as long as the runtime it is
+    /// spawned on runs. On the main runtime that says nothing about the HTTP
+    /// workers, which run on their own threads.
+    pub async fn watchdog(self, interval: Duration) {
+        let mut ticks = tokio::time::interval(interval);
+        loop {
+            ticks.tick().await;
+            self.send("WATCHDOG=1");
+        }
+    }
+}
+
+/// Stops the servers on a signal, one after the other. SIGTERM, which systemd
+/// sends, stops taking new connections and lets requests in flight finish
+/// within the shutdown timeout; Ctrl-C stops right away.
+pub async fn stop_on_signal(servers: Vec<ServerHandle>, notifier: Notifier) {
+    stop(servers, &notifier, shutdown_signal().await).await;
+}
+
+/// Tells systemd the service is stopping, then stops the servers.
+async fn stop(servers: Vec<ServerHandle>, notifier: &Notifier, graceful: bool) {
+    tracing::info!(graceful, "stopping");
+    notifier.send("STOPPING=1");
+    for server in servers {
+        server.stop(graceful).await;
+    }
+}
+
+/// Whether the stop should be graceful: `true` for SIGTERM.
+#[cfg(unix)]
+async fn shutdown_signal() -> bool {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let terminate = async {
+        match signal(SignalKind::terminate()) {
+            Ok(mut terminate) => {
+                terminate.recv().await;
+            }
+            Err(e) => {
+                tracing::error!("cannot listen for SIGTERM: {e}");
+                std::future::pending::<()>().await;
+            }
+        }
+    };
+    tokio::select! {
+        _ = terminate => true,
+        _ = tokio::signal::ctrl_c() => false,
+    }
+}
+
+#[cfg(not(unix))]
+async fn shutdown_signal() -> bool {
+    let _ = tokio::signal::ctrl_c().await;
+    false
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use std::collections::HashMap;
+    use std::os::unix::net::UnixDatagram;
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::server::{hex, random_bytes};
+
+    /// Variables as systemd would set them, without touching the process
+    /// environment the other tests share.
+    fn vars(pairs: &[(&str, String)]) -> impl Fn(&str) -> Option<String> {
+        let vars = pairs
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.clone()))
+            .collect::<HashMap<_, _>>();
+        move |name| vars.get(name).cloned()
+    }
+
+    fn own_pid() -> String {
+        std::process::id().to_string()
+    }
+
+    /// A socket standing in for systemd's, and the `NOTIFY_SOCKET` value
+    /// pointing at it.
+    fn socket_file() -> (UnixDatagram, PathBuf) {
+        let path = std::env::temp_dir().join(format!("notify-{}.sock", hex(&random_bytes()[..8])));
+        let socket = UnixDatagram::bind(&path).unwrap();
+        socket.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+        (socket, path)
+    }
+
+    fn receive(socket: &UnixDatagram) -> String {
+        let mut buffer = [0; 256];
+        let len = socket.recv(&mut buffer).unwrap();
+        String::from_utf8_lossy(&buffer[..len]).into_owned()
+    }
+
+    #[test]
+    fn notifies_a_socket_file() {
+        let (socket, path) = socket_file();
+        let notifier = Notifier::new(Some(path.clone().into()));
+        notifier.notify("READY=1\nSTATUS=Serving").unwrap();
+        assert_eq!(receive(&socket), "READY=1\nSTATUS=Serving");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn notifies_an_abstract_socket() {
+        use std::os::linux::net::SocketAddrExt;
+        use std::os::unix::net::SocketAddr;
+
+        let name = format!("notify-{}", hex(&random_bytes()[..8]));
+        let addr = SocketAddr::from_abstract_name(&name).unwrap();
+        let socket = UnixDatagram::bind_addr(&addr).unwrap();
+        socket.set_read_timeout(Some(Duration::from_secs(5))).unwrap();
+
+        let notifier = Notifier::new(Some(format!("@{name}").into()));
+        notifier.notify("READY=1").unwrap();
+        assert_eq!(receive(&socket), "READY=1");
+    }
+
+    #[test]
+    fn without_a_socket_nothing_is_sent() {
+        assert!(Notifier::new(None).notify("READY=1").is_ok());
+        assert!(Notifier::new(Some("".into())).notify("READY=1").is_ok());
+    }
+
+    #[actix_web::test]
+    async fn watchdog_pings() {
+        let (socket, path) = socket_file();
+        let notifier = Notifier::new(Some(path.clone().into()));
+        let pinging = notifier.watchdog(Duration::from_millis(10));
+        let _ = tokio::time::timeout(Duration::from_millis(100), pinging).await;
+        assert_eq!(receive(&socket), "WATCHDOG=1");
+        assert_eq!(receive(&socket), "WATCHDOG=1");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[actix_web::test]
+    async fn stopping_is_announced() {
+        let (socket, path) = socket_file();
+        let notifier = Notifier::new(Some(path.clone().into()));
+        stop(Vec::new(), &notifier, true).await;
+        assert_eq!(receive(&socket), "STOPPING=1");
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn listen_fds_for_another_process_are_ignored() {
+        let other = (std::process::id() + 1).to_string();
+        // Taking descriptor 3 here would be wrong, so nothing may be opened
+        let listeners = listeners_with(vars(&[("LISTEN_PID", other), ("LISTEN_FDS", "1".into())]));
+        assert!(listeners.unwrap().is_empty());
+        let listeners = listeners_with(vars(&[("LISTEN_FDS", "1".into())]));
+        assert!(listeners.unwrap().is_empty());
+    }
+
+    #[test]
+    fn no_listen_fds() {
+        let listeners = listeners_with(vars(&[("LISTEN_PID", own_pid()), ("LISTEN_FDS", "0".into())]));
+        assert!(listeners.unwrap().is_empty());
+        let listeners = listeners_with(vars(&[("LISTEN_PID", own_pid())]));
+        assert!(listeners.unwrap().is_empty());
+    }
+
+    #[test]
+    fn watchdog_interval_is_half_the_timeout() {
+        let usec = ("WATCHDOG_USEC", "30000000".to_string());
+        let half = Some(Duration::from_secs(15));
+        assert_eq!(watchdog_interval_with(vars(&[usec.clone()])), half);
+        assert_eq!(watchdog_interval_with(vars(&[usec.clone(), ("WATCHDOG_PID", own_pid())])), half);
+        assert_eq!(watchdog_interval_with(vars(&[(usec.0, " 30000000 ".into())])), half);
+    }
+
+    #[test]
+    fn watchdog_interval_needs_a_timeout_for_this_process() {
+        let other = (std::process::id() + 1).to_string();
+        let usec = ("WATCHDOG_USEC", "30000000".to_string());
+        assert_eq!(watchdog_interval_with(vars(&[usec, ("WATCHDOG_PID", other)])), None);
+        assert_eq!(watchdog_interval_with(vars(&[("WATCHDOG_USEC", "0".into())])), None);
+        assert_eq!(watchdog_interval_with(vars(&[("WATCHDOG_USEC", "soon".into())])), None);
+        assert_eq!(watchdog_interval_with(vars(&[])), None);
+    }
+}
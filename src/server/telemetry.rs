@@ -0,0 +1,342 @@
+//! Logging with `tracing`: human-readable or JSON lines, a span with an ID
+//! for every request, access logs in Common or Combined Log Format,
+//! `Server-Timing` headers with the time spent rendering pages, and a log
+//! filter that can be changed while the
+//! server runs (see [`LogLevel`]).
+
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use actix_web::body::{BodySize, BoxBody, EitherBody, MessageBody};
+use actix_web::dev::{ServiceRequest, ServiceResponse};
+use actix_web::http::header::{self, HeaderName, HeaderValue};
+use actix_web::middleware::Next;
+use actix_web::{web, HttpMessage, HttpRequest};
+use chrono::Utc;
+use tracing::Instrument;
+use tracing_subscriber::layer::SubscriberExt;
+use tracing_subscriber::util::SubscriberInitExt;
+use tracing_subscriber::{fmt, reload, EnvFilter, Registry};
+
+use super::{client_ip, hex, metrics, random_bytes};
+
+/// Header carrying the request ID, taken from a proxy that set one.
+pub const REQUEST_ID_HEADER: &str = "x-request-id";
+/// Longest request ID accepted from a client.
+const MAX_REQUEST_ID: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LogFormat {
+    /// One readable line per event, for a terminal or the journal.
+    Pretty,
+    /// One JSON object per event, for log collectors.
+    Json,
+}
+
+impl LogFormat {
+    /// The accepted values, for error messages.
+    pub const EXPECTED: &str = "pretty or json";
+}
+
+impl std::str::FromStr for LogFormat {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "pretty" => Ok(LogFormat::Pretty),
+            "json" => Ok(LogFormat::Json),
+            _ => Err(format!("expected {}", LogFormat::EXPECTED)),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccessLog {
+    Off,
+    /// Common Log Format: client, user, time, request line, status, size.
+    Common,
+    /// Common plus `Referer` and `User-Agent`, as nginx and Apache log.
+    Combined,
+}
+
+impl AccessLog {
+    /// The accepted values, for error messages.
+    pub const EXPECTED: &str = "off, common or combined";
+}
+
+impl std::str::FromStr for AccessLog {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "off" => Ok(AccessLog::Off),
+            "common" => Ok(AccessLog::Common),
+            "combined" => Ok(AccessLog::Combined),
+            _ => Err(format!("expected {}", AccessLog::EXPECTED)),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct LogConfig {
+    pub format: LogFormat,
+    /// `EnvFilter` directives, such as `info` or `warn,nicolasalmino_site=debug`.
+    pub filter: String,
+    pub access_log: AccessLog,
+    /// Log the client address from `Forwarded` / `X-Forwarded-For`. Only
+    /// enable behind a reverse proxy that sets them.
+    pub trust_proxy: bool,
+}
+
+impl Default for LogConfig {
+    fn default() -> Self {
+        LogConfig {
+            format: LogFormat::Json,
+            filter: "info".to_string(),
+            access_log: AccessLog::Combined,
+            trust_proxy: false,
+        }
+    }
+}
+
+/// Changes the log filter of the running server; shared with the admin
+/// server functions through `web::Data`.
+#[derive(Clone)]
+pub struct LogLevel(reload::Handle<EnvFilter, Registry>);
+
+impl LogLevel {
+    /// The filter in effect.
+    pub fn get(&self) -> String {
+        self.0
+            .with_current(|filter| filter.to_string())
+            .unwrap_or_default()
+    }
+
+    /// Replaces the filter until the next restart.
+    pub fn set(&self, directives: &str) -> Result<(), String> {
+        let filter = EnvFilter::try_new(directives.trim()).map_err(|e| e.to_string())?;
+        self.0.reload(filter).map_err(|e| e.to_string())?;
+        tracing::info!(filter = directives.trim(), "log filter changed");
+        Ok(())
+    }
+}
+
+/// Installs the global subscriber. Call once, early in `main`.
+pub fn init(config: &LogConfig) -> Result<LogLevel, String> {
+    let filter = EnvFilter::try_new(&config.filter).map_err(|e| e.to_string())?;
+    let (filter, handle) = reload::Layer::new(filter);
+    let json = config.format == LogFormat::Json;
+    tracing_subscriber::registry()
+        .with(filter)
+        .with(json.then(|| fmt::layer().json().with_current_span(true).with_span_list(false)))
+        .with((!json).then(fmt::layer))
+        .try_init()
+        .map_err(|e| e.to_string())?;
+    Ok(LogLevel(handle))
+}
+
+/// The span of a request, with its ID, in the request's extensions, for
+/// work that runs outside the middleware's future, such as streaming a page.
+#[derive(Clone)]
+struct RequestSpan(tracing::Span);
+
+/// Parts of the response time, sent as `Server-Timing`.
+#[derive(Default)]
+struct Timings(Vec<(&'static str, &'static str, Duration)>);
+
+/// Adds a part to the request's `Server-Timing` header.
+pub fn record_timing(req: &HttpRequest, name: &'static str, description: &'static str, duration: Duration) {
+    if let Some(timings) = req.extensions_mut().get_mut::<Timings>() {
+        timings.0.push((name, description, duration));
+    }
+}
+
+fn server_timing(timings: &[(&'static str, &'static str, Duration)], total: Duration) -> String {
+    let entry = |name: &str, description: &str, duration: Duration| {
+        format!("{name};dur={:.1};desc=\"{description}\"", duration.as_secs_f64() * 1000.0)
+    };
+    timings
+        .iter()
+        .map(|(name, description, duration)| entry(name, description, *duration))
+        .chain([entry("total", "Total", total)])
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// A client's ID is kept only when it is short and plain, so it cannot
+/// forge log lines.
+fn incoming_id(req: &ServiceRequest) -> Option<String> {
+    let id = req.headers().get(REQUEST_ID_HEADER)?.to_str().ok()?;
+    let plain = !id.is_empty()
+        && id.len() <= MAX_REQUEST_ID
+        && id.bytes().all(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'_' | b'.'));
+    plain.then(|| id.to_string())
+}
+
+/// One access log line, without a trailing newline. `size` is the body
+/// before `Compress`, so it is the uncompressed size of the page.
+fn access_line(format: AccessLog, trust_proxy: bool, req: &HttpRequest, status: u16, size: BodySize) -> String {
+    let quoted = |name| {
+        req.headers()
+            .get(name)
+            .and_then(|value: &HeaderValue| value.to_str().ok())
+            .unwrap_or("-")
+            .replace('"', "\\\"")
+    };
+    let target = match req.query_string() {
+        "" => req.path().to_string(),
+        query => format!("{}?{query}", req.path()),
+    };
+    let size = match size {
+        BodySize::Sized(bytes) => bytes.to_string(),
+        BodySize::None | BodySize::Stream => "-".to_string(),
+    };
+    let mut line = format!(
+        "{} - - [{}] \"{} {} {:?}\" {status} {size}",
+        client_ip(req, trust_proxy).map_or_else(|| "-".to_string(), |ip| ip.to_string()),
+        Utc::now().format("%d/%b/%Y:%H:%M:%S %z"),
+        req.method(),
+        target.replace('"', "%22"),
+        req.version(),
+    );
+    if format == AccessLog::Combined {
+        line.push_str(&format!(
+            " \"{}\" \"{}\"",
+            quoted(header::REFERER),
+            quoted(header::USER_AGENT)
+        ));
+    }
+    line
+}
+
+/// Gives every request an ID (`X-Request-Id`, echoed in the response) and a
+/// span carrying it, so everything logged while handling it, SSR and server
+/// functions included, can be told apart. Also writes the access log and the
+/// `Server-Timing` header. Register with
+/// `middleware::from_fn(telemetry::requests)`, outside the other middleware
+/// so their time is counted.
+pub async fn requests(
+    req: ServiceRequest,
+    next: Next<impl MessageBody>,
+) -> Result<ServiceResponse<impl MessageBody>, actix_web::Error> {
+    let start = Instant::now();
+    let id = incoming_id(&req).unwrap_or_else(|| hex(&random_bytes()[..8]));
+    let span = tracing::info_span!(
+        "request",
+        id = %id,
+        method = %req.method(),
+        path = %req.path(),
+    );
+    req.extensions_mut().insert(RequestSpan(span.clone()));
+    req.extensions_mut().insert(Timings::default());
+    let (access_log, trust_proxy) = req
+        .app_data::<web::Data<LogConfig>>()
+        .map_or((AccessLog::Off, false), |config| (config.access_log, config.trust_proxy));
+
+    let mut res = next.call(req).instrument(span.clone()).await?;
+    let elapsed = start.elapsed();
+
+    let timing = res
+        .request()
+        .extensions()
+        .get::<Timings>()
+        .map(|timings| server_timing(&timings.0, elapsed));
+    let headers = res.headers_mut();
+    if let Ok(id) = HeaderValue::from_str(&id) {
+        headers.insert(HeaderName::from_static(REQUEST_ID_HEADER), id);
+    }
+    if let Some(Ok(timing)) = timing.map(|timing| HeaderValue::from_str(&timing)) {
+        headers.insert(HeaderName::from_static("server-timing"), timing);
+    }
+    if access_log != AccessLog::Off {
+        let line = access_line(
+            access_log,
+            trust_proxy,
+            res.request(),
+            res.status().as_u16(),
+            res.response().body().size(),
+        );
+        span.in_scope(|| {
+            tracing::info!(
+                target: "access",
+                status = res.status().as_u16(),
+                duration_ms = elapsed.as_secs_f64() * 1000.0,
+                "{line}"
+            )
+        });
+    }
+    Ok(res)
+}
+
+/// A page body that adds the time it took to stream to the timings and the
+/// render metrics once its last chunk is out. Polled inside the request's
+/// span, so what the suspended parts log carries the request ID.
+pub struct Rendering {
+    body: BoxBody,
+    req: HttpRequest,
+    span: tracing::Span,
+    head: Duration,
+    start: Instant,
+    done: bool,
+}
+
+impl MessageBody for Rendering {
+    type Error = <BoxBody as MessageBody>::Error;
+
+    fn size(&self) -> BodySize {
+        self.body.size()
+    }
+
+    fn poll_next(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<web::Bytes, Self::Error>>> {
+        let this = self.get_mut();
+        let _entered = this.span.enter();
+        let next = Pin::new(&mut this.body).poll_next(cx);
+        if matches!(next, Poll::Ready(None)) && !this.done {
+            this.done = true;
+            let streamed = this.start.elapsed();
+            record_timing(&this.req, "stream", "SSR streaming", streamed);
+            metrics::record_render(&this.req, this.head + streamed);
+        }
+        next
+    }
+}
+
+/// Times the rendering of pages: the shell (`ssr`), then the suspended parts
+/// Leptos streams after it (`stream`). Register with
+/// `middleware::from_fn(telemetry::render)` inside `caching::headers`, which
+/// reads the whole page before [`requests`] sends `Server-Timing`.
+pub async fn render(
+    req: ServiceRequest,
+    next: Next<impl MessageBody + 'static>,
+) -> Result<ServiceResponse<EitherBody<impl MessageBody, Rendering>>, actix_web::Error> {
+    let start = Instant::now();
+    let res = next.call(req).await?;
+    let head = start.elapsed();
+    let is_html = res
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|value| value.to_str().ok())
+        .is_some_and(|value| value.starts_with("text/html"));
+    if !is_html {
+        return Ok(res.map_into_left_body());
+    }
+    record_timing(res.request(), "ssr", "SSR shell", head);
+    let req = res.request().clone();
+    let span = req
+        .extensions()
+        .get::<RequestSpan>()
+        .map_or_else(tracing::Span::current, |span| span.0.clone());
+    let res = res.map_body(|_, body| Rendering {
+        body: body.boxed(),
+        req,
+        span,
+        head,
+        start: Instant::now(),
+        done: false,
+    });
+    Ok(res.map_into_right_body())
+}
@@ -33,4 +33,13 @@ for i in "${!RESOLUTIONS[@]}"; do
     fi
 done
 
+# Poster: the video's thumbnail in the schema and the sitemap, and the
+# placeholder until it plays
+echo "Extracting the poster..."
+ffmpeg -y -ss 3 -i "$INPUT_FILE" -frames:v 1 -vf "scale=1280:-2" -q:v 3 "$OUTPUT_DIR/camara_poster.jpg"
+if [ $? -ne 0 ]; then
+    echo "✗ Failed to extract the poster"
+    exit 1
+fi
+
 echo "All transcoding complete!"
\ No newline at end of file